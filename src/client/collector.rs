@@ -0,0 +1,172 @@
+//! Collectors, used to await events from inside a handler
+
+use crate::{
+    models::gateway::events::{MessageCreate, MessageReactionAdd},
+    runtime,
+};
+
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    future::{self, Either},
+    lock::Mutex,
+    stream::StreamExt,
+};
+use std::time::Duration;
+
+type Filter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// A pending collector, it receives the events that match its filter
+/// until it collected max events or the receiver was dropped
+struct Collector<T> {
+    filter: Filter<T>,
+    remaining: usize,
+    sender: UnboundedSender<T>,
+}
+
+/// All the pending collectors of a session
+pub(crate) struct Collectors {
+    messages: Mutex<Vec<Collector<MessageCreate>>>,
+    reactions: Mutex<Vec<Collector<MessageReactionAdd>>>,
+}
+
+impl Collectors {
+    pub(crate) fn new() -> Self {
+        Collectors {
+            messages: Mutex::new(Vec::new()),
+            reactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Send a copy of the message to all collectors that are waiting for it
+    pub(crate) async fn feed_message(&self, event: &MessageCreate) {
+        feed(&self.messages, event).await;
+    }
+
+    /// Send a copy of the reaction to all collectors that are waiting for it
+    pub(crate) async fn feed_reaction(&self, event: &MessageReactionAdd) {
+        feed(&self.reactions, event).await;
+    }
+
+    pub(crate) async fn collect_messages<F>(&self, filter: F, max: usize, timeout: Duration) -> Vec<MessageCreate>
+    where
+        F: Fn(&MessageCreate) -> bool + Send + Sync + 'static,
+    {
+        collect(&self.messages, Box::new(filter), max, timeout).await
+    }
+
+    pub(crate) async fn collect_reactions<F>(
+        &self,
+        filter: F,
+        max: usize,
+        timeout: Duration,
+    ) -> Vec<MessageReactionAdd>
+    where
+        F: Fn(&MessageReactionAdd) -> bool + Send + Sync + 'static,
+    {
+        collect(&self.reactions, Box::new(filter), max, timeout).await
+    }
+}
+
+async fn feed<T: Clone>(collectors: &Mutex<Vec<Collector<T>>>, event: &T) {
+    let mut collectors = collectors.lock().await;
+
+    collectors.retain(|c| !c.sender.is_closed());
+    for collector in collectors.iter_mut() {
+        if (collector.filter)(event) && collector.sender.unbounded_send(event.clone()).is_ok() {
+            collector.remaining -= 1;
+        }
+    }
+    collectors.retain(|c| c.remaining > 0);
+}
+
+async fn collect<T>(collectors: &Mutex<Vec<Collector<T>>>, filter: Filter<T>, max: usize, timeout: Duration) -> Vec<T> {
+    if max == 0 {
+        return Vec::new();
+    }
+
+    let (sender, mut receiver) = mpsc::unbounded();
+    collectors.lock().await.push(Collector {
+        filter,
+        remaining: max,
+        sender,
+    });
+
+    // Events received before the timeout are returned anyway
+    let mut events = Vec::with_capacity(max);
+    let mut sleep = Box::pin(runtime::sleep(timeout));
+
+    while events.len() < max {
+        match future::select(receiver.next(), sleep).await {
+            Either::Left((Some(event), s)) => {
+                events.push(event);
+                sleep = s;
+            }
+            // The receiver is dropped here, so the collector will be removed at the next event
+            _ => break,
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    fn reaction(emoji: &str) -> MessageReactionAdd {
+        serde_json::from_value(json!({
+            "user_id": "80351110224678912",
+            "channel_id": "290926798999357250",
+            "message_id": "334385199974967042",
+            "emoji": {"id": null, "name": emoji}
+        }))
+        .unwrap()
+    }
+
+    fn add_collector(collectors: &Collectors, max: usize) -> mpsc::UnboundedReceiver<MessageReactionAdd> {
+        let (sender, receiver) = mpsc::unbounded();
+        block_on(collectors.reactions.lock()).push(Collector {
+            filter: Box::new(|r: &MessageReactionAdd| r.emoji.name.as_deref() == Some("🐼")),
+            remaining: max,
+            sender,
+        });
+
+        receiver
+    }
+
+    #[test]
+    fn feed_matching_events() {
+        let collectors = Collectors::new();
+        let mut receiver = add_collector(&collectors, 2);
+
+        block_on(collectors.feed_reaction(&reaction("🐼")));
+        block_on(collectors.feed_reaction(&reaction("🐻")));
+
+        let event = receiver.try_recv().unwrap();
+        assert_eq!(event.emoji.name.as_deref(), Some("🐼"));
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(block_on(collectors.reactions.lock()).len(), 1);
+    }
+
+    #[test]
+    fn remove_finished_collectors() {
+        let collectors = Collectors::new();
+        let _receiver = add_collector(&collectors, 1);
+        drop(add_collector(&collectors, 1));
+
+        block_on(collectors.feed_reaction(&reaction("🐼")));
+        assert!(block_on(collectors.reactions.lock()).is_empty());
+    }
+
+    #[test]
+    fn collect_nothing() {
+        let collectors = Collectors::new();
+        let events = block_on(collectors.collect_reactions(|_| true, 0, Duration::from_secs(60)));
+
+        assert!(events.is_empty());
+        assert!(block_on(collectors.reactions.lock()).is_empty());
+    }
+}
@@ -1,9 +1,45 @@
+use crate::models::gateway::Intents;
+
+use std::{env::consts::OS, time::Duration};
+
+/// Default number of events that can wait in the buffer until they are handled
+pub(crate) const DEFAULT_EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Default time after a disconnection where the session is resumed instead of starting a new one
+pub(crate) const DEFAULT_RESUME_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// What happens with a new event when the event buffer is full
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until there is space in the buffer, the gateway connection stops reading events
+    /// and sending heartbeats meanwhile, so Discord can close the connection
+    Block,
+    /// Remove the oldest event of the buffer, so the newest events are kept
+    DropOldest,
+    /// Close the connection with [`PandaError::EventBufferFull`], the client reconnects
+    ///
+    /// [`PandaError::EventBufferFull`]: ../../enum.PandaError.html#variant.EventBufferFull
+    Error,
+}
+
 /// Config contains all customizable options of the Client
 pub struct Config {
     pub(crate) gateway_large_treshold: u8,
     pub(crate) gateway_guilds_subscriptions: bool,
     pub(crate) gateway_shard_id: u64,
     pub(crate) gateway_num_shards: u64,
+    pub(crate) gateway_identify_os: String,
+    pub(crate) gateway_identify_browser: String,
+    pub(crate) gateway_identify_device: String,
+    pub(crate) gateway_intents: Option<Intents>,
+    pub(crate) gateway_event_buffer_size: usize,
+    pub(crate) gateway_overflow_policy: OverflowPolicy,
+    pub(crate) gateway_drop_replayed_events: bool,
+    pub(crate) gateway_chunk_large_guilds: bool,
+    pub(crate) gateway_skip_guild_members: bool,
+    pub(crate) gateway_resume_timeout: Duration,
+    pub(crate) http_api_version: Option<String>,
+    pub(crate) http_strict_validation: bool,
 }
 
 impl Config {
@@ -17,6 +53,18 @@ impl Config {
             gateway_guilds_subscriptions: true,
             gateway_shard_id: 0,
             gateway_num_shards: 1,
+            gateway_identify_os: OS.into(),
+            gateway_identify_browser: "panda".into(),
+            gateway_identify_device: "panda".into(),
+            gateway_intents: None,
+            gateway_event_buffer_size: DEFAULT_EVENT_BUFFER_SIZE,
+            gateway_overflow_policy: OverflowPolicy::DropOldest,
+            gateway_drop_replayed_events: false,
+            gateway_chunk_large_guilds: false,
+            gateway_skip_guild_members: false,
+            gateway_resume_timeout: DEFAULT_RESUME_TIMEOUT,
+            http_api_version: None,
+            http_strict_validation: false,
         }
     }
 }
@@ -26,6 +74,18 @@ pub struct ConfigBuilder {
     pub(crate) gateway_guilds_subscriptions: bool,
     pub(crate) gateway_shard_id: u64,
     pub(crate) gateway_num_shards: u64,
+    pub(crate) gateway_identify_os: String,
+    pub(crate) gateway_identify_browser: String,
+    pub(crate) gateway_identify_device: String,
+    pub(crate) gateway_intents: Option<Intents>,
+    pub(crate) gateway_event_buffer_size: usize,
+    pub(crate) gateway_overflow_policy: OverflowPolicy,
+    pub(crate) gateway_drop_replayed_events: bool,
+    pub(crate) gateway_chunk_large_guilds: bool,
+    pub(crate) gateway_skip_guild_members: bool,
+    pub(crate) gateway_resume_timeout: Duration,
+    pub(crate) http_api_version: Option<String>,
+    pub(crate) http_strict_validation: bool,
 }
 
 impl ConfigBuilder {
@@ -35,6 +95,18 @@ impl ConfigBuilder {
             gateway_guilds_subscriptions: true,
             gateway_shard_id: 0,
             gateway_num_shards: 1,
+            gateway_identify_os: OS.into(),
+            gateway_identify_browser: "panda".into(),
+            gateway_identify_device: "panda".into(),
+            gateway_intents: None,
+            gateway_event_buffer_size: DEFAULT_EVENT_BUFFER_SIZE,
+            gateway_overflow_policy: OverflowPolicy::DropOldest,
+            gateway_drop_replayed_events: false,
+            gateway_chunk_large_guilds: false,
+            gateway_skip_guild_members: false,
+            gateway_resume_timeout: DEFAULT_RESUME_TIMEOUT,
+            http_api_version: None,
+            http_strict_validation: false,
         }
     }
 
@@ -62,6 +134,91 @@ impl ConfigBuilder {
         self
     }
 
+    /// Set the properties sent with IDENTIFY. Default os is the current OS,
+    /// and browser and device are "panda"
+    pub fn set_identify_properties(
+        mut self,
+        os: impl Into<String>,
+        browser: impl Into<String>,
+        device: impl Into<String>,
+    ) -> Self {
+        self.gateway_identify_os = os.into();
+        self.gateway_identify_browser = browser.into();
+        self.gateway_identify_device = device.into();
+
+        self
+    }
+
+    /// Set the gateway intents sent with IDENTIFY. Events outside of these intents are
+    /// ignored without being parsed. Default none, so all events are received
+    pub fn set_intents(mut self, intents: Intents) -> Self {
+        self.gateway_intents = Some(intents);
+
+        self
+    }
+
+    /// Set the number of events that can wait to be handled, and what happens with new events
+    /// when it's full. Default 1024 events, dropping the oldest ones
+    pub fn set_event_buffer(mut self, size: usize, policy: OverflowPolicy) -> Self {
+        self.gateway_event_buffer_size = size;
+        self.gateway_overflow_policy = policy;
+
+        self
+    }
+
+    /// Drop the events that were already received, Discord can send them again after resuming
+    /// the session, so they would be handled twice. Default false
+    pub fn set_drop_replayed_events(mut self, drop: bool) -> Self {
+        self.gateway_drop_replayed_events = drop;
+
+        self
+    }
+
+    /// Request all the members of a large guild (above the large threshold) when its GUILD_CREATE
+    /// is received, the members are received with GUILD_MEMBERS_CHUNK events.
+    /// It needs the GUILD_MEMBERS intent. Default false, so no members are requested
+    pub fn set_chunk_large_guilds(mut self, chunk: bool) -> Self {
+        self.gateway_chunk_large_guilds = chunk;
+
+        self
+    }
+
+    /// Don't parse the members, presences and voice states of GUILD_CREATE, the guild is
+    /// received with empty `members`. Large guilds send thousands of members, it saves memory
+    /// and CPU for bots that don't use them. Default false
+    pub fn set_skip_guild_members(mut self, skip: bool) -> Self {
+        self.gateway_skip_guild_members = skip;
+
+        self
+    }
+
+    /// Set how long after a disconnection the session can be resumed, when the connection is
+    /// down for longer a new session is started with IDENTIFY. Discord keeps the sessions a few
+    /// minutes, default 5 minutes
+    pub fn set_resume_timeout(mut self, timeout: Duration) -> Self {
+        self.gateway_resume_timeout = timeout;
+
+        self
+    }
+
+    /// Set the API version used by the http client, like `v6`. It's validated when the
+    /// config is set to the client. Default v6
+    pub fn set_api_version(mut self, version: impl Into<String>) -> Self {
+        self.http_api_version = Some(version.into());
+
+        self
+    }
+
+    /// Check the required fields and the Discord limits of the outbound messages before sending
+    /// them, see [`HttpClient::set_strict_validation`]. Default false
+    ///
+    /// [`HttpClient::set_strict_validation`]: ../../struct.HttpClient.html#method.set_strict_validation
+    pub fn set_strict_validation(mut self, strict: bool) -> Self {
+        self.http_strict_validation = strict;
+
+        self
+    }
+
     /// Build a Config struct
     pub fn build(self) -> Config {
         Config {
@@ -69,6 +226,51 @@ impl ConfigBuilder {
             gateway_guilds_subscriptions: self.gateway_guilds_subscriptions,
             gateway_shard_id: self.gateway_shard_id,
             gateway_num_shards: self.gateway_num_shards,
+            gateway_identify_os: self.gateway_identify_os,
+            gateway_identify_browser: self.gateway_identify_browser,
+            gateway_identify_device: self.gateway_identify_device,
+            gateway_intents: self.gateway_intents,
+            gateway_event_buffer_size: self.gateway_event_buffer_size,
+            gateway_overflow_policy: self.gateway_overflow_policy,
+            gateway_drop_replayed_events: self.gateway_drop_replayed_events,
+            gateway_chunk_large_guilds: self.gateway_chunk_large_guilds,
+            gateway_skip_guild_members: self.gateway_skip_guild_members,
+            gateway_resume_timeout: self.gateway_resume_timeout,
+            http_api_version: self.http_api_version,
+            http_strict_validation: self.http_strict_validation,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_identify_properties() {
+        let config = Config::new().build();
+        let default = Config::new_default();
+
+        assert_eq!(config.gateway_identify_os, OS);
+        assert_eq!(config.gateway_identify_browser, default.gateway_identify_browser);
+        assert_eq!(config.gateway_identify_device, default.gateway_identify_device);
+    }
+
+    #[test]
+    fn set_identify_properties() {
+        let config = Config::new().set_identify_properties("linux", "my bot", "my device").build();
+
+        assert_eq!(config.gateway_identify_os, "linux");
+        assert_eq!(config.gateway_identify_browser, "my bot");
+        assert_eq!(config.gateway_identify_device, "my device");
+    }
+
+    #[test]
+    fn resume_timeout() {
+        assert_eq!(Config::new().build().gateway_resume_timeout, Duration::from_secs(300));
+        assert_eq!(Config::new_default().gateway_resume_timeout, DEFAULT_RESUME_TIMEOUT);
+
+        let config = Config::new().set_resume_timeout(Duration::from_secs(30)).build();
+        assert_eq!(config.gateway_resume_timeout, Duration::from_secs(30));
+    }
+}
@@ -19,6 +19,17 @@ macro_rules! event_trait {
 
 // READY function trait
 type ReadyFn<S> = event_trait!(Ready);
+type ReconnectedFn<S> = event_trait!(Reconnected);
+
+// RAW function trait
+#[cfg(feature = "raw-events")]
+type RawEventFn<S> = event_trait!(RawEvent);
+
+// AUTO MODERATION functions trait
+type AutoModerationRuleCreateFn<S> = event_trait!(AutoModerationRuleCreate);
+type AutoModerationRuleUpdateFn<S> = event_trait!(AutoModerationRuleUpdate);
+type AutoModerationRuleDeleteFn<S> = event_trait!(AutoModerationRuleDelete);
+type AutoModerationActionExecutionFn<S> = event_trait!(AutoModerationActionExecution);
 
 // CHANNEL functions trait
 type ChannelCreateFn<S> = event_trait!(ChannelCreate);
@@ -42,6 +53,9 @@ type GuildRoleCreateFn<S> = event_trait!(GuildRoleCreate);
 type GuildRoleUpdateFn<S> = event_trait!(GuildRoleUpdate);
 type GuildRoleDeleteFn<S> = event_trait!(GuildRoleDelete);
 
+// INTERACTION functions trait
+type InteractionCreateFn<S> = event_trait!(InteractionCreate);
+
 // MESSAGE functions trait
 type MessageCreateFn<S> = event_trait!(MessageCreate);
 type MessageUpdateFn<S> = event_trait!(MessageUpdate);
@@ -62,6 +76,17 @@ type OptionBox<T> = Option<Box<T>>;
 /// This struct it's where all functions created by the user will be saved
 pub(crate) struct EventHandler<S> {
     pub(crate) ready: OptionBox<ReadyFn<S>>,
+    pub(crate) reconnected: OptionBox<ReconnectedFn<S>>,
+
+    // Raw
+    #[cfg(feature = "raw-events")]
+    pub(crate) raw_event: OptionBox<RawEventFn<S>>,
+
+    // Auto moderation
+    pub(crate) auto_moderation_rule_create: OptionBox<AutoModerationRuleCreateFn<S>>,
+    pub(crate) auto_moderation_rule_update: OptionBox<AutoModerationRuleUpdateFn<S>>,
+    pub(crate) auto_moderation_rule_delete: OptionBox<AutoModerationRuleDeleteFn<S>>,
+    pub(crate) auto_moderation_action_execution: OptionBox<AutoModerationActionExecutionFn<S>>,
 
     // Channel
     pub(crate) channel_create: OptionBox<ChannelCreateFn<S>>,
@@ -85,6 +110,9 @@ pub(crate) struct EventHandler<S> {
     pub(crate) guild_role_update: OptionBox<GuildRoleUpdateFn<S>>,
     pub(crate) guild_role_delete: OptionBox<GuildRoleDeleteFn<S>>,
 
+    // Interaction
+    pub(crate) interaction_create: OptionBox<InteractionCreateFn<S>>,
+
     // Message
     pub(crate) message_create: OptionBox<MessageCreateFn<S>>,
     pub(crate) message_update: OptionBox<MessageUpdateFn<S>>,
@@ -105,6 +133,17 @@ impl<S> EventHandler<S> {
     pub(crate) fn new() -> Self {
         Self {
             ready: None,
+            reconnected: None,
+
+            // Raw
+            #[cfg(feature = "raw-events")]
+            raw_event: None,
+
+            // Auto moderation
+            auto_moderation_rule_create: None,
+            auto_moderation_rule_update: None,
+            auto_moderation_rule_delete: None,
+            auto_moderation_action_execution: None,
 
             // Channel
             channel_create: None,
@@ -128,6 +167,9 @@ impl<S> EventHandler<S> {
             guild_role_update: None,
             guild_role_delete: None,
 
+            // Interaction
+            interaction_create: None,
+
             // Message
             message_create: None,
             message_update: None,
@@ -1,27 +1,28 @@
 //! # Panda Client
 
 //modules
+mod collector;
 pub mod config;
 mod handler;
 mod session;
 
 pub use config::Config;
-pub use session::SessionData;
+pub use session::{ConnectionState, SessionData};
 use handler::EventHandler;
 
 use crate::{
-    error::{PandaError, Result},
+    error::Result,
     runtime,
-    gateway::{heartbeat, GatewayConnection},
+    gateway::{heartbeat, identify_queue, GatewayConnection},
     models::gateway::{
-        commands::Command,
+        commands::{Command, IdentifyProperties},
         events::*,
         events::{DispatchEvent, Event},
     },
 };
 
 use futures::{sink::SinkExt, stream::StreamExt, FutureExt};
-use std::{future::Future, sync::Arc};
+use std::{collections::HashSet, future::Future, sync::Arc, time::Instant};
 
 /// This macro it's used to handle all dispatched events of handler::EventHandler
 macro_rules! handle_event {
@@ -65,6 +66,10 @@ pub struct Client<S> {
     // SessionData will be shared between tasks, and it will be passed to the handler events
     session: Arc<SessionData<S>>,
     gateway: GatewayConnection,
+    // Guilds of READY and GUILD_DELETE that are waiting for their GUILD_CREATE
+    unavailable_guilds: HashSet<String>,
+    // True after a reconnect, until the session is resumed or a new one is started
+    reconnecting: bool,
 }
 
 impl<S: Sync + Send> Client<S> {
@@ -89,9 +94,12 @@ impl<S: Sync + Send> Client<S> {
             token: token.clone(),
             session: Arc::new(SessionData::new(token, (), to_gateway_ch)),
             gateway,
+            unavailable_guilds: HashSet::new(),
+            reconnecting: false,
         };
 
         // Send identify and spawn heartbeater
+        this.update_identify_concurrency().await;
         this.clean_connect().await;
 
         Ok(this)
@@ -118,9 +126,12 @@ impl<S: Sync + Send> Client<S> {
             token: token.clone(),
             session: Arc::new(SessionData::new(token, state, to_gateway_ch)),
             gateway,
+            unavailable_guilds: HashSet::new(),
+            reconnecting: false,
         };
 
         // Send identify and spawn heartbeater
+        this.update_identify_concurrency().await;
         this.clean_connect().await;
 
         Ok(this)
@@ -129,23 +140,79 @@ impl<S: Sync + Send> Client<S> {
     /// Create a new "discord" Client with personalized configs
     pub fn set_config(&mut self, config: config::ConfigBuilder) -> Result<()> {
         self.config = config.build();
+        self.gateway.set_intents(self.config.gateway_intents);
+        self.gateway
+            .set_event_buffer(self.config.gateway_event_buffer_size, self.config.gateway_overflow_policy);
+        self.gateway.set_drop_replayed_events(self.config.gateway_drop_replayed_events);
+        self.gateway.set_skip_guild_members(self.config.gateway_skip_guild_members);
+
+        self.session.http.set_strict_validation(self.config.http_strict_validation);
+
+        if let Some(version) = &self.config.http_api_version {
+            self.session.http.set_api_version(version.as_str())?;
+        }
 
         Ok(())
     }
 
+    /// Returns the current state of the gateway connection
+    pub fn connection_state(&self) -> ConnectionState {
+        self.session.connection_state()
+    }
+
+    /// Returns the session of the client, it can be used outside of the handlers,
+    /// like to [`wait_until_ready`] in another task before starting the client
+    ///
+    /// [`wait_until_ready`]: struct.SessionData.html#method.wait_until_ready
+    pub fn session(&self) -> Arc<SessionData<S>> {
+        Arc::clone(&self.session)
+    }
+
     /// Start the bot connection process
     pub async fn start(&mut self) -> Result<()> {
         // Connection loop
         loop {
             if let Some(event) = self.gateway.from_gateway.next().await {
+                // The json is given to the raw event handler, and the parsed event is handled below
+                #[cfg(feature = "raw-events")]
+                let event = match event {
+                    Event::RawDispatch(event, raw) => {
+                        handle_event!(self, raw_event, raw);
+                        match event {
+                            Some(event) => Event::Dispatch(event),
+                            None => continue,
+                        }
+                    }
+                    event => event,
+                };
+
                 match event {
                     Event::Dispatch(d) => match d {
                         DispatchEvent::Ready(e) => {
                             // Save SessionData id
                             let id = e.session_id.clone();
                             self.session.set_id(id).await;
+                            self.session.set_connection_state(ConnectionState::Ready);
+                            self.session.set_first_ready(&e).await;
+                            self.unavailable_guilds = e.guilds.iter().map(|g| g.id.clone()).collect();
 
                             handle_event!(self, ready, e);
+                            self.handle_reconnected(false, 0).await;
+                        }
+                        // Sent as Event::Resumed by the gateway, with the number of replayed events
+                        DispatchEvent::Resumed => {}
+                        // Auto moderation
+                        DispatchEvent::AutoModerationRuleCreate(e) => {
+                            handle_event!(self, auto_moderation_rule_create, e);
+                        }
+                        DispatchEvent::AutoModerationRuleUpdate(e) => {
+                            handle_event!(self, auto_moderation_rule_update, e);
+                        }
+                        DispatchEvent::AutoModerationRuleDelete(e) => {
+                            handle_event!(self, auto_moderation_rule_delete, e);
+                        }
+                        DispatchEvent::AutoModerationActionExecution(e) => {
+                            handle_event!(self, auto_moderation_action_execution, e);
                         }
                         // Channel
                         DispatchEvent::ChannelCreate(e) => {
@@ -161,13 +228,20 @@ impl<S: Sync + Send> Client<S> {
                             handle_event!(self, channel_pins_update, e);
                         }
                         // Guild
-                        DispatchEvent::GuildCreate(e) => {
+                        DispatchEvent::GuildCreate(mut e) => {
+                            if self.unavailable_guilds.remove(&e.id) {
+                                e.kind = GuildCreateKind::Available;
+                            }
+                            self.chunk_large_guild(&e);
                             handle_event!(self, guild_create, e);
                         }
                         DispatchEvent::GuildUpdate(e) => {
                             handle_event!(self, guild_update, e);
                         }
                         DispatchEvent::GuildDelete(e) => {
+                            if e.unavailable {
+                                self.unavailable_guilds.insert(e.id.clone());
+                            }
                             handle_event!(self, guild_delete, e);
                         }
                         DispatchEvent::GuildBanAdd(e) => {
@@ -203,8 +277,13 @@ impl<S: Sync + Send> Client<S> {
                         DispatchEvent::GuildRoleDelete(e) => {
                             handle_event!(self, guild_role_delete, e);
                         }
+                        // Interaction
+                        DispatchEvent::InteractionCreate(e) => {
+                            handle_event!(self, interaction_create, e);
+                        }
                         // Message
                         DispatchEvent::MessageCreate(e) => {
+                            self.session.collectors.feed_message(&e).await;
                             handle_event!(self, message_create, e);
                         }
                         DispatchEvent::MessageUpdate(e) => {
@@ -217,6 +296,7 @@ impl<S: Sync + Send> Client<S> {
                             handle_event!(self, message_delete_bulk, e);
                         }
                         DispatchEvent::MessageReactionAdd(e) => {
+                            self.session.collectors.feed_reaction(&e).await;
                             handle_event!(self, message_reaction_add, e);
                         }
                         DispatchEvent::MessageReactionRemove(e) => {
@@ -240,6 +320,10 @@ impl<S: Sync + Send> Client<S> {
                         }
                         _ => {}
                     },
+                    Event::Resumed(replayed_events) => {
+                        self.session.set_connection_state(ConnectionState::Ready);
+                        self.handle_reconnected(true, replayed_events).await;
+                    }
                     Event::Reconnect => {
                         log::info!("Reconnected successfully!");
                     }
@@ -249,17 +333,14 @@ impl<S: Sync + Send> Client<S> {
                     Event::HeartbeatACK => log::info!("HeartbeatACK received"),
                     Event::Close(error) => {
                         log::error!("Error detected {}", error);
+                        self.session.set_connection_state(ConnectionState::Disconnected);
 
                         // Return if there are unrecoverable errors
-                        match &error {
-                            PandaError::AuthenticationFailed
-                            | PandaError::InvalidApiGatewayVersion
-                            | PandaError::InvalidShard
-                            | PandaError::ShardingRequired => return Err(error),
-                            _ => {}
-                        }
-                        // If there was a recoverable error, try to reconnect
-                        self.reconnect().await;
+                        if error.is_fatal_close() {
+                            return Err(error);
+                        }
+                        // If there was a recoverable error, like a network error, try to reconnect
+                        self.reconnect(Instant::now()).await;
                     }
                     event => log::info!("Unhandled event received: {:?}", event),
                 };
@@ -269,8 +350,33 @@ impl<S: Sync + Send> Client<S> {
         //Ok(())
     }
 
-    /// Makes all necessary to reconnect to gateway
-    async fn reconnect(&mut self) {
+    /// Request all the members of a large guild, if it's enabled in the config
+    fn chunk_large_guild(&self, guild: &GuildCreate) {
+        if !self.config.gateway_chunk_large_guilds || guild.large != Some(true) {
+            return;
+        }
+
+        let cmd = Command::new_request_guild_members(&guild.id);
+        if self.gateway.to_gateway.unbounded_send(cmd).is_err() {
+            log::error!("Couldn't request the members of the guild {}", guild.id);
+        }
+    }
+
+    /// Call the reconnected handler if the connection was recovered after a reconnect
+    async fn handle_reconnected(&mut self, resumed: bool, replayed_events: u64) {
+        let session_id = self.session.id().await;
+        if let Some(event) = reconnected_event(&mut self.reconnecting, resumed, session_id, replayed_events) {
+            log::debug!("Reconnected, resumed: {}, replayed events: {}", resumed, replayed_events);
+            handle_event!(self, reconnected, event);
+        }
+    }
+
+    /// Makes all necessary to reconnect to gateway, the session is resumed if the connection
+    /// was down less than the resume timeout
+    async fn reconnect(&mut self, disconnected_at: Instant) {
+        self.session.set_connection_state(ConnectionState::Reconnecting);
+        self.reconnecting = true;
+
         // Close channels
         if let Err(e) = self.gateway.close_channels() {
             log::error!("Error when trying to close gateway channels at reconnect: {}", e);
@@ -279,6 +385,12 @@ impl<S: Sync + Send> Client<S> {
         // Reconnect and get last sequence received, needed to send a RESUME command
         let last_sequence = self.gateway.reconnect().await;
 
+        let down_for = disconnected_at.elapsed();
+        if down_for > self.config.gateway_resume_timeout {
+            log::warn!("The connection was down for {:?}, starting a new session", down_for);
+            self.session.set_resumable(false);
+        }
+
         // If SessionData is resumable, send a RESUME command
         if self.session.is_resumable() {
             self.resume_connect(last_sequence).await;
@@ -290,25 +402,44 @@ impl<S: Sync + Send> Client<S> {
         }
     }
 
+    /// Get the max_concurrency of the bot, used to space the IDENTIFY of the shards
+    async fn update_identify_concurrency(&self) {
+        match self.session.http.get_gateway_bot().await {
+            Ok(gateway_bot) => identify_queue::set_max_concurrency(gateway_bot.session_start_limit.max_concurrency),
+            Err(e) => log::warn!("Couldn't get the gateway max_concurrency: {}", e),
+        }
+    }
+
     async fn clean_connect(&mut self) {
+        // Spawn heartbeater, before waiting to identify so the connection isn't closed
+        self.spawn_heartbeater();
+
+        // Discord only allows 1 IDENTIFY every 5 seconds for each bucket
+        identify_queue::wait_identify(self.config.gateway_shard_id).await;
+
         // Create IDENTIFY
         let shard = [self.config.gateway_shard_id, self.config.gateway_num_shards];
+        let properties = IdentifyProperties {
+            os: self.config.gateway_identify_os.clone(),
+            browser: self.config.gateway_identify_browser.clone(),
+            device: self.config.gateway_identify_device.clone(),
+        };
         let identify = Command::new_identify(
             &self.token,
             self.config.gateway_large_treshold,
             self.config.gateway_guilds_subscriptions,
             shard,
+            properties,
+            self.config.gateway_intents,
         );
 
         // Send IDENTIFY, this should not fail
+        self.session.set_connection_state(ConnectionState::Identifying);
         self.gateway
             .to_gateway
             .send(identify)
             .await
             .expect("Could't send identify");
-
-        // Spawn heartbeater
-        self.spawn_heartbeater();
     }
 
     async fn resume_connect(&mut self, last_sequence: Option<u64>) {
@@ -317,7 +448,12 @@ impl<S: Sync + Send> Client<S> {
         let session_id = self.session.id().await;
         let resume = Command::new_resume(token, session_id, last_sequence);
 
+        if let Some(sequence) = last_sequence {
+            self.gateway.set_resume_sequence(sequence);
+        }
+
         // Send RESUME, this should not fail
+        self.session.set_connection_state(ConnectionState::Resuming);
         self.gateway
             .to_gateway
             .send(resume)
@@ -347,6 +483,46 @@ impl<S: Sync + Send> Client<S> {
         /// [`Ready`]: ../models/gateway/events/struct.Ready.html
         pub fn on_ready(ready, Ready);
 
+        /// Set the handler function for [`Reconnected`], it's called when the connection is
+        /// recovered after a reconnect, with a resumed session or a new one
+        ///
+        /// [`Reconnected`]: ../models/gateway/events/struct.Reconnected.html
+        pub fn on_reconnected(reconnected, Reconnected);
+
+        /// Set the handler function for [`RawEvent`], it receives the json of all dispatch
+        /// events, together with the handler of the parsed event. The events that panda can't
+        /// parse are received too. Requires the `raw-events` feature
+        ///
+        /// [`RawEvent`]: ../models/gateway/events/struct.RawEvent.html
+        #[cfg(feature = "raw-events")]
+        pub fn on_raw_event(raw_event, RawEvent);
+
+
+        // *******************************************************************************
+        // * AUTO MODERATION METHODS
+        // *******************************************************************************
+
+
+        /// Set the handler function for [`AutoModerationRuleCreate`] event
+        ///
+        /// [`AutoModerationRuleCreate`]: ../models/gateway/events/struct.AutoModerationRuleCreate.html
+        pub fn on_auto_moderation_rule_create(auto_moderation_rule_create, AutoModerationRuleCreate);
+
+        /// Set the handler function for [`AutoModerationRuleUpdate`] event
+        ///
+        /// [`AutoModerationRuleUpdate`]: ../models/gateway/events/struct.AutoModerationRuleUpdate.html
+        pub fn on_auto_moderation_rule_update(auto_moderation_rule_update, AutoModerationRuleUpdate);
+
+        /// Set the handler function for [`AutoModerationRuleDelete`] event
+        ///
+        /// [`AutoModerationRuleDelete`]: ../models/gateway/events/struct.AutoModerationRuleDelete.html
+        pub fn on_auto_moderation_rule_delete(auto_moderation_rule_delete, AutoModerationRuleDelete);
+
+        /// Set the handler function for [`AutoModerationActionExecution`] event
+        ///
+        /// [`AutoModerationActionExecution`]: ../models/gateway/events/struct.AutoModerationActionExecution.html
+        pub fn on_auto_moderation_action_execution(auto_moderation_action_execution, AutoModerationActionExecution);
+
 
         // *******************************************************************************
         // * CHANNEL METHODS
@@ -450,6 +626,17 @@ impl<S: Sync + Send> Client<S> {
         pub fn on_guild_role_delete(guild_role_delete, GuildRoleDelete);
 
 
+        // *******************************************************************************
+        // * INTERACTION METHODS
+        // *******************************************************************************
+
+
+        /// Set the handler function for [`InteractionCreate`] event
+        ///
+        /// [`InteractionCreate`]: ../models/gateway/events/struct.InteractionCreate.html
+        pub fn on_interaction_create(interaction_create, InteractionCreate);
+
+
         // *******************************************************************************
         // * MESSAGE METHODS
         // *******************************************************************************
@@ -517,3 +704,59 @@ impl<S: Sync + Send> Client<S> {
         pub fn on_user_update(user_update, UserUpdate)
     }
 }
+
+/// Returns the [`Reconnected`] event of a RESUMED or READY if they arrived after a reconnect, and
+/// clears the reconnecting flag, so the first READY of the client doesn't fire it
+///
+/// [`Reconnected`]: ../models/gateway/events/struct.Reconnected.html
+fn reconnected_event(
+    reconnecting: &mut bool,
+    resumed: bool,
+    session_id: String,
+    replayed_events: u64,
+) -> Option<Reconnected> {
+    if !std::mem::replace(reconnecting, false) {
+        return None;
+    }
+
+    Some(Reconnected {
+        resumed,
+        session_id,
+        replayed_events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_ready_is_not_a_reconnect() {
+        let mut reconnecting = false;
+
+        assert!(reconnected_event(&mut reconnecting, false, "session".into(), 0).is_none());
+    }
+
+    #[test]
+    fn resumed_after_a_reconnect() {
+        let mut reconnecting = true;
+
+        let event = reconnected_event(&mut reconnecting, true, "session".into(), 3).unwrap();
+        assert!(event.resumed);
+        assert_eq!((event.session_id.as_str(), event.replayed_events), ("session", 3));
+        assert!(!reconnecting);
+
+        // It's fired once per reconnect
+        assert!(reconnected_event(&mut reconnecting, true, "session".into(), 0).is_none());
+    }
+
+    #[test]
+    fn new_session_after_a_reconnect() {
+        let mut reconnecting = true;
+
+        let event = reconnected_event(&mut reconnecting, false, "new session".into(), 0).unwrap();
+        assert!(!event.resumed);
+        assert_eq!((event.session_id.as_str(), event.replayed_events), ("new session", 0));
+        assert!(!reconnecting);
+    }
+}
@@ -2,12 +2,20 @@
 
 use crate::{
     error::{Result, PandaError},
-    models::{ gateway::commands::Command, user::StatusUpdate },
+    models::{
+        gateway::{commands::Command, events::{MessageCreate, MessageReactionAdd, Ready}},
+        user::StatusUpdate,
+    },
     HttpClient};
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use super::collector::Collectors;
 
-use futures::{ channel::mpsc::UnboundedSender, lock::Mutex, sink::SinkExt };
+use std::{
+    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+    time::Duration,
+};
+
+use futures::{ channel::{mpsc::UnboundedSender, oneshot}, lock::Mutex, sink::SinkExt };
 
 /// The struct of the current session of the bot.
 pub struct SessionData<S> {
@@ -15,7 +23,52 @@ pub struct SessionData<S> {
     pub http: HttpClient,
     pub state: S,
     is_resumable: AtomicBool,
-    to_gateway_ch: Mutex<UnboundedSender<Command>>
+    connection_state: AtomicU8,
+    pub(crate) collectors: Collectors,
+    to_gateway_ch: Mutex<UnboundedSender<Command>>,
+    first_ready: Mutex<FirstReady>,
+}
+
+/// The first READY received, and the tasks waiting for it
+#[derive(Default)]
+struct FirstReady {
+    ready: Option<Ready>,
+    waiters: Vec<oneshot::Sender<Ready>>,
+}
+
+/// The state of the gateway connection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionState {
+    /// Connecting to the gateway
+    Connecting,
+
+    /// IDENTIFY was sent, waiting for READY
+    Identifying,
+
+    /// READY or RESUMED was received, the bot is receiving events
+    Ready,
+
+    /// RESUME was sent, waiting for RESUMED
+    Resuming,
+
+    /// The connection was lost, and it's trying to reconnect
+    Reconnecting,
+
+    /// The connection was closed
+    Disconnected,
+}
+
+impl From<u8> for ConnectionState {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Connecting,
+            1 => Self::Identifying,
+            2 => Self::Ready,
+            3 => Self::Resuming,
+            4 => Self::Reconnecting,
+            _ => Self::Disconnected,
+        }
+    }
 }
 
 impl<S> SessionData<S> {
@@ -25,7 +78,10 @@ impl<S> SessionData<S> {
             http: HttpClient::new(token),
             state,
             is_resumable: AtomicBool::new(true),
-            to_gateway_ch: Mutex::new(to_gateway_ch)
+            connection_state: AtomicU8::new(ConnectionState::Connecting as u8),
+            collectors: Collectors::new(),
+            to_gateway_ch: Mutex::new(to_gateway_ch),
+            first_ready: Mutex::new(FirstReady::default()),
         }
     }
 
@@ -39,6 +95,16 @@ impl<S> SessionData<S> {
         self.is_resumable.load(Ordering::Relaxed)
     }
 
+    /// Set the current state of the gateway connection
+    pub(crate) fn set_connection_state(&self, state: ConnectionState) {
+        self.connection_state.store(state as u8, Ordering::Relaxed);
+    }
+
+    /// Get the current state of the gateway connection
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection_state.load(Ordering::Relaxed).into()
+    }
+
     /// Set the value to id field
     pub(crate) async fn set_id(&self, id: String) {
         let mut session_id = self.id.lock().await;
@@ -51,6 +117,80 @@ impl<S> SessionData<S> {
         session_id.clone()
     }
 
+    /// Save the first READY, and wake the tasks waiting for it. The next READY events are ignored
+    pub(crate) async fn set_first_ready(&self, ready: &Ready) {
+        let mut first_ready = self.first_ready.lock().await;
+        if first_ready.ready.is_some() {
+            return;
+        }
+
+        for waiter in first_ready.waiters.drain(..) {
+            let _ = waiter.send(ready.clone());
+        }
+        first_ready.ready = Some(ready.clone());
+    }
+
+    /// Wait until the first [`Ready`] event is received, and returns it. After it was received
+    /// it returns immediately
+    ///
+    /// [`Ready`]: ../models/gateway/events/struct.Ready.html
+    pub async fn wait_until_ready(&self) -> Ready {
+        let receiver = {
+            let mut first_ready = self.first_ready.lock().await;
+            if let Some(ready) = &first_ready.ready {
+                return ready.clone();
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            first_ready.waiters.push(sender);
+            receiver
+        };
+
+        // The sender is only dropped after sending the READY
+        receiver.await.expect("Session dropped before READY")
+    }
+
+    /// Wait for the next [`MessageCreate`] that matches the predicate, it returns `None`
+    /// if no message was received before the timeout. Other events are still dispatched
+    /// to their handlers as usual
+    ///
+    /// [`MessageCreate`]: ../models/gateway/events/struct.MessageCreate.html
+    pub async fn await_message<F>(&self, predicate: F, timeout: Duration) -> Option<MessageCreate>
+    where
+        F: Fn(&MessageCreate) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.collect_messages(predicate, 1, timeout).await.pop()
+    }
+
+    /// Collect up to max messages that match the predicate, it returns the messages
+    /// received before the timeout
+    pub async fn collect_messages<F>(&self, predicate: F, max: usize, timeout: Duration) -> Vec<MessageCreate>
+    where
+        F: Fn(&MessageCreate) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.collect_messages(predicate, max, timeout).await
+    }
+
+    /// Wait for the next [`MessageReactionAdd`] that matches the predicate, it returns `None`
+    /// if no reaction was received before the timeout
+    ///
+    /// [`MessageReactionAdd`]: ../models/gateway/events/struct.MessageReactionAdd.html
+    pub async fn await_reaction<F>(&self, predicate: F, timeout: Duration) -> Option<MessageReactionAdd>
+    where
+        F: Fn(&MessageReactionAdd) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.collect_reactions(predicate, 1, timeout).await.pop()
+    }
+
+    /// Collect up to max reactions that match the predicate, it returns the reactions
+    /// received before the timeout
+    pub async fn collect_reactions<F>(&self, predicate: F, max: usize, timeout: Duration) -> Vec<MessageReactionAdd>
+    where
+        F: Fn(&MessageReactionAdd) -> bool + Send + Sync + 'static,
+    {
+        self.collectors.collect_reactions(predicate, max, timeout).await
+    }
+
     //Send
     pub async fn update_status(&self, status_update: StatusUpdate) -> Result<()> {
 
@@ -62,3 +202,64 @@ impl<S> SessionData<S> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::{channel::mpsc, executor::block_on, FutureExt};
+    use serde_json::json;
+
+    fn session() -> SessionData<()> {
+        let (sender, _) = mpsc::unbounded();
+        SessionData::new("token".into(), (), sender)
+    }
+
+    fn ready(session_id: &str) -> Ready {
+        serde_json::from_value(json!({
+            "v": 6,
+            "user": {
+                "id": "80351110224678912",
+                "username": "Nelly",
+                "discriminator": "1337",
+                "avatar": null
+            },
+            "guilds": [{"id": "290926798626357999", "unavailable": true}],
+            "session_id": session_id
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn connection_states() {
+        let session = session();
+        assert_eq!(session.connection_state(), ConnectionState::Connecting);
+
+        for state in [
+            ConnectionState::Identifying,
+            ConnectionState::Ready,
+            ConnectionState::Resuming,
+            ConnectionState::Reconnecting,
+            ConnectionState::Disconnected,
+        ]
+        .iter()
+        {
+            session.set_connection_state(*state);
+            assert_eq!(session.connection_state(), *state);
+        }
+    }
+
+    #[test]
+    fn wait_until_first_ready() {
+        let session = session();
+
+        let mut waiting = Box::pin(session.wait_until_ready());
+        assert!((&mut waiting).now_or_never().is_none());
+
+        block_on(session.set_first_ready(&ready("first")));
+        block_on(session.set_first_ready(&ready("second")));
+
+        assert_eq!(block_on(waiting).session_id, "first");
+        assert_eq!(block_on(session.wait_until_ready()).session_id, "first");
+    }
+}
@@ -3,7 +3,7 @@
 //! Here there are some wrappers of [Discord API errors](https://discordapp.com/developers/docs/topics/opcodes-and-status-codes)
 
 use async_tungstenite::tungstenite::Error as TungsteniteError;
-use std::{error::Error, fmt, result::Result as StdResult};
+use std::{error::Error, fmt, result::Result as StdResult, time::Duration};
 
 /// This library use a shared result type, because all functions returns the same error type
 pub type Result<T> = StdResult<T, PandaError>;
@@ -12,9 +12,6 @@ pub type Result<T> = StdResult<T, PandaError>;
 #[derive(Debug)]
 pub enum PandaError {
     // TODO: Use different error enums
-    /// Returned when there was an authentication error and the gateway is closed
-    AuthenticationFailed,
-
     /// Returned when "discord" fails to connect to the gateway, it can only be returned at
     /// the first connection, all reconnections are handled by "discord"
     CantConnectToGateway,
@@ -22,15 +19,16 @@ pub enum PandaError {
     /// Returned when the gateway connection is unexpected closed
     ConnectionClosed,
 
+    /// Returned when Discord closes the gateway connection, with the close code and the reason
+    /// sent by Discord. panda reconnects after it, unless the code is fatal, like 4004 for an
+    /// invalid token. See [`PandaError::is_fatal_close`]
+    ///
+    /// [`PandaError::is_fatal_close`]: #method.is_fatal_close
+    GatewayClosed { code: u16, reason: String },
+
     /// Returned when "discord" receives a unknown message format
     UnknownPayloadReceived,
 
-    /// Returned when panda sent an invalid Opcode
-    UnknownOpcodeSent,
-
-    /// Returned when panda sent an invalid payload
-    InvalidDecodeSent,
-
     /// Returned when panda recevies a invalid message format
     InvalidPayloadFormat(&'static str),
 
@@ -58,17 +56,47 @@ pub enum PandaError {
     /// such as wrong {channel_id}
     HttpInvalidParameters,
 
-    /// Returned when the gateway couldn't close the connection succesfully
-    UnsuccessfulConnectionClose,
+    /// Returned when Discord answered with a 429, the request can be sent again after
+    /// `retry_after`. A global rate limit also delays all the next requests until then
+    HttpRateLimited { retry_after: Duration, global: bool },
+
+    /// Returned when the user isn't a member of the guild
+    UnknownMember,
+
+    /// Returned when the bot isn't a member of the guild
+    NotInGuild,
+
+    /// Returned when pinning a message in a channel that already has 50 pinned messages
+    PinLimitReached,
+
+    /// Returned when the guild already has the max number of emojis, stickers or roles
+    /// of its boost tier
+    ResourceLimitReached { kind: ResourceKind },
+
+    /// Returned when trying to send a message to a channel that isn't messageable,
+    /// like a category or voice channel
+    NotTextChannel,
+
+    /// Returned when a response was already sent for an interaction
+    InteractionAlreadyResponded,
+
+    /// Returned when an attachment is bigger than the allowed size
+    AttachmentTooLarge,
 
-    /// Returned when you send an invalid shard
-    InvalidShard,
+    /// Returned when the attachment url expired, and it needs to be fetched again
+    AttachmentExpired,
 
-    /// Returned when you handled too many guilds, and shard is necessary
-    ShardingRequired,
+    /// Returned when a builder has an invalid value, before sending the request
+    Validation { field: &'static str, reason: String },
 
-    // Invalid API version (gateway)
-    InvalidApiGatewayVersion,
+    /// Returned when a command argument can't be converted to the expected type
+    InvalidArgument { arg: String, expected: &'static str },
+
+    /// Returned when the event buffer is full, with the `Error` overflow policy
+    EventBufferFull,
+
+    /// Returned when the gateway couldn't close the connection succesfully
+    UnsuccessfulConnectionClose,
 
     /// serde_json
     SerdeError(serde_json::Error),
@@ -82,9 +110,9 @@ pub enum PandaError {
 impl fmt::Display for PandaError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::AuthenticationFailed => write!(f, "Authentication failed"),
             Self::CantConnectToGateway => write!(f, "'Discord' couldn't connect to gateway"),
             Self::ConnectionClosed => write!(f, "Connection closed unexpectedly"),
+            Self::GatewayClosed { code, reason } => write!(f, "Gateway closed with code {}: {}", code, reason),
             Self::UnknownPayloadReceived => write!(f, "Unknown payload format received"),
             Self::InvalidPayloadFormat(p) => write!(f, "Invalid payload format received: {}", p),
             Self::UnexpectedPayloadReceived => write!(f, "Unexpected payload received"),
@@ -94,14 +122,24 @@ impl fmt::Display for PandaError {
             Self::HttpUnauthorized => write!(f, "The client token is invalid"),
             Self::HttpForbidden => write!(f, "The client did not have permission to the resource"),
             Self::HttpInvalidParameters => write!(f, "The request had invalid parameters"),
+            Self::HttpRateLimited { retry_after, global } => {
+                let kind = if *global { "global" } else { "route" };
+                write!(f, "Reached the {} rate limit, retry after {:?}", kind, retry_after)
+            }
+            Self::UnknownMember => write!(f, "The user is not a member of the guild"),
+            Self::NotInGuild => write!(f, "The bot is not a member of the guild"),
+            Self::PinLimitReached => write!(f, "The channel already has the max number of pinned messages"),
+            Self::ResourceLimitReached { kind } => write!(f, "The guild already has the max number of {}", kind),
+            Self::NotTextChannel => write!(f, "Messages can't be sent to this channel kind"),
+            Self::InteractionAlreadyResponded => write!(f, "The interaction was already acknowledged"),
+            Self::AttachmentTooLarge => write!(f, "The attachment is bigger than the allowed size"),
+            Self::AttachmentExpired => write!(f, "The attachment url expired"),
+            Self::Validation { field, reason } => write!(f, "Invalid value for {}: {}", field, reason),
+            Self::InvalidArgument { arg, expected } => write!(f, "Invalid argument `{}`, expected {}", arg, expected),
+            Self::EventBufferFull => write!(f, "The event buffer is full"),
             Self::UnsuccessfulConnectionClose => write!(f, "The gateway couldn't close succesfully the connection"),
-            Self::InvalidShard => write!(f, "You sent an invalid shard"),
-            Self::ShardingRequired => write!(f, "The SessionData would have handled too many guilds - you are required to shard your connection in order to connect."),
-            Self::InvalidApiGatewayVersion => write!(f, "panda needs to update the gateway version"),
             Self::SerdeError(e) => write!(f, "Serde Error: {}", e),
             Self::TungsteniteError(e) => write!(f, "Tungstenite Error: {}", e),
-            Self::UnknownOpcodeSent => write!(f, "panda sent an invalid Opcode, please report the bug"),
-            Self::InvalidDecodeSent => write!(f, "panda sent an invalid payload, please report the bug"),
             Self::RuntimeError => write!(f, "runtime error")
         }
     }
@@ -109,6 +147,56 @@ impl fmt::Display for PandaError {
 
 impl Error for PandaError {}
 
+/// Close codes after which reconnecting would fail again: unknown opcode and decode error are
+/// bugs of panda, then authentication failed, invalid shard, sharding required, invalid API
+/// version, invalid intents and disallowed intents
+const FATAL_CLOSE_CODES: [u16; 8] = [4001, 4002, 4004, 4010, 4011, 4012, 4013, 4014];
+
+impl PandaError {
+    /// Returns the gateway close code of the error, if Discord closed the connection.
+    /// [Discord Documentation](https://discord.com/developers/docs/topics/opcodes-and-status-codes#gateway-gateway-close-event-codes)
+    pub fn close_code(&self) -> Option<u16> {
+        match self {
+            Self::GatewayClosed { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason sent by Discord with the close code
+    pub fn close_reason(&self) -> Option<&str> {
+        match self {
+            Self::GatewayClosed { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Returns true if Discord closed the connection with a code that can't be fixed by
+    /// reconnecting, like an invalid token or invalid intents. The client returns this error
+    pub fn is_fatal_close(&self) -> bool {
+        self.close_code().map_or(false, |code| FATAL_CLOSE_CODES.contains(&code))
+    }
+}
+
+/// The guild resources with a max number, used by [`PandaError::ResourceLimitReached`]
+///
+/// [`PandaError::ResourceLimitReached`]: enum.PandaError.html#variant.ResourceLimitReached
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResourceKind {
+    Emojis,
+    Stickers,
+    Roles,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Emojis => write!(f, "emojis"),
+            Self::Stickers => write!(f, "stickers"),
+            Self::Roles => write!(f, "roles"),
+        }
+    }
+}
+
 // Error parsing
 impl From<serde_json::Error> for PandaError {
     fn from(error: serde_json::Error) -> Self {
@@ -148,3 +236,38 @@ impl From<tokio::task::JoinError> for PandaError {
         PandaError::RuntimeError
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closed(code: u16) -> PandaError {
+        PandaError::GatewayClosed {
+            code,
+            reason: "closed".into(),
+        }
+    }
+
+    #[test]
+    fn fatal_close_codes() {
+        for code in FATAL_CLOSE_CODES.iter() {
+            assert!(closed(*code).is_fatal_close(), "{} must be fatal", code);
+        }
+
+        // Discord allows to reconnect after these codes
+        for code in [1000, 4000, 4003, 4005, 4007, 4008, 4009].iter() {
+            assert!(!closed(*code).is_fatal_close(), "{} must not be fatal", code);
+        }
+        assert!(!PandaError::ConnectionClosed.is_fatal_close());
+    }
+
+    #[test]
+    fn close_code_and_reason() {
+        let error = closed(4004);
+        assert_eq!(error.close_code(), Some(4004));
+        assert_eq!(error.close_reason(), Some("closed"));
+
+        assert_eq!(PandaError::ConnectionClosed.close_code(), None);
+        assert_eq!(PandaError::ConnectionClosed.close_reason(), None);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{
+    client::config::OverflowPolicy,
+    error::{PandaError, Result},
+    models::gateway::events::Event,
+};
+
+use futures::{future::poll_fn, stream::Stream};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Bounded channel used to send the events from the gateway process to the client,
+/// when it's full the [`OverflowPolicy`] decides what happens with new events
+pub(crate) fn channel(capacity: usize, policy: OverflowPolicy) -> (EventSender, EventReceiver) {
+    let state = State {
+        buffer: VecDeque::new(),
+        capacity: capacity.max(1),
+        policy,
+        closed: false,
+        receiver: None,
+        sender: None,
+    };
+    let inner = Arc::new(Mutex::new(state));
+
+    (
+        EventSender {
+            inner: Arc::clone(&inner),
+        },
+        EventReceiver { inner },
+    )
+}
+
+struct State {
+    buffer: VecDeque<Event>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    closed: bool,
+    // Wakers of the waiting receiver, and the sender blocked by a full buffer
+    receiver: Option<Waker>,
+    sender: Option<Waker>,
+}
+
+pub(crate) struct EventSender {
+    inner: Arc<Mutex<State>>,
+}
+
+pub(crate) struct EventReceiver {
+    inner: Arc<Mutex<State>>,
+}
+
+impl State {
+    fn push(&mut self, event: Event) {
+        self.buffer.push_back(event);
+
+        if let Some(waker) = self.receiver.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl EventSender {
+    /// Send an event to the client, returns [`PandaError::EventBufferFull`] if the buffer
+    /// is full with the `Error` policy
+    pub(crate) async fn send(&self, event: Event) -> Result<()> {
+        let mut event = Some(event);
+
+        poll_fn(|cx| {
+            let mut state = self.inner.lock().unwrap();
+
+            if state.closed {
+                return Poll::Ready(Err(PandaError::ConnectionClosed));
+            }
+
+            if state.buffer.len() >= state.capacity {
+                match state.policy {
+                    OverflowPolicy::Block => {
+                        state.sender = Some(cx.waker().clone());
+                        return Poll::Pending;
+                    }
+                    OverflowPolicy::DropOldest => {
+                        log::warn!("Event buffer is full, dropping the oldest event");
+                        state.buffer.pop_front();
+                    }
+                    OverflowPolicy::Error => return Poll::Ready(Err(PandaError::EventBufferFull)),
+                }
+            }
+
+            state.push(event.take().unwrap());
+            Poll::Ready(Ok(()))
+        })
+        .await
+    }
+
+    /// Send the close event, it's always added even if the buffer is full
+    pub(crate) fn send_close(&self, error: PandaError) {
+        let mut state = self.inner.lock().unwrap();
+
+        if !state.closed {
+            state.push(Event::Close(error));
+        }
+    }
+}
+
+impl Drop for EventSender {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock().unwrap();
+        state.closed = true;
+
+        if let Some(waker) = state.receiver.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl EventReceiver {
+    /// Change the buffer size and the overflow policy, the events already in the buffer are kept
+    pub(crate) fn set_limits(&self, capacity: usize, policy: OverflowPolicy) {
+        let mut state = self.inner.lock().unwrap();
+        state.capacity = capacity.max(1);
+        state.policy = policy;
+
+        if let Some(waker) = state.sender.take() {
+            waker.wake();
+        }
+    }
+
+    /// Close the channel, the events in the buffer can still be received
+    pub(crate) fn close(&mut self) {
+        let mut state = self.inner.lock().unwrap();
+        state.closed = true;
+
+        if let Some(waker) = state.sender.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Stream for EventReceiver {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let mut state = self.inner.lock().unwrap();
+
+        if let Some(event) = state.buffer.pop_front() {
+            if let Some(waker) = state.sender.take() {
+                waker.wake();
+            }
+
+            return Poll::Ready(Some(event));
+        }
+
+        if state.closed {
+            return Poll::Ready(None);
+        }
+
+        state.receiver = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for EventReceiver {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::{executor::block_on, FutureExt, StreamExt};
+
+    fn hello(receiver: &mut EventReceiver) -> Option<u64> {
+        match block_on(receiver.next()) {
+            Some(Event::Hello(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn drop_oldest_keeps_newest_events() {
+        let (sender, mut receiver) = channel(2, OverflowPolicy::DropOldest);
+
+        for n in 1..=3 {
+            block_on(sender.send(Event::Hello(n))).unwrap();
+        }
+
+        assert_eq!(hello(&mut receiver), Some(2));
+        assert_eq!(hello(&mut receiver), Some(3));
+    }
+
+    #[test]
+    fn error_policy_rejects_new_events() {
+        let (sender, mut receiver) = channel(1, OverflowPolicy::Error);
+
+        block_on(sender.send(Event::Hello(1))).unwrap();
+        assert!(matches!(
+            block_on(sender.send(Event::Hello(2))),
+            Err(PandaError::EventBufferFull)
+        ));
+        assert_eq!(hello(&mut receiver), Some(1));
+    }
+
+    #[test]
+    fn block_policy_waits_for_space() {
+        let (sender, mut receiver) = channel(1, OverflowPolicy::Block);
+
+        block_on(sender.send(Event::Hello(1))).unwrap();
+        assert!(sender.send(Event::Hello(2)).now_or_never().is_none());
+
+        assert_eq!(hello(&mut receiver), Some(1));
+        assert!(sender.send(Event::Hello(3)).now_or_never().is_some());
+        assert_eq!(hello(&mut receiver), Some(3));
+    }
+
+    #[test]
+    fn close_event_ignores_capacity() {
+        let (sender, mut receiver) = channel(1, OverflowPolicy::Error);
+
+        block_on(sender.send(Event::Hello(1))).unwrap();
+        sender.send_close(PandaError::ConnectionClosed);
+        drop(sender);
+
+        assert_eq!(hello(&mut receiver), Some(1));
+        assert!(matches!(
+            block_on(receiver.next()),
+            Some(Event::Close(PandaError::ConnectionClosed))
+        ));
+        assert!(block_on(receiver.next()).is_none());
+    }
+
+    #[test]
+    fn closed_receiver_rejects_events() {
+        let (sender, mut receiver) = channel(1, OverflowPolicy::Block);
+        receiver.close();
+
+        assert!(matches!(
+            block_on(sender.send(Event::Hello(1))),
+            Err(PandaError::ConnectionClosed)
+        ));
+    }
+
+    #[test]
+    fn set_limits_changes_capacity() {
+        let (sender, mut receiver) = channel(1, OverflowPolicy::Error);
+        receiver.set_limits(2, OverflowPolicy::Error);
+
+        block_on(sender.send(Event::Hello(1))).unwrap();
+        block_on(sender.send(Event::Hello(2))).unwrap();
+        assert_eq!(hello(&mut receiver), Some(1));
+        assert_eq!(hello(&mut receiver), Some(2));
+    }
+}
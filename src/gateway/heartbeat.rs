@@ -1,4 +1,4 @@
-use crate::{models::gateway::commands::Command, runtime};
+use crate::{models::gateway::commands::Command, runtime, utils::random::random_u64};
 
 use futures::{channel::mpsc::UnboundedSender, sink::SinkExt};
 use std::time::Duration;
@@ -7,8 +7,13 @@ use std::time::Duration;
 /// it will send a heartbeat COMMAND to gateway every heartbeat_interval.
 /// When the channel is closed, it will be terminated
 pub(crate) async fn heartbeater(heartbeat_interval: u64, mut to_gateway: UnboundedSender<Command>) {
+    // Discord recommends to send the first heartbeat after heartbeat_interval * jitter,
+    // to avoid all clients sending heartbeats at the same time
+    let mut delay = first_heartbeat_delay(heartbeat_interval, random_u64());
+
     loop {
-        runtime::sleep(Duration::from_millis(heartbeat_interval)).await;
+        runtime::sleep(Duration::from_millis(delay)).await;
+        delay = heartbeat_interval;
 
         // Always check first if the channel it's open
         if to_gateway.is_closed() {
@@ -21,3 +26,30 @@ pub(crate) async fn heartbeater(heartbeat_interval: u64, mut to_gateway: Unbound
         };
     }
 }
+
+/// Returns a delay between [0, heartbeat_interval), using the given random value
+fn first_heartbeat_delay(heartbeat_interval: u64, random: u64) -> u64 {
+    match heartbeat_interval {
+        0 => 0,
+        interval => random % interval,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_delay_is_within_interval() {
+        for random in [0, 1, 41_249, 41_250, 99_999, u64::MAX].iter() {
+            let delay = first_heartbeat_delay(41_250, *random);
+            assert!(delay < 41_250, "delay {} out of bounds", delay);
+        }
+        assert_eq!(first_heartbeat_delay(41_250, 41_251), 1);
+    }
+
+    #[test]
+    fn first_delay_with_zero_interval() {
+        assert_eq!(first_heartbeat_delay(0, 12345), 0);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::runtime;
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Discord allows 1 IDENTIFY every 5 seconds for each bucket
+const IDENTIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shared by all the clients of the process, so shards started together are spaced
+static QUEUE: Mutex<IdentifyQueue> = Mutex::new(IdentifyQueue::new());
+
+/// Keeps the time of the last IDENTIFY of each bucket, the bucket of a shard
+/// is `shard_id % max_concurrency`
+struct IdentifyQueue {
+    max_concurrency: u64,
+    last_identify: Vec<Option<Instant>>,
+}
+
+impl IdentifyQueue {
+    const fn new() -> Self {
+        IdentifyQueue {
+            max_concurrency: 1,
+            last_identify: Vec::new(),
+        }
+    }
+
+    fn set_max_concurrency(&mut self, max_concurrency: u64) {
+        let max_concurrency = max_concurrency.max(1);
+
+        // The buckets change, so the last identify of all of them must be kept
+        if max_concurrency != self.max_concurrency {
+            let last = self.last_identify.iter().flatten().max().copied();
+            self.last_identify = vec![last; max_concurrency as usize];
+            self.max_concurrency = max_concurrency;
+        }
+    }
+
+    /// Reserve the next IDENTIFY of the shard bucket, and returns how long to wait until it
+    fn reserve(&mut self, shard_id: u64, now: Instant) -> Duration {
+        let bucket = (shard_id % self.max_concurrency) as usize;
+        if self.last_identify.len() <= bucket {
+            self.last_identify.resize(bucket + 1, None);
+        }
+
+        let at = match self.last_identify[bucket] {
+            Some(last) => (last + IDENTIFY_INTERVAL).max(now),
+            None => now,
+        };
+        self.last_identify[bucket] = Some(at);
+
+        at - now
+    }
+}
+
+/// Set the max_concurrency returned by Discord at `GET /gateway/bot`
+pub(crate) fn set_max_concurrency(max_concurrency: u64) {
+    QUEUE.lock().unwrap().set_max_concurrency(max_concurrency);
+}
+
+/// Wait until the shard can send an IDENTIFY
+pub(crate) async fn wait_identify(shard_id: u64) {
+    let delay = QUEUE.lock().unwrap().reserve(shard_id, Instant::now());
+
+    if delay > Duration::from_secs(0) {
+        log::info!("Waiting {:?} to identify shard {}", delay, shard_id);
+        runtime::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_identify_is_immediate() {
+        let mut queue = IdentifyQueue::new();
+        assert_eq!(queue.reserve(0, Instant::now()), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn identifies_of_a_bucket_are_spaced() {
+        let mut queue = IdentifyQueue::new();
+        let now = Instant::now();
+
+        assert_eq!(queue.reserve(0, now), Duration::from_secs(0));
+        assert_eq!(queue.reserve(1, now), IDENTIFY_INTERVAL);
+        assert_eq!(queue.reserve(2, now), IDENTIFY_INTERVAL * 2);
+
+        // After some time, the wait is shorter
+        let later = now + Duration::from_secs(12);
+        assert_eq!(queue.reserve(3, later), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn buckets_are_independent() {
+        let mut queue = IdentifyQueue::new();
+        queue.set_max_concurrency(2);
+        let now = Instant::now();
+
+        assert_eq!(queue.reserve(0, now), Duration::from_secs(0));
+        assert_eq!(queue.reserve(1, now), Duration::from_secs(0));
+        assert_eq!(queue.reserve(2, now), IDENTIFY_INTERVAL);
+        assert_eq!(queue.reserve(3, now), IDENTIFY_INTERVAL);
+    }
+
+    #[test]
+    fn changing_concurrency_keeps_last_identify() {
+        let mut queue = IdentifyQueue::new();
+        let now = Instant::now();
+        queue.reserve(0, now);
+
+        queue.set_max_concurrency(4);
+        for shard_id in 0..4 {
+            assert!(queue.reserve(shard_id, now) >= IDENTIFY_INTERVAL);
+        }
+    }
+
+    #[test]
+    fn zero_concurrency_is_one() {
+        let mut queue = IdentifyQueue::new();
+        queue.set_max_concurrency(0);
+        assert_eq!(queue.max_concurrency, 1);
+    }
+}
@@ -1,37 +1,74 @@
 // modules
+mod event_queue;
 pub(crate) mod heartbeat;
+pub(crate) mod identify_queue;
 mod process;
+mod replay_filter;
+use event_queue::EventReceiver;
 use process::gateway_process;
+use replay_filter::ReplayFilter;
 
 // crate imports
 use crate::{
+    client::config::{OverflowPolicy, DEFAULT_EVENT_BUFFER_SIZE},
     runtime::{self, websocket::connect_async},
     error::{PandaError, Result},
-    models::gateway::{commands::Command, events::Event},
+    models::gateway::{commands::Command, events::Event, Intents},
 };
 
 use std::{
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
 };
 
 use futures::{
-    channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    channel::mpsc::{self, UnboundedSender},
     stream::StreamExt,
 };
 
+/// Value of the intents filter when no intents were configured
+const NO_INTENTS_FILTER: u64 = u64::MAX;
+
 pub(crate) struct GatewayConnection {
     last_sequence: Arc<AtomicU64>,
+    // Shared with the gateway process, used to ignore events outside of the intents
+    intents: Arc<AtomicU64>,
+    // Shared with the gateway process, kept across reconnections to drop replayed events
+    replay_filter: Arc<ReplayFilter>,
+    // Shared with the gateway process, removes the members of GUILD_CREATE before parsing it
+    skip_guild_members: Arc<AtomicBool>,
+    event_buffer_size: usize,
+    overflow_policy: OverflowPolicy,
     pub(crate) heartbeat_interval: u64,
-    pub(crate) from_gateway: UnboundedReceiver<Event>,
+    pub(crate) from_gateway: EventReceiver,
     pub(crate) to_gateway: UnboundedSender<Command>,
 }
 
 impl GatewayConnection {
     pub(crate) async fn new() -> Result<GatewayConnection> {
+        let intents = Arc::new(AtomicU64::new(NO_INTENTS_FILTER));
+        let replay_filter = Arc::new(ReplayFilter::default());
+        let skip_guild_members = Arc::new(AtomicBool::new(false));
+        GatewayConnection::connect(
+            intents,
+            replay_filter,
+            skip_guild_members,
+            DEFAULT_EVENT_BUFFER_SIZE,
+            OverflowPolicy::DropOldest,
+        )
+        .await
+    }
+
+    async fn connect(
+        intents: Arc<AtomicU64>,
+        replay_filter: Arc<ReplayFilter>,
+        skip_guild_members: Arc<AtomicBool>,
+        event_buffer_size: usize,
+        overflow_policy: OverflowPolicy,
+    ) -> Result<GatewayConnection> {
         // Parse discord url
         let url = url::Url::parse("wss://gateway.discord.gg/?v=6&encoding=json").unwrap();
 
@@ -41,14 +78,26 @@ impl GatewayConnection {
         let (ws, _) = connect_async(url).await.expect("Can't connect to gateway");
 
         // Spawn gateway process manager
-        let (to_client, mut from_gateway) = mpsc::unbounded();
+        let (to_client, mut from_gateway) = event_queue::channel(event_buffer_size, overflow_policy);
         let (to_gateway, from_client) = mpsc::unbounded();
 
         let last_sequence = Arc::new(AtomicU64::default());
         let last_sequence_clone = Arc::clone(&last_sequence);
+        let intents_clone = Arc::clone(&intents);
+        let replay_filter_clone = Arc::clone(&replay_filter);
+        let skip_guild_members_clone = Arc::clone(&skip_guild_members);
 
         runtime::spawn(async move {
-            gateway_process(ws, to_client, from_client, last_sequence_clone).await;
+            gateway_process(
+                ws,
+                to_client,
+                from_client,
+                last_sequence_clone,
+                intents_clone,
+                replay_filter_clone,
+                skip_guild_members_clone,
+            )
+            .await;
         });
 
         // Receive Hello event from the gatewat
@@ -61,12 +110,46 @@ impl GatewayConnection {
 
         Ok(GatewayConnection {
             last_sequence,
+            intents,
+            replay_filter,
+            skip_guild_members,
+            event_buffer_size,
+            overflow_policy,
             heartbeat_interval,
             from_gateway,
             to_gateway,
         })
     }
 
+    /// Set the intents used to filter the received events, `None` disables the filter
+    pub(crate) fn set_intents(&self, intents: Option<Intents>) {
+        let bits = intents.map(Intents::bits).unwrap_or(NO_INTENTS_FILTER);
+        self.intents.store(bits, Ordering::Relaxed);
+    }
+
+    /// Drop the dispatch events that were already received, replayed by Discord after a RESUME
+    pub(crate) fn set_drop_replayed_events(&self, drop: bool) {
+        self.replay_filter.set_enabled(drop);
+    }
+
+    /// Remove the members, presences and voice states of GUILD_CREATE before it's parsed
+    pub(crate) fn set_skip_guild_members(&self, skip: bool) {
+        self.skip_guild_members.store(skip, Ordering::Relaxed);
+    }
+
+    /// Set the size of the event buffer and what happens when it's full
+    pub(crate) fn set_event_buffer(&mut self, size: usize, policy: OverflowPolicy) {
+        self.event_buffer_size = size;
+        self.overflow_policy = policy;
+        self.from_gateway.set_limits(size, policy);
+    }
+
+    /// Continue the sequence of the resumed session, so the gaps in the replayed events
+    /// are detected. It must be called before sending RESUME
+    pub(crate) fn set_resume_sequence(&self, sequence: u64) {
+        self.last_sequence.store(sequence, Ordering::Relaxed);
+    }
+
     pub(crate) fn close_channels(&mut self) -> Result<()> {
         self.from_gateway.close();
         self.to_gateway.close_channel();
@@ -82,7 +165,17 @@ impl GatewayConnection {
 
         loop {
             log::error!("Disconnected from the gateway, starting reconnect...");
-            match GatewayConnection::new().await {
+            let intents = Arc::clone(&self.intents);
+            let replay_filter = Arc::clone(&self.replay_filter);
+            let skip_guild_members = Arc::clone(&self.skip_guild_members);
+            let connect = GatewayConnection::connect(
+                intents,
+                replay_filter,
+                skip_guild_members,
+                self.event_buffer_size,
+                self.overflow_policy,
+            );
+            match connect.await {
                 Ok(g) => {
                     std::mem::replace(self, g);
                     log::info!("Connected succesfully");
@@ -1,7 +1,13 @@
+use super::{event_queue::EventSender, replay_filter::ReplayFilter};
 use crate::{
     runtime::websocket::{WebSocket, WebSocketSender},
     error::{PandaError, Result},
-    models::gateway::{commands::Command, events::Event, payload::Payload},
+    models::gateway::{
+        commands::Command,
+        events::Event,
+        payload::{Opcode, Payload},
+        Intents,
+    },
 };
 
 // std
@@ -9,14 +15,14 @@ use std::{
     convert::TryFrom,
     result::Result as StdResult,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
 };
 
 // futures
 use futures::{
-    channel::mpsc::{UnboundedReceiver, UnboundedSender},
+    channel::mpsc::UnboundedReceiver,
     select,
     sink::SinkExt,
     stream::StreamExt,
@@ -25,31 +31,81 @@ use futures::{
 // tungstenite
 use async_tungstenite::tungstenite::{Error as TungsteniteError, Message as TungsteniteMessage};
 
+use serde_json::Value;
+
 type TungsteniteOptionResult = Option<StdResult<TungsteniteMessage, TungsteniteError>>;
 
+/// Filters applied to the received events, read from the gateway config for each event
+struct EventFilter<'a> {
+    intents: Intents,
+    replay_filter: &'a ReplayFilter,
+    skip_guild_members: bool,
+}
+
+/// Counts the dispatch events received before READY or RESUMED, after a RESUME they are the
+/// events replayed by Discord. It's counted before the filters, so dropped events are included
+#[derive(Default)]
+struct ReplayCounter {
+    session_started: bool,
+    replayed: u64,
+}
+
+impl ReplayCounter {
+    fn count(&mut self, event_name: Option<&str>) {
+        match event_name {
+            Some("READY") | Some("RESUMED") => self.session_started = true,
+            _ if !self.session_started => self.replayed += 1,
+            _ => {}
+        }
+    }
+}
+
 /// This function manages all library/gateway commands and events
 #[allow(unused_must_use)]
 pub(crate) async fn gateway_process(
     ws: WebSocket,
-    mut to_client: UnboundedSender<Event>,
+    to_client: EventSender,
     mut from_client: UnboundedReceiver<Command>,
     last_sequence: Arc<AtomicU64>,
+    intents: Arc<AtomicU64>,
+    replay_filter: Arc<ReplayFilter>,
+    skip_guild_members: Arc<AtomicBool>,
 ) {
     // Split the websocket
     let (mut ws_sender, ws_receiver) = ws.split();
     let mut from_gateway = ws_receiver.fuse();
+    let mut replay_counter = ReplayCounter::default();
     loop {
         select! {
             // gateway -> client
             tm = from_gateway.next()  => {
                 let last_sequence = Arc::clone(&last_sequence);
+                let intents = Intents::from_bits(intents.load(Ordering::Relaxed));
+                let filter = EventFilter {
+                    intents,
+                    replay_filter: &replay_filter,
+                    skip_guild_members: skip_guild_members.load(Ordering::Relaxed),
+                };
 
-                if let Err(e) = from_gateway_process(tm, &mut to_client, last_sequence).await {
+                let result = from_gateway_process(
+                    tm,
+                    &to_client,
+                    &mut ws_sender,
+                    last_sequence,
+                    filter,
+                    &mut replay_counter,
+                )
+                .await;
+                if let Err(e) = result {
                     log::error!("Error when receiving an event: {}", e);
                     // Check if there are unrecoverable errors
                     match e {
-                        PandaError::AuthenticationFailed | PandaError::ConnectionClosed => {
-                            to_client.send(Event::Close(e)).await.expect("EVENT CLOSE");
+                        // Discord closed the connection, the client reconnects or returns the error
+                        PandaError::ConnectionClosed
+                        | PandaError::GatewayClosed { .. }
+                        | PandaError::TungsteniteError(_)
+                        | PandaError::EventBufferFull => {
+                            to_client.send_close(e);
                             break;
                         },
                         _ => {},
@@ -62,8 +118,7 @@ pub(crate) async fn gateway_process(
                 // An error means that the connection was closed
                 if let Err(e) = to_gateway_process(cmd, &mut ws_sender, last_sequence).await {
                     log::error!("Error when sending command to gateway: {}", e);
-                    // Unhandled result, TODO: Handle result
-                    to_client.send(Event::Close(PandaError::ConnectionClosed)).await;
+                    to_client.send_close(PandaError::ConnectionClosed);
                     break;
                 }
             }
@@ -76,26 +131,76 @@ pub(crate) async fn gateway_process(
 /// This function manages all events received
 async fn from_gateway_process(
     tm: TungsteniteOptionResult,
-    to_client: &mut UnboundedSender<Event>,
+    to_client: &EventSender,
+    ws_sender: &mut WebSocketSender,
     last_sequence: Arc<AtomicU64>,
+    filter: EventFilter<'_>,
+    replay_counter: &mut ReplayCounter,
 ) -> Result<()> {
     // This error means connection error
     let tm = tm.ok_or_else(|| PandaError::ConnectionClosed)?;
     let msg = tm?;
 
     // Get Payload from TungsteniteMessage
-    let p = Payload::try_from(msg)?;
+    let mut p = Payload::try_from(msg)?;
+
+    // Discord can add new opcodes, they are ignored instead of closing the connection
+    if p.op == Opcode::Unknown {
+        log::warn!("Ignored payload with unknown opcode: {:?}", p);
+        return Ok(());
+    }
 
     // Get Payload sequence
     if let Some(seq) = p.s {
-        last_sequence.store(seq, Ordering::Relaxed);
+        let _previous = last_sequence.swap(seq, Ordering::Relaxed);
+
+        // A gap means that some dispatch was lost, useful to diagnose missed events
+        #[cfg(feature = "tracing")]
+        if let Some(missed) = sequence_gap(_previous, seq) {
+            log::warn!(
+                "Sequence gap detected: expected {}, received {} ({} events missed)",
+                _previous + 1,
+                seq,
+                missed
+            );
+        }
+
+        if p.op == Opcode::Dispatch {
+            replay_counter.count(p.t.as_deref());
+        }
+
+        // Discord can send again the events received before a RESUME
+        if p.op == Opcode::Dispatch && filter.replay_filter.is_replayed(p.t.as_deref(), seq) {
+            log::info!("Dropped replayed event with sequence {}", seq);
+            return Ok(());
+        }
+    }
+
+    // Ignore the events that can't be received with the configured intents, without parsing them
+    if is_filtered_by_intents(&p, filter.intents) {
+        return Ok(());
+    }
+
+    // The members are the biggest part of GUILD_CREATE, they aren't parsed if they aren't needed
+    if filter.skip_guild_members && p.t.as_deref() == Some("GUILD_CREATE") {
+        remove_guild_members(&mut p);
     }
 
     // Transform Event from Payload
-    let event = Event::try_from(p)?;
+    let mut event = Event::try_from(p)?;
+
+    if event.is_resumed() {
+        event = Event::Resumed(replay_counter.replayed);
+    }
+
+    // Discord asks for a heartbeat, it's sent here so it isn't delayed by the events in the buffer
+    if let Event::HeartbeatRequest = event {
+        log::info!("Heartbeat requested by Discord");
+        return to_gateway_process(Some(Command::new_heartbeat()), ws_sender, last_sequence).await;
+    }
 
     // Send Event to client
-    to_client.send(event).await.map_err(|_| PandaError::ConnectionClosed)?;
+    to_client.send(event).await?;
 
     Ok(())
 }
@@ -131,3 +236,127 @@ async fn to_gateway_process(
 
     Ok(())
 }
+
+/// Remove the members, presences and voice states of a GUILD_CREATE payload
+fn remove_guild_members(p: &mut Payload) {
+    if let Some(Value::Object(guild)) = &mut p.d {
+        guild.remove("members");
+        guild.remove("presences");
+        guild.remove("voice_states");
+    }
+}
+
+/// Returns true if the payload is a dispatch event outside of the intents
+fn is_filtered_by_intents(p: &Payload, intents: Intents) -> bool {
+    if p.op != Opcode::Dispatch {
+        return false;
+    }
+
+    match p.t.as_deref().and_then(Intents::for_event) {
+        Some(required) => !intents.intersects(required),
+        None => false,
+    }
+}
+
+/// Returns the number of missed events between the previous and the received sequence.
+/// The previous sequence is 0 for a new session, so there is nothing to compare
+#[cfg(feature = "tracing")]
+fn sequence_gap(previous: u64, received: u64) -> Option<u64> {
+    if previous == 0 || received <= previous + 1 {
+        return None;
+    }
+
+    Some(received - previous - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn dispatch(name: &str, d: Value) -> Payload {
+        Payload {
+            op: Opcode::Dispatch,
+            d: Some(d),
+            s: Some(1),
+            t: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn events_outside_intents_are_filtered() {
+        let p = dispatch("MESSAGE_CREATE", json!({}));
+        assert!(is_filtered_by_intents(&p, Intents::GUILDS));
+        assert!(!is_filtered_by_intents(&p, Intents::GUILD_MESSAGES));
+        assert!(!is_filtered_by_intents(&p, Intents::DIRECT_MESSAGES));
+    }
+
+    #[test]
+    fn events_without_intents_are_kept() {
+        let p = dispatch("READY", json!({}));
+        assert!(!is_filtered_by_intents(&p, Intents::empty()));
+
+        let hello = Payload {
+            op: Opcode::Hello,
+            d: None,
+            s: None,
+            t: None,
+        };
+        assert!(!is_filtered_by_intents(&hello, Intents::empty()));
+    }
+
+    #[test]
+    fn guild_members_are_removed() {
+        let mut p = dispatch(
+            "GUILD_CREATE",
+            json!({
+                "id": "1",
+                "name": "guild",
+                "members": [{"user": {"id": "2"}}],
+                "presences": [],
+                "voice_states": [],
+                "channels": []
+            }),
+        );
+        remove_guild_members(&mut p);
+
+        assert_eq!(p.d, Some(json!({"id": "1", "name": "guild", "channels": []})));
+    }
+
+    #[test]
+    fn counts_events_replayed_before_resumed() {
+        let mut counter = ReplayCounter::default();
+        counter.count(Some("MESSAGE_CREATE"));
+        counter.count(Some("MESSAGE_UPDATE"));
+        counter.count(Some("RESUMED"));
+        counter.count(Some("MESSAGE_CREATE"));
+
+        assert_eq!(counter.replayed, 2);
+    }
+
+    #[test]
+    fn new_session_has_no_replays() {
+        let mut counter = ReplayCounter::default();
+        counter.count(Some("READY"));
+        counter.count(Some("GUILD_CREATE"));
+
+        assert_eq!(counter.replayed, 0);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn sequence_gap_counts_missed_events() {
+        assert_eq!(sequence_gap(1, 2), None);
+        assert_eq!(sequence_gap(2, 4), Some(1));
+        assert_eq!(sequence_gap(10, 15), Some(4));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn sequence_gap_ignores_new_sessions_and_replays() {
+        assert_eq!(sequence_gap(0, 7), None);
+        assert_eq!(sequence_gap(5, 3), None);
+        assert_eq!(sequence_gap(5, 5), None);
+    }
+}
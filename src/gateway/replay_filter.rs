@@ -0,0 +1,67 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Drops the dispatch events that were already received, Discord can send them again after
+/// a RESUME. It's shared by all the connections of the client, disabled by default
+#[derive(Default)]
+pub(crate) struct ReplayFilter {
+    enabled: AtomicBool,
+    // Highest sequence received in the current session
+    last_processed: AtomicU64,
+}
+
+impl ReplayFilter {
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns true if the dispatch event was already received. A READY starts a new
+    /// session, so the sequence starts again
+    pub(crate) fn is_replayed(&self, event_name: Option<&str>, sequence: u64) -> bool {
+        if event_name == Some("READY") {
+            self.last_processed.store(0, Ordering::Relaxed);
+        }
+
+        let previous = self.last_processed.fetch_max(sequence, Ordering::Relaxed);
+
+        self.enabled.load(Ordering::Relaxed) && sequence <= previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_filter_keeps_everything() {
+        let filter = ReplayFilter::default();
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 5));
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 5));
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 3));
+    }
+
+    #[test]
+    fn drops_replayed_events() {
+        let filter = ReplayFilter::default();
+        filter.set_enabled(true);
+
+        for sequence in 1..=4 {
+            assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), sequence));
+        }
+
+        // After a RESUME, Discord sends again the events 3 and 4
+        assert!(!filter.is_replayed(Some("RESUMED"), 5));
+        assert!(filter.is_replayed(Some("MESSAGE_CREATE"), 3));
+        assert!(filter.is_replayed(Some("MESSAGE_CREATE"), 4));
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 6));
+    }
+
+    #[test]
+    fn ready_starts_a_new_session() {
+        let filter = ReplayFilter::default();
+        filter.set_enabled(true);
+
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 10));
+        assert!(!filter.is_replayed(Some("READY"), 1));
+        assert!(!filter.is_replayed(Some("MESSAGE_CREATE"), 2));
+    }
+}
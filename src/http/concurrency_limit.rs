@@ -0,0 +1,163 @@
+use futures::channel::oneshot;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// Used to limit the number of simultaneous requests, when the limit is reached
+/// new requests wait until a request is completed
+pub(crate) struct ConcurrencyLimit {
+    inner: Arc<Mutex<State>>,
+}
+
+struct State {
+    max: usize,
+    in_use: usize,
+    waiters: VecDeque<oneshot::Sender<Permit>>,
+}
+
+/// A permit to make a request, it's released when dropped
+pub(crate) struct Permit {
+    // None when the permit was already released
+    inner: Option<Arc<Mutex<State>>>,
+}
+
+impl ConcurrencyLimit {
+    pub(crate) fn new(max: usize) -> Self {
+        let state = State {
+            max: max.max(1),
+            in_use: 0,
+            waiters: VecDeque::new(),
+        };
+
+        ConcurrencyLimit {
+            inner: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Change the max number of simultaneous requests, it's at least 1
+    pub(crate) fn set_max(&self, max: usize) {
+        let mut state = self.inner.lock().unwrap();
+        state.max = max.max(1);
+
+        wake_waiters(&self.inner, &mut state);
+    }
+
+    /// Wait until a request can be made
+    pub(crate) async fn acquire(&self) -> Permit {
+        let receiver = {
+            let mut state = self.inner.lock().unwrap();
+
+            if state.in_use < state.max {
+                state.in_use += 1;
+                return Permit {
+                    inner: Some(Arc::clone(&self.inner)),
+                };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state.waiters.push_back(sender);
+            receiver
+        };
+
+        // The sender is only dropped with the limit, and the limit lives in the HttpClient
+        receiver.await.expect("Concurrency limit dropped")
+    }
+}
+
+/// Give permits to the waiters, while there are free slots
+fn wake_waiters(inner: &Arc<Mutex<State>>, state: &mut State) {
+    while state.in_use < state.max {
+        let sender = match state.waiters.pop_front() {
+            Some(sender) => sender,
+            None => break,
+        };
+
+        state.in_use += 1;
+        let permit = Permit {
+            inner: Some(Arc::clone(inner)),
+        };
+
+        // The waiter was cancelled, the permit is released here, because dropping it needs the lock
+        if let Err(mut permit) = sender.send(permit) {
+            permit.inner = None;
+            state.in_use -= 1;
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            let mut state = inner.lock().unwrap();
+            state.in_use -= 1;
+
+            wake_waiters(&inner, &mut state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::{executor::block_on, FutureExt};
+
+    fn in_use(limit: &ConcurrencyLimit) -> usize {
+        limit.inner.lock().unwrap().in_use
+    }
+
+    #[test]
+    fn waits_for_a_released_permit() {
+        let limit = ConcurrencyLimit::new(1);
+        let permit = block_on(limit.acquire());
+
+        let mut waiting = Box::pin(limit.acquire());
+        assert!((&mut waiting).now_or_never().is_none());
+
+        drop(permit);
+        let _permit = block_on(waiting);
+        assert_eq!(in_use(&limit), 1);
+    }
+
+    #[test]
+    fn cancelled_waiters_release_the_permit() {
+        let limit = ConcurrencyLimit::new(1);
+        let permit = block_on(limit.acquire());
+
+        assert!(limit.acquire().now_or_never().is_none());
+        drop(permit);
+
+        assert_eq!(in_use(&limit), 0);
+        assert!(limit.acquire().now_or_never().is_some());
+    }
+
+    #[test]
+    fn set_max_wakes_waiters() {
+        let limit = ConcurrencyLimit::new(0);
+        let _first = block_on(limit.acquire());
+
+        let mut waiting = Box::pin(limit.acquire());
+        assert!((&mut waiting).now_or_never().is_none());
+
+        limit.set_max(2);
+        assert!(waiting.now_or_never().is_some());
+    }
+
+    #[test]
+    fn third_request_waits_for_one_of_two_permits() {
+        let limit = ConcurrencyLimit::new(2);
+        let first = block_on(limit.acquire());
+        let _second = block_on(limit.acquire());
+
+        let mut third = Box::pin(limit.acquire());
+        assert!((&mut third).now_or_never().is_none());
+        assert!((&mut third).now_or_never().is_none());
+        assert_eq!(in_use(&limit), 2);
+
+        drop(first);
+        let third = third.now_or_never();
+        assert!(third.is_some());
+        assert_eq!(in_use(&limit), 2);
+    }
+}
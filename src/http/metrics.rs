@@ -0,0 +1,138 @@
+use crate::error::{PandaError, Result};
+
+use std::time::Duration;
+
+/// Information of a request made by the [`HttpClient`], given to [`HttpMetrics`].
+/// The uri is not included, because some routes have tokens in it
+///
+/// [`HttpClient`]: struct.HttpClient.html
+/// [`HttpMetrics`]: trait.HttpMetrics.html
+#[derive(Clone, Copy, Debug)]
+pub struct RequestInfo<'a> {
+    /// HTTP method, like "GET"
+    pub method: &'a str,
+
+    /// The rate limit bucket of the route, like "channels:639562328521703445"
+    pub bucket_key: &'a str,
+}
+
+/// Callbacks called by the [`HttpClient`] for every request, they can be used to collect metrics
+/// without panda depending on a metrics library. All methods do nothing by default
+///
+/// [`HttpClient`]: struct.HttpClient.html
+pub trait HttpMetrics: Send + Sync {
+    /// Called before sending the request
+    fn on_request(&self, _request: RequestInfo<'_>) {}
+
+    /// Called when the response is received, duration doesn't include the rate limit wait
+    fn on_response(&self, _request: RequestInfo<'_>, _status: u16, _duration: Duration) {}
+
+    /// Called when the request had to wait because the rate limit was reached, or when Discord
+    /// answered with a 429, then the wait is the `retry_after` of the response
+    fn on_rate_limited(&self, _request: RequestInfo<'_>, _wait: Duration) {}
+}
+
+/// Calls the metrics before sending the request, `waited` is how long the request waited for the
+/// rate limit
+pub(crate) fn report_request(metrics: Option<&dyn HttpMetrics>, info: RequestInfo<'_>, waited: Option<Duration>) {
+    if let Some(metrics) = metrics {
+        if let Some(wait) = waited {
+            metrics.on_rate_limited(info, wait);
+        }
+        metrics.on_request(info);
+    }
+}
+
+/// Calls the metrics when the response is received, `rate_limit` is the result of updating the
+/// bucket with the response
+pub(crate) fn report_response(
+    metrics: Option<&dyn HttpMetrics>,
+    info: RequestInfo<'_>,
+    status: u16,
+    duration: Duration,
+    rate_limit: &Result<()>,
+) {
+    if let Some(metrics) = metrics {
+        metrics.on_response(info, status, duration);
+
+        if let Err(PandaError::HttpRateLimited { retry_after, .. }) = rate_limit {
+            metrics.on_rate_limited(info, *retry_after);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    /// Records the calls as strings, like "response GET channels:1 200"
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl HttpMetrics for Recorder {
+        fn on_request(&self, request: RequestInfo<'_>) {
+            let call = format!("request {} {}", request.method, request.bucket_key);
+            self.0.lock().unwrap().push(call);
+        }
+
+        fn on_response(&self, request: RequestInfo<'_>, status: u16, _duration: Duration) {
+            let call = format!("response {} {} {}", request.method, request.bucket_key, status);
+            self.0.lock().unwrap().push(call);
+        }
+
+        fn on_rate_limited(&self, request: RequestInfo<'_>, wait: Duration) {
+            let call = format!("rate limited {} {:?}", request.bucket_key, wait);
+            self.0.lock().unwrap().push(call);
+        }
+    }
+
+    fn info() -> RequestInfo<'static> {
+        RequestInfo {
+            method: "GET",
+            bucket_key: "channels:1",
+        }
+    }
+
+    #[test]
+    fn report_request_and_response() {
+        let recorder = Recorder::default();
+
+        report_request(Some(&recorder), info(), None);
+        report_response(Some(&recorder), info(), 200, Duration::from_millis(50), &Ok(()));
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec!["request GET channels:1", "response GET channels:1 200"]
+        );
+    }
+
+    #[test]
+    fn report_rate_limits() {
+        let recorder = Recorder::default();
+        let rate_limited = Err(PandaError::HttpRateLimited {
+            retry_after: Duration::from_secs(2),
+            global: false,
+        });
+
+        report_request(Some(&recorder), info(), Some(Duration::from_secs(1)));
+        report_response(Some(&recorder), info(), 429, Duration::from_millis(50), &rate_limited);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                "rate limited channels:1 1s",
+                "request GET channels:1",
+                "response GET channels:1 429",
+                "rate limited channels:1 2s"
+            ]
+        );
+    }
+
+    #[test]
+    fn no_metrics() {
+        report_request(None, info(), Some(Duration::from_secs(1)));
+        report_response(None, info(), 200, Duration::from_millis(50), &Ok(()));
+    }
+}
@@ -1,31 +1,106 @@
+mod concurrency_limit;
+mod metrics;
+mod multipart;
 mod rate_limit;
-mod routing;
+pub(crate) mod routing;
 
+pub use metrics::{HttpMetrics, RequestInfo};
+
+use concurrency_limit::ConcurrencyLimit;
+use multipart::Multipart;
 use rate_limit::RateLimit;
-use routing::Route;
+use routing::{Route, DISCORD_API_URL};
 
 use crate::{
-    error::{PandaError, Result},
+    error::{PandaError, ResourceKind, Result},
     models::{
-        channel::{Channel, Embed, Message},
+        channel::{
+            ActiveThreads, Attachment, Channel, Embed, EmbedDefaults, EmbedFooter, FollowedChannel, Message, ThreadMember,
+        },
+        emoji::Emoji,
+        gateway::GatewayBot,
+        guild::{
+            AutoModerationRule, Guild, GuildMember, GuildPreview, GuildWidget, GuildWidgetSettings, Integration,
+            GuildTemplate, Onboarding, PartialGuild, Role, VanityUrl,
+        },
+        interaction::{CommandPermission, GuildCommandPermissions, InteractionResponse},
+        invite::Invite,
+        IntoId,
+        oauth2::AccessToken,
+        sticker::Sticker,
         user::User,
     },
+    utils::builders::{
+        validate_message, AddGuildMember, AttachmentFile, ChannelPosition, CreateAutoModerationRule, CreateMessage,
+        CreateSticker, ExecuteWebhook, GuildEdit, MessageEdit, ModifyOnboarding, RoleEdit,
+    },
 };
 
 use isahc::{
-    http::{Method, StatusCode},
+    http::StatusCode,
     prelude::*,
     HttpClient as IsachClient,
 };
+use futures::{future::join_all, io::AsyncReadExt};
 use serde::Serialize;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::Instant,
+};
 
 /// It's the http client of panda, it have methods to make requests to all routes
 pub struct HttpClient {
     token: String,
     client: IsachClient,
     rate_limit: RateLimit,
+    concurrency_limit: ConcurrencyLimit,
+    metrics: RwLock<Option<Arc<dyn HttpMetrics>>>,
+    embed_defaults: RwLock<EmbedDefaults>,
+    api_version: RwLock<String>,
+    strict_validation: AtomicBool,
 }
 
+/// Optional request settings, used by _send_request
+#[derive(Default)]
+struct RequestOptions<'a> {
+    /// Reason shown in the guild audit log
+    reason: Option<&'a str>,
+    /// Content type of the body, default is application/json
+    content_type: Option<&'a str>,
+    /// Don't send the bot token
+    without_authorization: bool,
+    /// Authorization header sent instead of the bot token, like a Bearer token
+    authorization: Option<&'a str>,
+}
+
+/// Default API version, used in the url of every request
+const DEFAULT_API_VERSION: &str = "v6";
+
+/// Default max number of simultaneous requests
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 50;
+
+/// JSON error code returned by Discord when the message doesn't exist, or it was deleted
+const UNKNOWN_MESSAGE_ERROR_CODE: u64 = 10008;
+
+/// JSON error code returned by Discord when the channel already has 50 pinned messages
+const MAX_PINS_ERROR_CODE: u64 = 30003;
+
+/// Max number of permissions of an application command
+const MAX_COMMAND_PERMISSIONS: usize = 100;
+
+/// Max number of guilds returned by GET /users/@me/guilds
+const MAX_GUILDS_PER_PAGE: u8 = 200;
+
+/// JSON error codes returned by Discord when the guild has the max number of a resource
+const MAX_ROLES_ERROR_CODE: u64 = 30005;
+const MAX_EMOJIS_ERROR_CODE: u64 = 30008;
+const MAX_STICKERS_ERROR_CODE: u64 = 30039;
+
 impl HttpClient {
     /// Creates a new http client
     pub fn new(token: impl Into<String>) -> HttpClient {
@@ -34,43 +109,198 @@ impl HttpClient {
             token: token.into(),
             client,
             rate_limit: RateLimit::default(),
+            concurrency_limit: ConcurrencyLimit::new(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            metrics: RwLock::new(None),
+            embed_defaults: RwLock::new(EmbedDefaults::default()),
+            api_version: RwLock::new(DEFAULT_API_VERSION.into()),
+            strict_validation: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the API version used in the url of the requests, like `v6`. Default is v6.
+    /// It fails with [`PandaError::Validation`] if the version doesn't look like `v` and a number
+    ///
+    /// [`PandaError::Validation`]: ../../panda/enum.PandaError.html#variant.Validation
+    pub fn set_api_version(&self, version: impl Into<String>) -> Result<()> {
+        let version = version.into();
+        validate_api_version(&version)?;
+
+        *self.api_version.write().unwrap() = version;
+
+        Ok(())
+    }
+
+    /// Returns the API version used in the url of the requests
+    pub fn api_version(&self) -> String {
+        self.api_version.read().unwrap().clone()
+    }
+
+    /// Check the required fields and the Discord limits of the messages, embeds and webhook
+    /// messages before sending them, they fail with a [`PandaError::Validation`] that names the
+    /// field instead of a generic 400 from Discord. It's useful for debugging, default false
+    ///
+    /// [`PandaError::Validation`]: ../../panda/enum.PandaError.html#variant.Validation
+    pub fn set_strict_validation(&self, strict: bool) {
+        self.strict_validation.store(strict, Ordering::Relaxed);
+    }
+
+    /// Returns true if the strict validation of the outbound payloads is enabled
+    pub fn strict_validation(&self) -> bool {
+        self.strict_validation.load(Ordering::Relaxed)
+    }
+
+    /// Set the [`HttpMetrics`] callbacks, they will be called for every request
+    ///
+    /// [`HttpMetrics`]: trait.HttpMetrics.html
+    pub fn set_metrics(&self, metrics: impl HttpMetrics + 'static) {
+        *self.metrics.write().unwrap() = Some(Arc::new(metrics));
+    }
+
+    /// Set the color used for the embeds sent without a color, `None` removes the default.
+    /// An embed can clear it with `set_color(None)`
+    pub fn set_default_embed_color(&self, color: Option<u64>) {
+        self.embed_defaults.write().unwrap().color = color;
+    }
+
+    /// Set the footer used for the embeds sent without a footer, `None` removes the default.
+    /// An embed can clear it with `remove_footer`
+    pub fn set_default_embed_footer(&self, footer: Option<EmbedFooter>) {
+        self.embed_defaults.write().unwrap().footer = footer;
+    }
+
+    /// Apply the default color and footer to the embeds
+    fn apply_embed_defaults<'a>(&self, embeds: impl Iterator<Item = &'a mut Embed>) {
+        let defaults = self.embed_defaults.read().unwrap();
+        for embed in embeds {
+            embed.apply_defaults(&defaults);
         }
     }
 
+    /// Set the max number of simultaneous requests, when it's reached new requests wait for
+    /// another request to complete. Default is 50
+    pub fn set_max_concurrent_requests(&self, max: usize) {
+        self.concurrency_limit.set_max(max);
+    }
+
     async fn _make_request<B: Into<Body>>(&self, route: Route<B>) -> Result<Response<Body>> {
-        // Check and wait if we reach the limit
-        self.rate_limit.check_and_sleep(&route.bucket_key).await;
-
-        // TODO: Improve this
-        let response = match route.method {
-            Method::GET | Method::PUT | Method::DELETE => {
-                let request = Request::builder()
-                    .method(route.method)
-                    .uri(&route.uri)
-                    .header("Authorization", &self.token)
-                    .body(())
-                    .unwrap();
-
-                // Get response
-                self.client.send_async(request).await?
-            }
-            Method::POST | Method::PATCH => {
-                let request = Request::builder()
-                    .method(route.method)
-                    .uri(&route.uri)
-                    .header("Authorization", &self.token)
-                    .header("Content-Type", "application/json")
-                    .body(route.body)
-                    .unwrap();
-
-                // Get response
-                self.client.send_async(request).await?
-            }
-            _ => unimplemented!(),
+        self._make_request_with_reason(route, None).await
+    }
+
+    /// Same as _make_request, but the reason will be shown in the guild audit log
+    async fn _make_request_with_reason<B: Into<Body>>(
+        &self,
+        route: Route<B>,
+        reason: Option<&str>,
+    ) -> Result<Response<Body>> {
+        let options = RequestOptions {
+            reason,
+            ..Default::default()
+        };
+
+        self._send_request(route, options).await
+    }
+
+    /// Same as _make_request, but the body is sent as multipart/form-data, with the payload and the files
+    async fn _make_multipart_request(
+        &self,
+        route: Route<()>,
+        payload: &impl Serialize,
+        files: &[AttachmentFile],
+    ) -> Result<Response<Body>> {
+        let mut multipart = Multipart::new();
+        multipart.add_json(payload)?;
+        for (index, file) in files.iter().enumerate() {
+            multipart.add_file(index, file);
+        }
+
+        self._make_form_request(route, multipart).await
+    }
+
+    /// Same as _make_request, but the body is already a multipart form
+    async fn _make_form_request(&self, route: Route<()>, multipart: Multipart) -> Result<Response<Body>> {
+        let (content_type, body) = multipart.finish();
+
+        let route = Route {
+            method: route.method,
+            uri: route.uri,
+            bucket_key: route.bucket_key,
+            body,
+        };
+
+        let options = RequestOptions {
+            content_type: Some(&content_type),
+            ..Default::default()
+        };
+
+        self._send_request(route, options).await
+    }
+
+    /// Same as _make_request, but the body is sent as application/x-www-form-urlencoded,
+    /// and without the bot token, used by OAuth2 routes
+    async fn _make_urlencoded_request(&self, route: Route<()>, form: String) -> Result<Response<Body>> {
+        let route = Route {
+            method: route.method,
+            uri: route.uri,
+            bucket_key: route.bucket_key,
+            body: form,
+        };
+
+        let options = RequestOptions {
+            content_type: Some("application/x-www-form-urlencoded"),
+            without_authorization: true,
+            ..Default::default()
+        };
+
+        self._send_request(route, options).await
+    }
+
+    async fn _send_request<B: Into<Body>>(&self, route: Route<B>, options: RequestOptions<'_>) -> Result<Response<Body>> {
+        let metrics = self.metrics.read().unwrap().clone();
+        let method = route.method.to_string();
+        let info = RequestInfo {
+            method: &method,
+            bucket_key: &route.bucket_key,
         };
 
-        // Update the limit with the response headers
-        self.rate_limit.update(route.bucket_key, &response).await;
+        // Check and wait if we reach the limit
+        let waited = self.rate_limit.check_and_sleep(&route.bucket_key).await;
+
+        metrics::report_request(metrics.as_deref(), info, waited);
+
+        let body: Body = route.body.into();
+
+        let uri = format!("{}/{}{}", DISCORD_API_URL, self.api_version.read().unwrap(), route.uri);
+        let mut request = Request::builder().method(route.method).uri(uri);
+
+        match options.authorization {
+            Some(authorization) => request = request.header("Authorization", authorization),
+            None if !options.without_authorization => request = request.header("Authorization", &self.token),
+            None => {}
+        }
+
+        // Only requests with a body needs the content type
+        if !body.is_empty() {
+            request = request.header("Content-Type", options.content_type.unwrap_or("application/json"));
+        }
+
+        if let Some(reason) = options.reason {
+            request = request.header("X-Audit-Log-Reason", encode_audit_log_reason(reason));
+        }
+
+        // Get response, the permit is released when the response is received
+        let permit = self.concurrency_limit.acquire().await;
+        let start = Instant::now();
+        let response = self.client.send_async(request.body(body).unwrap()).await;
+        drop(permit);
+        let mut response = response?;
+
+        let duration = start.elapsed();
+
+        // Update the limit with the response headers, a 429 returns an error
+        let rate_limit = self.rate_limit.update(route.bucket_key.clone(), &mut response).await;
+
+        metrics::report_response(metrics.as_deref(), info, response.status().as_u16(), duration, &rate_limit);
+        rate_limit?;
 
         Ok(response)
     }
@@ -81,14 +311,12 @@ impl HttpClient {
             StatusCode::OK
             | StatusCode::CREATED
             | StatusCode::NO_CONTENT
-            | StatusCode::NOT_MODIFIED
-            | StatusCode::TOO_MANY_REQUESTS => return Ok(()),
+            | StatusCode::NOT_MODIFIED => return Ok(()),
 
             StatusCode::BAD_REQUEST => PandaError::HttpImproperlyFormatted,
             StatusCode::FORBIDDEN => PandaError::HttpForbidden, // no autorizado
             StatusCode::NOT_FOUND => PandaError::HttpInvalidParameters, // not found or bad format
             StatusCode::METHOD_NOT_ALLOWED => PandaError::HttpNoResponse, // method not allowed
-            // HANDLED BY RATELIMIT StatusCode::TOO_MANY_REQUESTS => PandaError::HttpNoResponse,
             StatusCode::BAD_GATEWAY => PandaError::HttpNoResponse, // gateway unavailable
             _ => PandaError::HttpNoResponse,
         };
@@ -105,10 +333,11 @@ impl HttpClient {
     /// Get a channel by ID. Returns a [`Channel`] object, it will fail if the ID it's invalid
     ///
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
-    pub async fn get_channel(&self, channel_id: impl AsRef<str>) -> Result<Channel> {
+    pub async fn get_channel(&self, channel_id: impl IntoId) -> Result<Channel> {
         // Create Route
-        let route = Route::get_channel(channel_id);
+        let route = Route::get_channel(channel_id.as_id());
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -120,12 +349,13 @@ impl HttpClient {
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
     /// [`MessageEdit`]: ../../panda/utils/builder/struct.MessageEdit.html
     /// [`ChannelUpdate`]: ../../panda/models/gateway/events/struct.ChannelUpdate.html
-    pub async fn edit_channel(&self, channel_id: impl AsRef<str>, body: impl Serialize) -> Result<Channel> {
+    pub async fn edit_channel(&self, channel_id: impl IntoId, body: impl Serialize) -> Result<Channel> {
         // Create route
         let body = serde_json::to_string(&body)?;
-        let route = Route::edit_channel(channel_id, body);
+        let route = Route::edit_channel(channel_id.as_id(), body);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         // If an error wasn't returned, it's safe to unwrap
         Ok(res.json()?)
@@ -137,29 +367,58 @@ impl HttpClient {
     ///
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
     /// [`ChannelDelete`]: ../../panda/models/gateway/events/struct.ChannelDelete.html
-    pub async fn delete_channel(&self, channel_id: impl AsRef<str>) -> Result<Channel> {
+    pub async fn delete_channel(&self, channel_id: impl IntoId) -> Result<Channel> {
         // Parse URL
-        let route = Route::delete_channel(channel_id);
+        let route = Route::delete_channel(channel_id.as_id());
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
 
+    /// Returns a Vec<[`Message`]> with the latest messages of a channel (1 - 100). If operating
+    /// on a guild channel, this endpoint requires the **VIEW_CHANNEL** permission to be present
+    /// on the current user.
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn get_messages(&self, channel_id: impl IntoId, limit: u8) -> Result<Vec<Message>> {
+        // Create route
+        let route = Route::get_latest_channel_messages(channel_id.as_id(), limit);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Fetch the latest messages (1 - 100) of many channels at the same time, the requests are
+    /// limited by the max number of simultaneous requests. Returns the result of each channel,
+    /// so a channel without permissions doesn't fail the others. There is no cache, so the
+    /// messages are returned to be stored by the caller
+    pub async fn prefetch_messages<I, S>(&self, channel_ids: I, limit: u8) -> HashMap<String, Result<Vec<Message>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        prefetch_with(channel_ids, move |id| async move { self.get_messages(&id, limit).await }).await
+    }
+
     /// Returns a Vec<[`Message`]> of a channel. If operating on a guild channel, this endpoint
     /// requires the **VIEW_CHANNEL** permission to be present on the current user.
     ///
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
     pub async fn get_messages_around(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         limit: u8,
     ) -> Result<Vec<Message>> {
         // Create route
-        let route = Route::get_channel_messages("around", channel_id, message_id, limit);
+        let route = Route::get_channel_messages("around", channel_id.as_id(), message_id.as_id(), limit);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -170,14 +429,15 @@ impl HttpClient {
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
     pub async fn get_messages_before(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         limit: u8,
     ) -> Result<Vec<Message>> {
         // Create route
-        let route = Route::get_channel_messages("before", channel_id, message_id, limit);
+        let route = Route::get_channel_messages("before", channel_id.as_id(), message_id.as_id(), limit);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -188,14 +448,15 @@ impl HttpClient {
     /// [`Channel`]: ../../panda/models/channel/struct.Channel.html
     pub async fn get_messages_after(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         limit: u8,
     ) -> Result<Vec<Message>> {
         // Create route
-        let route = Route::get_channel_messages("after", channel_id, message_id, limit);
+        let route = Route::get_channel_messages("after", channel_id.as_id(), message_id.as_id(), limit);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -204,11 +465,17 @@ impl HttpClient {
     /// requires the **READ_MESSAGE_HISTORY** permission to be present on the current user.
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
-    pub async fn get_message(&self, channel_id: impl AsRef<str>, msg_id: impl AsRef<str>) -> Result<Message> {
+    pub async fn get_message(&self, channel_id: impl IntoId, msg_id: impl IntoId) -> Result<Message> {
         // Create route
-        let route = Route::get_channel_message(channel_id, msg_id);
+        let route = Route::get_channel_message(channel_id.as_id(), msg_id.as_id());
+
+        self._get_message(route).await
+    }
 
+    /// Same as get_message, with the route already built
+    pub(crate) async fn _get_message(&self, route: Route<()>) -> Result<Message> {
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -218,7 +485,11 @@ impl HttpClient {
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageCreate`]: ../../panda/models/gateway/events/struct.MessageCreate.html
-    pub async fn send_message(&self, channel_id: impl AsRef<str>, content: impl AsRef<str>) -> Result<Message> {
+    pub async fn send_message(&self, channel_id: impl IntoId, content: impl AsRef<str>) -> Result<Message> {
+        if self.strict_validation() {
+            validate_message(Some(content.as_ref()), std::iter::empty())?;
+        }
+
         // Create message body
         let body = serde_json::json!({
             "content": content.as_ref(),
@@ -228,18 +499,32 @@ impl HttpClient {
         let body = serde_json::to_string(&body)?;
 
         // Create route
-        let route = Route::create_message(channel_id, body);
+        let route = Route::create_message(channel_id.as_id(), body);
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
 
+    /// Send a text to speech message to the channel, it's read aloud to the users viewing
+    /// the channel. Requires the **SEND_TTS_MESSAGES** permission
+    pub async fn send_tts(&self, channel_id: impl IntoId, content: impl Into<String>) -> Result<Message> {
+        let builder = CreateMessage::new().content(content).tts(true);
+
+        self.create_message(channel_id, builder).await
+    }
+
     /// Creates a new message, and returns the [`Message`]. This will also trigger
     /// [`MessageCreate`] event
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageCreate`]: ../../panda/models/gateway/events/struct.MessageCreate.html
-    pub async fn send_embed(&self, channel_id: impl AsRef<str>, embed: Embed) -> Result<Message> {
+    pub async fn send_embed(&self, channel_id: impl IntoId, mut embed: Embed) -> Result<Message> {
+        self.apply_embed_defaults(std::iter::once(&mut embed));
+        if self.strict_validation() {
+            validate_message(None, std::iter::once(&embed))?;
+        }
+
         let body = serde_json::json!({
             "embed": embed,
             "tts": false
@@ -248,9 +533,33 @@ impl HttpClient {
         let body = serde_json::to_string(&body)?;
 
         // Create route
-        let route = Route::create_message(channel_id, body);
+        let route = Route::create_message(channel_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Creates a new message from a [`CreateMessage`] builder, and returns the [`Message`].
+    /// This will also trigger [`MessageCreate`] event
+    ///
+    /// [`CreateMessage`]: ../../panda/utils/builders/struct.CreateMessage.html
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    /// [`MessageCreate`]: ../../panda/models/gateway/events/struct.MessageCreate.html
+    pub async fn create_message(&self, channel_id: impl IntoId, mut builder: CreateMessage) -> Result<Message> {
+        builder.validate()?;
+        self.apply_embed_defaults(builder.embeds_mut());
+        if self.strict_validation() {
+            builder.validate_strict()?;
+        }
+        let body = serde_json::to_string(&builder)?;
+
+        // Create route
+        let route = Route::create_message(channel_id.as_id(), body);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -261,14 +570,15 @@ impl HttpClient {
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     pub async fn add_reaction(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         emoji: impl AsRef<str>,
     ) -> Result<()> {
         // Create route
-        let route = Route::create_reaction(channel_id, message_id, emoji);
+        let route = Route::create_reaction(channel_id.as_id(), message_id.as_id(), emoji);
 
-        let _res = self._make_request(route).await?;
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(())
     }
@@ -279,12 +589,12 @@ impl HttpClient {
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     pub async fn remove_own_reaction(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         emoji: impl AsRef<str>,
     ) -> Result<()> {
         // Create route
-        let route = Route::delete_own_reaction(channel_id, message_id, emoji);
+        let route = Route::delete_own_reaction(channel_id.as_id(), message_id.as_id(), emoji);
 
         let _res = self._make_request(route).await?;
 
@@ -299,15 +609,16 @@ impl HttpClient {
     /// [`User`]: ../../panda/models/user/struct.User.html
     pub async fn remove_user_reaction(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
-        user_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        user_id: impl IntoId,
         emoji: impl AsRef<str>,
     ) -> Result<()> {
         // Create route
-        let route = Route::delete_user_reaction(channel_id, message_id, emoji, user_id);
+        let route = Route::delete_user_reaction(channel_id.as_id(), message_id.as_id(), emoji, user_id.as_id());
 
-        let _res = self._make_request(route).await?;
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(())
     }
@@ -320,13 +631,14 @@ impl HttpClient {
     /// [`User`]: ../../panda/models/user/struct.User.html
     pub async fn get_reactions(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         emoji: impl AsRef<str>,
     ) -> Result<Vec<User>> {
-        let route = Route::get_reactions(channel_id, message_id, emoji);
+        let route = Route::get_reactions(channel_id.as_id(), message_id.as_id(), emoji);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
@@ -336,10 +648,11 @@ impl HttpClient {
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageReactionRemoveAll`]: ../../panda/models/gateway/events/struct.MessageReactionRemoveAll.html
-    pub async fn remove_all_reactions(&self, channel_id: impl AsRef<str>, message_id: impl AsRef<str>) -> Result<()> {
-        let route = Route::delete_all_reactions(channel_id, message_id);
+    pub async fn remove_all_reactions(&self, channel_id: impl IntoId, message_id: impl IntoId) -> Result<()> {
+        let route = Route::delete_all_reactions(channel_id.as_id(), message_id.as_id());
 
-        let _res = self._make_request(route).await?;
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(())
     }
@@ -351,57 +664,175 @@ impl HttpClient {
     /// [`MessageReactionRemoveEmoji`]: ../../panda/models/gateway/events/struct.MessageReactionRemoveEmoji.html
     pub async fn remove_all_emoji_reactions(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
         emoji: impl AsRef<str>,
     ) -> Result<()> {
-        let route = Route::delete_all_reactions_for_emoji(channel_id, message_id, emoji);
+        let route = Route::delete_all_reactions_for_emoji(channel_id.as_id(), message_id.as_id(), emoji);
 
         let _res = self._make_request(route).await?;
 
         Ok(())
     }
 
-    /// Edits message, and returns the [`Message`]. This will also trigger [`MessageUpdate`] event
+    /// Edits message with a [`MessageEdit`] builder, and returns the [`Message`]. If the builder has
+    /// files, they are uploaded with the edit. This will also trigger [`MessageUpdate`] event
     ///
+    /// [`MessageEdit`]: ../../panda/utils/builders/struct.MessageEdit.html
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageUpdate`]: ../../panda/models/gateway/events/struct.MessageUpdate.html
     pub async fn edit_message(
         &self,
-        channel_id: impl AsRef<str>,
-        message_id: impl AsRef<str>,
-        body: impl Serialize,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        builder: MessageEdit,
     ) -> Result<Message> {
-        let body = serde_json::to_string(&body)?;
+        let mut res = self._edit_message(channel_id, message_id, builder).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Same as edit_message, but returns the response without checking the status
+    async fn _edit_message(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        mut builder: MessageEdit,
+    ) -> Result<Response<Body>> {
+        self.apply_embed_defaults(builder.embeds_mut());
+        if self.strict_validation() {
+            builder.validate_strict()?;
+        }
+        let (payload, files) = builder.into_payload()?;
 
-        let route = Route::edit_message(channel_id, message_id, body);
+        if files.is_empty() {
+            let body = serde_json::to_string(&payload)?;
+            let route = Route::edit_message(channel_id.as_id(), message_id.as_id(), body);
 
-        let mut res = self._make_request(route).await?;
+            self._make_request(route).await
+        } else {
+            let route = Route::edit_message(channel_id.as_id(), message_id.as_id(), ());
 
-        Ok(res.json()?)
+            self._make_multipart_request(route, &payload, &files).await
+        }
+    }
+
+    /// Edits the existing message with the content, and sends a new message when there is no
+    /// existing message or it was deleted. Useful for status messages, the id of the returned
+    /// [`Message`] can be stored for the next call
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn edit_or_send(
+        &self,
+        channel_id: impl IntoId,
+        existing: Option<&str>,
+        content: impl Into<String>,
+    ) -> Result<Message> {
+        let content = content.into();
+        let channel_id = channel_id.as_id();
+
+        let edit = existing.map(|message_id| {
+            let builder = MessageEdit::new().content(content.clone());
+            self._edit_message(channel_id, message_id, builder)
+        });
+        let send = self.send_message(channel_id, &content);
+
+        self._edit_or_send(edit, send).await
+    }
+
+    /// Returns the edited message, or the result of `send` if there is nothing to edit or
+    /// Discord doesn't know the message. Other errors of the edit are returned
+    async fn _edit_or_send(
+        &self,
+        edit: Option<impl Future<Output = Result<Response<Body>>>>,
+        send: impl Future<Output = Result<Message>>,
+    ) -> Result<Message> {
+        if let Some(edit) = edit {
+            let mut res = edit.await?;
+
+            // Unknown message, send a new one
+            let deleted = res.status() == StatusCode::NOT_FOUND
+                && discord_error_code(&mut res) == Some(UNKNOWN_MESSAGE_ERROR_CODE);
+
+            if !deleted {
+                self._catch_http_errors(&res)?;
+                return Ok(res.json()?);
+            }
+        }
+
+        send.await
     }
 
     /// Delete a [`Message`], This will also trigger [`MessageDelete`] event
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageDelete`]: ../../panda/models/gateway/events/struct.MessageDelete.html
-    pub async fn delete_message(&self, channel_id: impl AsRef<str>, message_id: impl AsRef<str>) -> Result<()> {
-        let route = Route::delete_message(channel_id, message_id);
+    pub async fn delete_message(&self, channel_id: impl IntoId, message_id: impl IntoId) -> Result<()> {
+        let route = Route::delete_message(channel_id.as_id(), message_id.as_id());
 
         let _res = self._make_request(route).await?;
 
         Ok(())
     }
 
+    /// Delete a [`Message`], the reason will be shown in the guild audit log.
+    /// This will also trigger [`MessageDelete`] event
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    /// [`MessageDelete`]: ../../panda/models/gateway/events/struct.MessageDelete.html
+    pub async fn delete_message_with_reason(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        reason: impl AsRef<str>,
+    ) -> Result<()> {
+        let route = Route::delete_message(channel_id.as_id(), message_id.as_id());
+
+        let res = self._make_request_with_reason(route, Some(reason.as_ref())).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Delete the latest messages (up to 100) of the channel that match the filter, and
+    /// returns the number of deleted messages. Messages newer than 2 weeks are deleted in bulk,
+    /// older messages are deleted one by one. Requires the **MANAGE_MESSAGES** permission.
+    ///
+    /// To delete all the messages use `|_| true` as filter.
+    pub async fn purge_messages<F>(&self, channel_id: impl IntoId, count: u8, filter: F) -> Result<usize>
+    where
+        F: Fn(&Message) -> bool,
+    {
+        let channel_id = channel_id.as_id();
+        let messages = self.get_messages(channel_id, count.min(100)).await?;
+
+        let ids: Vec<&str> = messages.iter().filter(|m| filter(m)).map(|m| m.id.as_str()).collect();
+        let (bulk, single) = split_by_bulk_delete_age(&ids, current_timestamp());
+
+        // Bulk delete only accepts 2 - 100 messages
+        match bulk.len() {
+            0 => {}
+            1 => self.delete_message(channel_id, bulk[0]).await?,
+            _ => self.delete_many_messages(channel_id, &bulk).await?,
+        }
+
+        for id in &single {
+            self.delete_message(channel_id, id).await?;
+        }
+
+        Ok(bulk.len() + single.len())
+    }
+
     /// Delete a a bulk of [`Message`] (2 - 100), This will also trigger [`MessageDeleteBulk`] event.
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageDelete`]: ../../panda/models/gateway/events/struct.MessageDelete.html
-    pub async fn delete_many_messages(&self, channel_id: impl AsRef<str>, messages: &[&str]) -> Result<()> {
+    pub async fn delete_many_messages(&self, channel_id: impl IntoId, messages: &[&str]) -> Result<()> {
         let body = serde_json::json!({ "messages": messages });
         let body = serde_json::to_string(&body).unwrap();
 
-        let route = Route::bulk_delete_messages(channel_id, body);
+        let route = Route::bulk_delete_messages(channel_id.as_id(), body);
 
         let _res = self._make_request(route).await?;
 
@@ -413,7 +844,7 @@ impl HttpClient {
     ///
     /// [`Message`]: ../../panda/models/channel/struct.Message.html
     /// [`MessageDelete`]: ../../panda/models/gateway/events/struct.MessageDelete.html
-    pub async fn edit_channel_permissions(&self, _channel_id: impl AsRef<str>) -> Result<()> {
+    pub async fn edit_channel_permissions(&self, _channel_id: impl IntoId) -> Result<()> {
         unimplemented!();
         // // Create RateLimit Key
         // let route = Route::edit_channel_permissions(channel_id, overwrite_id, body);
@@ -423,69 +854,1495 @@ impl HttpClient {
         // Ok(())
     }
 
-    pub async fn get_channel_invites(&self, channel_id: impl AsRef<str>) -> Result<()> {
-        let route = Route::get_channel_invite(channel_id);
-        let res = self._make_request(route).await?;
+    /// Returns a Vec of [`Invite`] (with metadata) for the channel. Only usable for guild channels.
+    /// Requires the **MANAGE_CHANNELS** permission.
+    ///
+    /// [`Invite`]: ../../panda/models/invite/struct.Invite.html
+    pub async fn get_channel_invites(&self, channel_id: impl IntoId) -> Result<Vec<Invite>> {
+        let route = Route::get_channel_invites(channel_id.as_id());
 
-        dbg!(res);
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
-        Ok(())
+        Ok(res.json()?)
     }
-    // // pub async fn create_channel_invite() {}
 
-    // // pub async fn delete_channel_permissions() {}
+    /// Returns the [`Guild`] with the given id, the bot must be a member of the guild.
+    /// The fields only sent with GUILD_CREATE, like `members` and `channels`, are empty
+    ///
+    /// [`Guild`]: ../../panda/models/guild/struct.Guild.html
+    pub async fn get_guild(&self, guild_id: impl IntoId) -> Result<Guild> {
+        let route = Route::get_guild(guild_id.as_id(), false);
 
-    /// Post a typing indicator for the specified channel.
-    /// Fires a [`TypingStart`] Gateway event
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Modify a guild with a [`GuildEdit`] builder, and returns the updated [`Guild`].
+    /// Requires the **MANAGE_GUILD** permission. Fires a [`GuildUpdate`] event.
     ///
-    /// [`TypingStart`]: ../../panda/models/gateway/events/struct.TypingStart.html
-    pub async fn trigger_typing(&self, channel_id: impl AsRef<str>) -> Result<()> {
-        let route = Route::trigger_typing_indicator(channel_id);
-        let _res = self._make_request(route).await?;
+    /// [`GuildEdit`]: ../../panda/utils/builders/struct.GuildEdit.html
+    /// [`Guild`]: ../../panda/models/guild/struct.Guild.html
+    /// [`GuildUpdate`]: ../../panda/models/gateway/events/struct.GuildUpdate.html
+    pub async fn modify_guild(&self, guild_id: impl IntoId, builder: GuildEdit) -> Result<Guild> {
+        builder.validate()?;
 
-        Ok(())
+        let body = serde_json::to_string(&builder)?;
+        let route = Route::modify_guild(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
     }
 
-    /// Returns all pinned messages in the channel as a Vec of [`Message`] objects.
+    /// Same as [`get_guild`], but the guild has the `approximate_member_count` and
+    /// `approximate_presence_count` fields
     ///
-    /// [`Message`]: ../../panda/models/channel/struct.Message.html
-    pub async fn get_pinned_messages(&self, channel_id: impl AsRef<str>) -> Result<Vec<Message>> {
+    /// [`get_guild`]: #method.get_guild
+    pub async fn get_guild_with_counts(&self, guild_id: impl IntoId) -> Result<Guild> {
+        let route = Route::get_guild(guild_id.as_id(), true);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
 
-        // TODO: Returns a 204 empty response on success.
+    /// Returns the [`GuildPreview`] of a guild. If the user is not in the guild,
+    /// the guild must be discoverable.
+    ///
+    /// [`GuildPreview`]: ../../panda/models/guild/struct.GuildPreview.html
+    pub async fn get_guild_preview(&self, guild_id: impl IntoId) -> Result<GuildPreview> {
+        let route = Route::get_guild_preview(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`GuildTemplate`] with the code, like the one of a `discord.new` link
+    ///
+    /// [`GuildTemplate`]: ../../panda/models/guild/struct.GuildTemplate.html
+    pub async fn get_guild_template(&self, code: impl AsRef<str>) -> Result<GuildTemplate> {
+        let route = Route::get_guild_template(code);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Create a guild from a template, the bot is the owner of the new guild. The icon is a
+    /// data URI of an image. Only bots in less than 10 guilds can create guilds
+    pub async fn create_guild_from_template(
+        &self,
+        code: impl AsRef<str>,
+        name: impl AsRef<str>,
+        icon: Option<&str>,
+    ) -> Result<Guild> {
+        let name = name.as_ref();
+        if !(2..=100).contains(&name.chars().count()) {
+            return Err(PandaError::Validation {
+                field: "name",
+                reason: "the name of a guild must be between 2 and 100 characters".into(),
+            });
+        }
 
-        let route = Route::get_pinned_messages(channel_id);
+        let body = serde_json::json!({
+            "name": name,
+            "icon": icon,
+        });
+        let body = serde_json::to_string(&body)?;
+        let route = Route::create_guild_from_template(code, body);
 
         let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(res.json()?)
     }
 
-    /// Pin a message in a channel. Requires the **MANAGE_MESSAGES** permission
-    /// **The max pinned messages is 50.**
-    pub async fn pin_message(&self, channel_id: impl AsRef<str>, message_id: impl AsRef<str>) -> Result<()> {
+    /// Create a template of the guild, the description is optional.
+    /// Requires the **MANAGE_GUILD** permission
+    pub async fn create_guild_template(
+        &self,
+        guild_id: impl IntoId,
+        name: impl AsRef<str>,
+        description: Option<&str>,
+    ) -> Result<GuildTemplate> {
+        let name = name.as_ref();
+        if !(1..=100).contains(&name.chars().count()) {
+            return Err(PandaError::Validation {
+                field: "name",
+                reason: "the name of a template must be between 1 and 100 characters".into(),
+            });
+        }
+
+        if description.map_or(false, |d| d.chars().count() > 120) {
+            return Err(PandaError::Validation {
+                field: "description",
+                reason: "the description of a template can't be longer than 120 characters".into(),
+            });
+        }
+
+        let body = serde_json::json!({
+            "name": name,
+            "description": description,
+        });
+        let body = serde_json::to_string(&body)?;
+        let route = Route::create_guild_template(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
 
-        // TODO: Returns a 204 empty response on success.
+    /// Change the positions of the channels of the guild, only the moved channels are needed.
+    /// Requires the **MANAGE_CHANNELS** permission. Fires multiple [`ChannelUpdate`] events.
+    ///
+    /// [`ChannelUpdate`]: ../../panda/models/gateway/events/struct.ChannelUpdate.html
+    pub async fn modify_guild_channel_positions(
+        &self,
+        guild_id: impl IntoId,
+        positions: Vec<ChannelPosition>,
+    ) -> Result<()> {
+        for (i, position) in positions.iter().enumerate() {
+            if positions[..i].iter().any(|p| p.id == position.id) {
+                return Err(PandaError::Validation {
+                    field: "positions",
+                    reason: format!("the channel {} is repeated", position.id),
+                });
+            }
+        }
 
-        let route = Route::add_pinned_channel_message(channel_id, message_id);
+        let body = serde_json::to_string(&positions)?;
+        let route = Route::modify_guild_channel_positions(guild_id.as_id(), body);
 
-        let _ = self._make_request(route).await?;
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
         Ok(())
     }
 
-    /// Pin a message in a channel. Requires the **MANAGE_MESSAGES** permission.
-    pub async fn unpin_message(&self, channel_id: impl AsRef<str>, message_id: impl AsRef<str>) -> Result<()> {
+    /// Returns the [`GuildWidgetSettings`] of the guild. Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`GuildWidgetSettings`]: ../../panda/models/guild/struct.GuildWidgetSettings.html
+    pub async fn get_guild_widget_settings(&self, guild_id: impl IntoId) -> Result<GuildWidgetSettings> {
+        let route = Route::get_guild_widget_settings(guild_id.as_id());
 
-        // TODO: Returns a 204 empty response on success.
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
 
-        let route = Route::delete_pinned_channel_message(channel_id, message_id);
+        Ok(res.json()?)
+    }
 
-        let _ = self._make_request(route).await?;
+    /// Modify the widget of the guild, and returns the updated [`GuildWidgetSettings`].
+    /// Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`GuildWidgetSettings`]: ../../panda/models/guild/struct.GuildWidgetSettings.html
+    pub async fn modify_guild_widget(
+        &self,
+        guild_id: impl IntoId,
+        settings: GuildWidgetSettings,
+    ) -> Result<GuildWidgetSettings> {
+        let body = serde_json::to_string(&settings)?;
+        let route = Route::modify_guild_widget(guild_id.as_id(), body);
 
-        Ok(())
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
     }
 
-    // PUT/channels/{channel.id}/recipients/{user.id}
+    /// Returns the [`Onboarding`] of the guild
+    ///
+    /// [`Onboarding`]: ../../panda/models/guild/struct.Onboarding.html
+    pub async fn get_guild_onboarding(&self, guild_id: impl IntoId) -> Result<Onboarding> {
+        let route = Route::get_guild_onboarding(guild_id.as_id());
 
-    // DELETE/channels/{channel.id}/recipients/{user.id}
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Modify the onboarding of the guild, and returns the updated [`Onboarding`]. Requires
+    /// the **MANAGE_GUILD** and **MANAGE_ROLES** permissions. It's recommended to use
+    /// [`ModifyOnboarding`] builder.
+    ///
+    /// [`Onboarding`]: ../../panda/models/guild/struct.Onboarding.html
+    /// [`ModifyOnboarding`]: ../../panda/utils/builders/struct.ModifyOnboarding.html
+    pub async fn modify_guild_onboarding(
+        &self,
+        guild_id: impl IntoId,
+        builder: ModifyOnboarding,
+    ) -> Result<Onboarding> {
+        let body = serde_json::to_string(&builder)?;
+        let route = Route::modify_guild_onboarding(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the public [`GuildWidget`] of the guild, the widget must be enabled
+    ///
+    /// [`GuildWidget`]: ../../panda/models/guild/struct.GuildWidget.html
+    pub async fn get_guild_widget(&self, guild_id: impl IntoId) -> Result<GuildWidget> {
+        let route = Route::get_guild_widget(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`VanityUrl`] of the guild, with the code and the number of uses.
+    /// Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`VanityUrl`]: ../../panda/models/guild/struct.VanityUrl.html
+    pub async fn get_guild_vanity_url(&self, guild_id: impl IntoId) -> Result<VanityUrl> {
+        let route = Route::get_guild_vanity_url(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`ActiveThreads`] of the guild, with the thread members of the bot
+    ///
+    /// [`ActiveThreads`]: ../../panda/models/channel/struct.ActiveThreads.html
+    pub async fn list_active_threads(&self, guild_id: impl IntoId) -> Result<ActiveThreads> {
+        let route = Route::list_active_threads(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns a Vec of [`ThreadMember`] of the thread. Requires the **GUILD_MEMBERS** intent
+    ///
+    /// [`ThreadMember`]: ../../panda/models/channel/struct.ThreadMember.html
+    pub async fn list_thread_members(&self, channel_id: impl IntoId) -> Result<Vec<ThreadMember>> {
+        let route = Route::list_thread_members(channel_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns a Vec of [`Integration`] of the guild. Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`Integration`]: ../../panda/models/guild/struct.Integration.html
+    pub async fn get_guild_integrations(&self, guild_id: impl IntoId) -> Result<Vec<Integration>> {
+        let route = Route::get_guild_integrations(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Delete an [`Integration`] of the guild, the bot of the integration is kicked.
+    /// Requires the **MANAGE_GUILD** permission. Fires a [`GuildIntegrationsUpdate`] event.
+    ///
+    /// [`Integration`]: ../../panda/models/guild/struct.Integration.html
+    /// [`GuildIntegrationsUpdate`]: ../../panda/models/gateway/events/struct.GuildIntegrationsUpdate.html
+    pub async fn delete_guild_integration(
+        &self,
+        guild_id: impl IntoId,
+        integration_id: impl IntoId,
+    ) -> Result<()> {
+        let route = Route::delete_guild_integration(guild_id.as_id(), integration_id.as_id());
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Returns a Vec of [`Invite`] (with metadata) for the guild.
+    /// Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`Invite`]: ../../panda/models/invite/struct.Invite.html
+    pub async fn get_guild_invites(&self, guild_id: impl IntoId) -> Result<Vec<Invite>> {
+        let route = Route::get_guild_invites(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Follow an announcement channel, to send its messages to the target channel.
+    /// Returns a [`FollowedChannel`] with the ID of the created target webhook. Requires the
+    /// **MANAGE_WEBHOOKS** permission in the target channel.
+    ///
+    /// [`FollowedChannel`]: ../../panda/models/channel/struct.FollowedChannel.html
+    pub async fn follow_announcement_channel(
+        &self,
+        announcement_channel_id: impl IntoId,
+        target_channel_id: impl IntoId,
+    ) -> Result<FollowedChannel> {
+        let body = serde_json::json!({ "webhook_channel_id": target_channel_id.as_id() });
+        let body = serde_json::to_string(&body)?;
+
+        let route = Route::follow_news_channel(announcement_channel_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    // // pub async fn create_channel_invite() {}
+
+    // // pub async fn delete_channel_permissions() {}
+
+    /// Post a typing indicator for the specified channel.
+    /// Fires a [`TypingStart`] Gateway event
+    ///
+    /// [`TypingStart`]: ../../panda/models/gateway/events/struct.TypingStart.html
+    pub async fn trigger_typing(&self, channel_id: impl IntoId) -> Result<()> {
+        let route = Route::trigger_typing_indicator(channel_id.as_id());
+        let _res = self._make_request(route).await?;
+
+        Ok(())
+    }
+
+    /// Ends the poll of a message sent by the bot, and returns the [`Message`] with the
+    /// finalized results
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn end_poll(&self, channel_id: impl IntoId, message_id: impl IntoId) -> Result<Message> {
+        let route = Route::end_poll(channel_id.as_id(), message_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`User`]s that voted for the answer of a poll
+    ///
+    /// [`User`]: ../../panda/models/user/struct.User.html
+    pub async fn get_poll_answer_voters(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        answer_id: u64,
+    ) -> Result<Vec<User>> {
+        #[derive(serde::Deserialize)]
+        struct AnswerVoters {
+            users: Vec<User>,
+        }
+
+        let route = Route::get_answer_voters(channel_id.as_id(), message_id.as_id(), answer_id);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        let voters: AnswerVoters = res.json()?;
+
+        Ok(voters.users)
+    }
+
+    /// Returns all pinned messages in the channel as a Vec of [`Message`] objects.
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn get_pinned_messages(&self, channel_id: impl IntoId) -> Result<Vec<Message>> {
+        let route = Route::get_pinned_messages(channel_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Pin a message in a channel. Requires the **MANAGE_MESSAGES** permission.
+    /// **The max pinned messages is 50**, it fails with [`PandaError::PinLimitReached`] when
+    /// the channel already has 50 pins.
+    ///
+    /// [`PandaError::PinLimitReached`]: ../../panda/enum.PandaError.html#variant.PinLimitReached
+    pub async fn pin_message(&self, channel_id: impl IntoId, message_id: impl IntoId) -> Result<()> {
+        self._pin_message(channel_id, message_id, None).await
+    }
+
+    /// Same as [`pin_message`], but the reason will be shown in the guild audit log
+    ///
+    /// [`pin_message`]: #method.pin_message
+    pub async fn pin_message_with_reason(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        reason: impl AsRef<str>,
+    ) -> Result<()> {
+        self._pin_message(channel_id, message_id, Some(reason.as_ref())).await
+    }
+
+    async fn _pin_message(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let route = Route::add_pinned_channel_message(channel_id.as_id(), message_id.as_id());
+
+        let mut res = self._make_request_with_reason(route, reason).await?;
+
+        if let StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN = res.status() {
+            if discord_error_code(&mut res) == Some(MAX_PINS_ERROR_CODE) {
+                return Err(PandaError::PinLimitReached);
+            }
+        }
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Unpin a message in a channel. Requires the **MANAGE_MESSAGES** permission.
+    pub async fn unpin_message(&self, channel_id: impl IntoId, message_id: impl IntoId) -> Result<()> {
+        let route = Route::delete_pinned_channel_message(channel_id.as_id(), message_id.as_id());
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Same as [`unpin_message`], but the reason will be shown in the guild audit log
+    ///
+    /// [`unpin_message`]: #method.unpin_message
+    pub async fn unpin_message_with_reason(
+        &self,
+        channel_id: impl IntoId,
+        message_id: impl IntoId,
+        reason: impl AsRef<str>,
+    ) -> Result<()> {
+        let route = Route::delete_pinned_channel_message(channel_id.as_id(), message_id.as_id());
+
+        let res = self._make_request_with_reason(route, Some(reason.as_ref())).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Returns up to 200 guilds of the bot, sorted by id, starting after the given guild id.
+    /// To get all the guilds use [`get_all_current_user_guilds`]
+    ///
+    /// [`get_all_current_user_guilds`]: #method.get_all_current_user_guilds
+    pub async fn get_current_user_guilds(&self, after: Option<&str>, limit: u8) -> Result<Vec<PartialGuild>> {
+        let route = Route::get_current_user_guilds(after, limit.min(MAX_GUILDS_PER_PAGE));
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns all the guilds of the bot, they are requested in pages of 200 guilds
+    pub async fn get_all_current_user_guilds(&self) -> Result<Vec<PartialGuild>> {
+        let mut guilds: Vec<PartialGuild> = Vec::new();
+
+        loop {
+            let after = guilds.last().map(|g| g.id.as_str());
+            let page = self.get_current_user_guilds(after, MAX_GUILDS_PER_PAGE).await?;
+            let is_last_page = page.len() < MAX_GUILDS_PER_PAGE as usize;
+
+            guilds.extend(page);
+
+            if is_last_page {
+                return Ok(guilds);
+            }
+        }
+    }
+
+    /// Leave the guild, it fails with [`PandaError::NotInGuild`] if the bot isn't a member
+    /// of the guild. Fires a [`GuildDelete`] event.
+    ///
+    /// [`PandaError::NotInGuild`]: ../../panda/enum.PandaError.html#variant.NotInGuild
+    /// [`GuildDelete`]: ../../panda/models/gateway/events/struct.GuildDelete.html
+    pub async fn leave_guild(&self, guild_id: impl IntoId) -> Result<()> {
+        let route = Route::leave_guild(guild_id.as_id());
+
+        let res = self._make_request(route).await?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            return Err(PandaError::NotInGuild);
+        }
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Returns the [`User`] with the given id
+    ///
+    /// [`User`]: ../../panda/models/user/struct.User.html
+    pub async fn get_user(&self, user_id: impl IntoId) -> Result<User> {
+        let route = Route::get_user(user_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns all the [`Role`]s of the guild
+    ///
+    /// [`Role`]: ../../panda/models/guild/struct.Role.html
+    pub async fn get_guild_roles(&self, guild_id: impl IntoId) -> Result<Vec<Role>> {
+        let route = Route::get_guild_roles(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Create a new [`Role`] in the guild. Requires the **MANAGE_ROLES** permission.
+    /// Fires a [`GuildRoleCreate`] event.
+    ///
+    /// [`Role`]: ../../panda/models/guild/struct.Role.html
+    /// [`GuildRoleCreate`]: ../../panda/models/gateway/events/struct.GuildRoleCreate.html
+    pub async fn create_guild_role(&self, guild_id: impl IntoId, builder: RoleEdit) -> Result<Role> {
+        builder.validate()?;
+
+        let body = serde_json::to_string(&builder)?;
+        let route = Route::create_guild_role(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        catch_resource_limit(&mut res)?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Create an emoji in the guild, the image is a data URI of a png, jpeg or gif of max 256 KiB,
+    /// and the roles limit who can use it. Requires the **MANAGE_EMOJIS** permission.
+    /// It fails with [`PandaError::ResourceLimitReached`] when the guild has the max emojis of its tier
+    ///
+    /// [`PandaError::ResourceLimitReached`]: ../../panda/enum.PandaError.html#variant.ResourceLimitReached
+    pub async fn create_guild_emoji(
+        &self,
+        guild_id: impl IntoId,
+        name: impl AsRef<str>,
+        image: impl AsRef<str>,
+        roles: &[&str],
+    ) -> Result<Emoji> {
+        let body = serde_json::json!({
+            "name": name.as_ref(),
+            "image": image.as_ref(),
+            "roles": roles,
+        });
+        let body = serde_json::to_string(&body)?;
+        let route = Route::create_guild_emoji(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        catch_resource_limit(&mut res)?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Modify a [`Role`] of the guild, and returns the updated role. Requires the
+    /// **MANAGE_ROLES** permission. Fires a [`GuildRoleUpdate`] event.
+    ///
+    /// [`Role`]: ../../panda/models/guild/struct.Role.html
+    /// [`GuildRoleUpdate`]: ../../panda/models/gateway/events/struct.GuildRoleUpdate.html
+    pub async fn modify_guild_role(
+        &self,
+        guild_id: impl IntoId,
+        role_id: impl IntoId,
+        builder: RoleEdit,
+    ) -> Result<Role> {
+        builder.validate()?;
+
+        let body = serde_json::to_string(&builder)?;
+        let route = Route::modify_guild_role(guild_id.as_id(), role_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`GuildMember`] of the given user, it fails with [`PandaError::UnknownMember`]
+    /// if the user isn't a member of the guild.
+    ///
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    /// [`PandaError::UnknownMember`]: ../../panda/enum.PandaError.html#variant.UnknownMember
+    pub async fn get_guild_member(&self, guild_id: impl IntoId, user_id: impl IntoId) -> Result<GuildMember> {
+        let route = Route::get_guild_member(guild_id.as_id(), user_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            return Err(PandaError::UnknownMember);
+        }
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Move a member to a voice channel of the guild, the member must be connected to a voice
+    /// channel. Requires the **MOVE_MEMBERS** permission, and **CONNECT** in the new channel.
+    /// Returns the updated [`GuildMember`]
+    ///
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    pub async fn move_member_to_voice(
+        &self,
+        guild_id: impl IntoId,
+        user_id: impl IntoId,
+        channel_id: impl IntoId,
+    ) -> Result<GuildMember> {
+        let body = serde_json::json!({ "channel_id": channel_id.as_id() });
+
+        self._modify_guild_member(guild_id, user_id, body).await
+    }
+
+    /// Disconnect a member from its voice channel. Requires the **MOVE_MEMBERS** permission.
+    /// Returns the updated [`GuildMember`]
+    ///
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    pub async fn disconnect_member_from_voice(&self, guild_id: impl IntoId, user_id: impl IntoId) -> Result<GuildMember> {
+        let body = serde_json::json!({ "channel_id": null });
+
+        self._modify_guild_member(guild_id, user_id, body).await
+    }
+
+    async fn _modify_guild_member(
+        &self,
+        guild_id: impl IntoId,
+        user_id: impl IntoId,
+        body: serde_json::Value,
+    ) -> Result<GuildMember> {
+        let body = serde_json::to_string(&body)?;
+        let route = Route::modify_guild_member(guild_id.as_id(), user_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Adds a [`User`] to the guild, using an OAuth2 access token with the `guilds.join` scope.
+    /// The bot must be a member of the guild with **CREATE_INSTANT_INVITE** permission.
+    /// Returns the new [`GuildMember`], or `None` if the user was already a member.
+    /// Fires a [`GuildMemberAdd`] event.
+    ///
+    /// [`User`]: ../../panda/models/user/struct.User.html
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    /// [`GuildMemberAdd`]: ../../panda/models/gateway/events/struct.GuildMemberAdd.html
+    pub async fn add_guild_member(
+        &self,
+        guild_id: impl IntoId,
+        user_id: impl IntoId,
+        access_token: impl Into<String>,
+        builder: AddGuildMember,
+    ) -> Result<Option<GuildMember>> {
+        let mut body = serde_json::to_value(builder)?;
+        body["access_token"] = access_token.into().into();
+        let body = serde_json::to_string(&body)?;
+
+        let route = Route::add_guild_member(guild_id.as_id(), user_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        // Discord returns 204 No Content when the user is already a member
+        if res.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+
+        Ok(Some(res.json()?))
+    }
+
+    /// Adds a [`Role`] to a [`GuildMember`]. Requires the **MANAGE_ROLES** permission.
+    /// Fires a [`GuildMemberUpdate`] event.
+    ///
+    /// [`Role`]: ../../panda/models/guild/struct.Role.html
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    /// [`GuildMemberUpdate`]: ../../panda/models/gateway/events/struct.GuildMemberUpdate.html
+    pub async fn add_member_role(
+        &self,
+        guild_id: impl IntoId,
+        user_id: impl IntoId,
+        role_id: impl IntoId,
+    ) -> Result<()> {
+        let route = Route::add_guild_member_role(guild_id.as_id(), user_id.as_id(), role_id.as_id());
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Removes a [`Role`] from a [`GuildMember`]. Requires the **MANAGE_ROLES** permission.
+    /// Fires a [`GuildMemberUpdate`] event.
+    ///
+    /// [`Role`]: ../../panda/models/guild/struct.Role.html
+    /// [`GuildMember`]: ../../panda/models/guild/struct.GuildMember.html
+    /// [`GuildMemberUpdate`]: ../../panda/models/gateway/events/struct.GuildMemberUpdate.html
+    pub async fn remove_member_role(
+        &self,
+        guild_id: impl IntoId,
+        user_id: impl IntoId,
+        role_id: impl IntoId,
+    ) -> Result<()> {
+        let route = Route::remove_guild_member_role(guild_id.as_id(), user_id.as_id(), role_id.as_id());
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Send a response to an [`Interaction`], it needs the [`Interaction`] ID and token.
+    /// An interaction can only be responded once.
+    ///
+    /// [`Interaction`]: ../../panda/models/interaction/struct.Interaction.html
+    pub async fn create_interaction_response(
+        &self,
+        interaction_id: impl IntoId,
+        interaction_token: impl AsRef<str>,
+        response: InteractionResponse,
+    ) -> Result<()> {
+        let body = serde_json::to_string(&response)?;
+
+        let route = Route::create_interaction_response(interaction_id.as_id(), interaction_token, body);
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Download the content of an [`Attachment`]. If `max_size` is set, attachments bigger than
+    /// it (in bytes) are rejected before being downloaded.
+    ///
+    /// [`Attachment`]: ../../panda/models/channel/struct.Attachment.html
+    pub async fn download_attachment(&self, attachment: &Attachment, max_size: Option<u64>) -> Result<Vec<u8>> {
+        let mut body = self.download_attachment_stream(attachment, max_size).await?;
+
+        let mut bytes = Vec::with_capacity(attachment.size as usize);
+        body.read_to_end(&mut bytes)
+            .await
+            .map_err(|_| PandaError::HttpNoResponse)?;
+
+        Ok(bytes)
+    }
+
+    /// Same as [`download_attachment`], but returns the body without buffering it,
+    /// it implements `AsyncRead`.
+    ///
+    /// [`download_attachment`]: #method.download_attachment
+    pub async fn download_attachment_stream(&self, attachment: &Attachment, max_size: Option<u64>) -> Result<Body> {
+        // Check the size reported by Discord first, so we don't make the request
+        if let Some(max) = max_size {
+            if attachment.size > max {
+                return Err(PandaError::AttachmentTooLarge);
+            }
+        }
+
+        let res = self.client.get_async(&attachment.url).await?;
+
+        match res.status() {
+            // Signed CDN urls expire after some time
+            StatusCode::FORBIDDEN => return Err(PandaError::AttachmentExpired),
+            status if !status.is_success() => return Err(PandaError::HttpNoResponse),
+            _ => {}
+        };
+
+        // The CDN could return a different size
+        if let (Some(max), Some(len)) = (max_size, res.body().len()) {
+            if len > max {
+                return Err(PandaError::AttachmentTooLarge);
+            }
+        }
+
+        Ok(res.into_body())
+    }
+
+    /// Exchange an OAuth2 authorization code for an [`AccessToken`]
+    ///
+    /// [`AccessToken`]: ../../panda/models/oauth2/struct.AccessToken.html
+    pub async fn exchange_code(
+        &self,
+        client_id: impl AsRef<str>,
+        client_secret: impl AsRef<str>,
+        code: impl AsRef<str>,
+        redirect_uri: impl AsRef<str>,
+    ) -> Result<AccessToken> {
+        let form = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("client_id", client_id.as_ref())
+            .append_pair("client_secret", client_secret.as_ref())
+            .append_pair("grant_type", "authorization_code")
+            .append_pair("code", code.as_ref())
+            .append_pair("redirect_uri", redirect_uri.as_ref())
+            .finish();
+
+        let route = Route::oauth2_token();
+
+        let mut res = self._make_urlencoded_request(route, form).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Get a new [`AccessToken`] using the refresh token of a previous one
+    ///
+    /// [`AccessToken`]: ../../panda/models/oauth2/struct.AccessToken.html
+    pub async fn refresh_token(
+        &self,
+        client_id: impl AsRef<str>,
+        client_secret: impl AsRef<str>,
+        refresh_token: impl AsRef<str>,
+    ) -> Result<AccessToken> {
+        let form = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("client_id", client_id.as_ref())
+            .append_pair("client_secret", client_secret.as_ref())
+            .append_pair("grant_type", "refresh_token")
+            .append_pair("refresh_token", refresh_token.as_ref())
+            .finish();
+
+        let route = Route::oauth2_token();
+
+        let mut res = self._make_urlencoded_request(route, form).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the [`GatewayBot`] information, with the recommended number of shards
+    /// and the session start limits
+    ///
+    /// [`GatewayBot`]: ../../panda/models/gateway/struct.GatewayBot.html
+    pub async fn get_gateway_bot(&self) -> Result<GatewayBot> {
+        let route = Route::get_gateway_bot();
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns a [`Sticker`] by its id
+    ///
+    /// [`Sticker`]: ../../panda/models/sticker/struct.Sticker.html
+    pub async fn get_sticker(&self, sticker_id: impl IntoId) -> Result<Sticker> {
+        let route = Route::get_sticker(sticker_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns a Vec of [`Sticker`] of the guild
+    ///
+    /// [`Sticker`]: ../../panda/models/sticker/struct.Sticker.html
+    pub async fn list_guild_stickers(&self, guild_id: impl IntoId) -> Result<Vec<Sticker>> {
+        let route = Route::list_guild_stickers(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Create a new [`Sticker`] for the guild, and returns it. Requires the
+    /// **MANAGE_EMOJIS_AND_STICKERS** permission.
+    ///
+    /// [`Sticker`]: ../../panda/models/sticker/struct.Sticker.html
+    pub async fn create_guild_sticker(&self, guild_id: impl IntoId, builder: CreateSticker) -> Result<Sticker> {
+        builder.validate()?;
+
+        let mut multipart = Multipart::new();
+        multipart.add_text("name", &builder.name);
+        multipart.add_text("description", &builder.description);
+        multipart.add_text("tags", &builder.tags);
+        multipart.add_file_field("file", &builder.file);
+
+        let route = Route::create_guild_sticker(guild_id.as_id());
+
+        let mut res = self._make_form_request(route, multipart).await?;
+        catch_resource_limit(&mut res)?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns a Vec of [`AutoModerationRule`] of the guild. Requires the **MANAGE_GUILD** permission.
+    ///
+    /// [`AutoModerationRule`]: ../../panda/models/guild/struct.AutoModerationRule.html
+    pub async fn list_auto_moderation_rules(&self, guild_id: impl IntoId) -> Result<Vec<AutoModerationRule>> {
+        let route = Route::list_auto_moderation_rules(guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Create a new [`AutoModerationRule`] for the guild, and returns it. Requires the
+    /// **MANAGE_GUILD** permission. Fires an [`AutoModerationRuleCreate`] event.
+    ///
+    /// [`AutoModerationRule`]: ../../panda/models/guild/struct.AutoModerationRule.html
+    /// [`AutoModerationRuleCreate`]: ../../panda/models/gateway/events/struct.AutoModerationRuleCreate.html
+    pub async fn create_auto_moderation_rule(
+        &self,
+        guild_id: impl IntoId,
+        builder: CreateAutoModerationRule,
+    ) -> Result<AutoModerationRule> {
+        builder.validate()?;
+
+        let body = serde_json::to_string(&builder)?;
+        let route = Route::create_auto_moderation_rule(guild_id.as_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Execute a webhook, if wait is true Discord confirms the message was saved, and the
+    /// created [`Message`] is returned
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn execute_webhook(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        mut builder: ExecuteWebhook,
+        wait: bool,
+    ) -> Result<Option<Message>> {
+        builder.validate()?;
+        self.apply_embed_defaults(builder.embeds_mut());
+        if self.strict_validation() {
+            builder.validate_strict()?;
+        }
+        let body = serde_json::to_string(&builder)?;
+
+        let route = Route::execute_webhook(webhook_id.as_id(), token, wait, builder.get_thread_id(), body);
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        // Without wait Discord returns 204 No Content
+        if !wait {
+            return Ok(None);
+        }
+
+        Ok(Some(res.json()?))
+    }
+
+    /// Edit a [`Message`] created by the webhook, it doesn't require the bot to have
+    /// access to the channel
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn edit_webhook_message(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        message_id: impl IntoId,
+        builder: MessageEdit,
+    ) -> Result<Message> {
+        self._edit_webhook_message(webhook_id, token, message_id, None, builder).await
+    }
+
+    /// Same as [`edit_webhook_message`], for a message sent to a thread
+    ///
+    /// [`edit_webhook_message`]: #method.edit_webhook_message
+    pub async fn edit_webhook_message_in_thread(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        thread_id: impl IntoId,
+        message_id: impl IntoId,
+        builder: MessageEdit,
+    ) -> Result<Message> {
+        self._edit_webhook_message(webhook_id, token, message_id, Some(thread_id.as_id()), builder)
+            .await
+    }
+
+    async fn _edit_webhook_message(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        message_id: impl IntoId,
+        thread_id: Option<&str>,
+        mut builder: MessageEdit,
+    ) -> Result<Message> {
+        self.apply_embed_defaults(builder.embeds_mut());
+        if self.strict_validation() {
+            builder.validate_strict()?;
+        }
+        let (payload, files) = builder.into_payload()?;
+
+        let mut res = if files.is_empty() {
+            let body = serde_json::to_string(&payload)?;
+            let route = Route::edit_webhook_message(webhook_id.as_id(), token, message_id.as_id(), thread_id, body);
+
+            self._make_request(route).await?
+        } else {
+            let route = Route::edit_webhook_message(webhook_id.as_id(), token, message_id.as_id(), thread_id, ());
+
+            self._make_multipart_request(route, &payload, &files).await?
+        };
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Delete a [`Message`] created by the webhook
+    ///
+    /// [`Message`]: ../../panda/models/channel/struct.Message.html
+    pub async fn delete_webhook_message(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        message_id: impl IntoId,
+    ) -> Result<()> {
+        let route = Route::delete_webhook_message(webhook_id.as_id(), token, message_id.as_id(), None);
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Same as [`delete_webhook_message`], for a message sent to a thread
+    ///
+    /// [`delete_webhook_message`]: #method.delete_webhook_message
+    pub async fn delete_webhook_message_in_thread(
+        &self,
+        webhook_id: impl IntoId,
+        token: impl AsRef<str>,
+        thread_id: impl IntoId,
+        message_id: impl IntoId,
+    ) -> Result<()> {
+        let route = Route::delete_webhook_message(webhook_id.as_id(), token, message_id.as_id(), Some(thread_id.as_id()));
+
+        let res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(())
+    }
+
+    /// Returns the permissions of all the commands of the application in the guild, the commands
+    /// without permissions aren't included
+    pub async fn get_guild_application_command_permissions(
+        &self,
+        application_id: impl IntoId,
+        guild_id: impl IntoId,
+    ) -> Result<Vec<GuildCommandPermissions>> {
+        let route = Route::get_guild_application_command_permissions(application_id.as_id(), guild_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Returns the permissions of a command of the application in the guild
+    pub async fn get_application_command_permissions(
+        &self,
+        application_id: impl IntoId,
+        guild_id: impl IntoId,
+        command_id: impl IntoId,
+    ) -> Result<GuildCommandPermissions> {
+        let route =
+            Route::get_application_command_permissions(application_id.as_id(), guild_id.as_id(), command_id.as_id());
+
+        let mut res = self._make_request(route).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    /// Replace the permissions of a command of the application in the guild, up to 100 permissions.
+    /// Discord doesn't accept bot tokens for this route, the `access_token` is the OAuth2 access
+    /// token of a guild admin with the `applications.commands.permissions.update` scope, it's sent
+    /// as a Bearer token instead of the bot token
+    pub async fn edit_application_command_permissions(
+        &self,
+        access_token: impl AsRef<str>,
+        application_id: impl IntoId,
+        guild_id: impl IntoId,
+        command_id: impl IntoId,
+        permissions: &[CommandPermission],
+    ) -> Result<GuildCommandPermissions> {
+        if permissions.len() > MAX_COMMAND_PERMISSIONS {
+            return Err(PandaError::Validation {
+                field: "permissions",
+                reason: format!("it can have up to {} permissions", MAX_COMMAND_PERMISSIONS),
+            });
+        }
+
+        let body = serde_json::json!({ "permissions": permissions });
+        let body = serde_json::to_string(&body)?;
+        let route = Route::edit_application_command_permissions(
+            application_id.as_id(),
+            guild_id.as_id(),
+            command_id.as_id(),
+            body,
+        );
+
+        let authorization = format!("Bearer {}", access_token.as_ref());
+        let options = RequestOptions {
+            authorization: Some(&authorization),
+            ..Default::default()
+        };
+
+        let mut res = self._send_request(route, options).await?;
+        self._catch_http_errors(&res)?;
+
+        Ok(res.json()?)
+    }
+
+    // PUT/channels/{channel.id}/recipients/{user.id}
+
+    // DELETE/channels/{channel.id}/recipients/{user.id}
+}
+
+/// Discord epoch (2015-01-01) in milliseconds
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// Messages older than 2 weeks can't be bulk deleted
+const BULK_DELETE_MAX_AGE: u64 = 14 * 24 * 60 * 60 * 1000;
+
+/// Returns the creation time of a snowflake id, in milliseconds since unix epoch
+fn snowflake_timestamp(id: &str) -> Option<u64> {
+    id.parse::<u64>().ok().map(|id| (id >> 22) + DISCORD_EPOCH)
+}
+
+/// Current time in milliseconds since unix epoch
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Split the ids in (bulk deletable, too old for bulk delete). Invalid ids are considered old,
+/// so they are deleted one by one
+fn split_by_bulk_delete_age<'a>(ids: &[&'a str], now: u64) -> (Vec<&'a str>, Vec<&'a str>) {
+    ids.iter().partition(|id| match snowflake_timestamp(id) {
+        Some(created) => now.saturating_sub(created) < BULK_DELETE_MAX_AGE,
+        None => false,
+    })
+}
+
+/// The version must be `v` followed by a number, like `v6`
+fn validate_api_version(version: &str) -> Result<()> {
+    let number = version.strip_prefix('v').unwrap_or("");
+
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(PandaError::Validation {
+            field: "api_version",
+            reason: format!("`{}` isn't a valid version, it must be like `v6`", version),
+        });
+    }
+
+    Ok(())
+}
+
+/// The audit log reason header must be url encoded, to support non ascii characters
+fn encode_audit_log_reason(reason: &str) -> String {
+    let mut encoded = String::with_capacity(reason.len());
+
+    for byte in reason.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// Calls `fetch` for all the channels at the same time, and returns the messages of each channel
+async fn prefetch_with<I, S, F, Fut>(channel_ids: I, fetch: F) -> HashMap<String, Result<Vec<Message>>>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<Vec<Message>>>,
+{
+    let requests = channel_ids.into_iter().map(|id| {
+        let id = id.into();
+        let messages = fetch(id.clone());
+
+        async move { (id, messages.await) }
+    });
+
+    join_all(requests).await.into_iter().collect()
+}
+
+/// Reads the JSON error code of a failed request, Discord sends it as `{"code": 30003, "message": "..."}`
+fn discord_error_code(res: &mut Response<Body>) -> Option<u64> {
+    let body: serde_json::Value = res.json().ok()?;
+
+    body.get("code").and_then(|code| code.as_u64())
+}
+
+/// Returns [`PandaError::ResourceLimitReached`] if the request failed because the guild has the
+/// max number of emojis, stickers or roles
+fn catch_resource_limit(res: &mut Response<Body>) -> Result<()> {
+    if res.status() != StatusCode::BAD_REQUEST {
+        return Ok(());
+    }
+
+    match discord_error_code(res).and_then(resource_limit_kind) {
+        Some(kind) => Err(PandaError::ResourceLimitReached { kind }),
+        None => Ok(()),
+    }
+}
+
+fn resource_limit_kind(code: u64) -> Option<ResourceKind> {
+    match code {
+        MAX_ROLES_ERROR_CODE => Some(ResourceKind::Roles),
+        MAX_EMOJIS_ERROR_CODE => Some(ResourceKind::Emojis),
+        MAX_STICKERS_ERROR_CODE => Some(ResourceKind::Stickers),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a snowflake id created at the given unix timestamp, in milliseconds
+    fn snowflake_at(timestamp: u64) -> String {
+        ((timestamp - DISCORD_EPOCH) << 22).to_string()
+    }
+
+    #[test]
+    fn snowflake_creation_time() {
+        assert_eq!(snowflake_timestamp("175928847299117063"), Some(1_462_015_105_796));
+        assert_eq!(snowflake_timestamp("not an id"), None);
+    }
+
+    #[test]
+    fn split_messages_by_age() {
+        let now = 1_600_000_000_000;
+        let recent = snowflake_at(now - 1000);
+        let limit = snowflake_at(now - BULK_DELETE_MAX_AGE);
+        let old = snowflake_at(now - BULK_DELETE_MAX_AGE - 1000);
+        let ids = [recent.as_str(), limit.as_str(), old.as_str(), "invalid"];
+
+        let (bulk, single) = split_by_bulk_delete_age(&ids, now);
+        assert_eq!(bulk, vec![recent.as_str()]);
+        assert_eq!(single, vec![limit.as_str(), old.as_str(), "invalid"]);
+    }
+
+    #[test]
+    fn encode_audit_log_reasons() {
+        assert_eq!(encode_audit_log_reason("spam"), "spam");
+        assert_eq!(encode_audit_log_reason("too much spam!"), "too%20much%20spam%21");
+        assert_eq!(encode_audit_log_reason("ñ"), "%C3%B1");
+    }
+
+    #[test]
+    fn valid_api_versions() {
+        assert!(validate_api_version("v6").is_ok());
+        assert!(validate_api_version("v10").is_ok());
+
+        for version in ["", "v", "6", "v6.1", "version6"].iter() {
+            assert!(matches!(
+                validate_api_version(version),
+                Err(PandaError::Validation { field: "api_version", .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn resource_limit_error_codes() {
+        assert_eq!(resource_limit_kind(30005), Some(ResourceKind::Roles));
+        assert_eq!(resource_limit_kind(30008), Some(ResourceKind::Emojis));
+        assert_eq!(resource_limit_kind(30039), Some(ResourceKind::Stickers));
+        assert_eq!(resource_limit_kind(MAX_PINS_ERROR_CODE), None);
+    }
+
+    #[test]
+    fn repeated_channel_positions() {
+        let http = HttpClient::new("token");
+        let positions = vec![ChannelPosition::new("1", 0), ChannelPosition::new("2", 1), ChannelPosition::new("1", 2)];
+
+        assert!(matches!(
+            futures::executor::block_on(http.modify_guild_channel_positions("3", positions)),
+            Err(PandaError::Validation { field: "positions", .. })
+        ));
+    }
+
+    #[test]
+    fn too_large_attachment_is_not_downloaded() {
+        let attachment: Attachment = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "filename": "panda.png",
+            "size": 2048,
+            "url": "https://cdn.discordapp.com/attachments/1/2/panda.png",
+            "proxy_url": "https://media.discordapp.net/attachments/1/2/panda.png"
+        }))
+        .unwrap();
+        let http = HttpClient::new("token");
+
+        assert!(matches!(
+            futures::executor::block_on(http.download_attachment(&attachment, Some(1024))),
+            Err(PandaError::AttachmentTooLarge)
+        ));
+    }
+
+    #[test]
+    fn discord_error_codes() {
+        let mut res = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(r#"{"code": 30003, "message": "Maximum number of pins reached (50)"}"#))
+            .unwrap();
+        assert_eq!(discord_error_code(&mut res), Some(MAX_PINS_ERROR_CODE));
+
+        let mut res = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Bad Request"))
+            .unwrap();
+        assert_eq!(discord_error_code(&mut res), None);
+    }
+
+    #[test]
+    fn prefetch_no_channels() {
+        let http = HttpClient::new("token");
+        let messages = futures::executor::block_on(http.prefetch_messages(Vec::<String>::new(), 50));
+
+        assert!(messages.is_empty());
+    }
+
+    /// A message returned by Discord, with the given id
+    fn message_response(id: &str) -> Response<Body> {
+        let message = serde_json::json!({
+            "id": id,
+            "channel_id": "290926798999357250",
+            "author": {
+                "id": "53908099506183680",
+                "username": "Mason",
+                "discriminator": "9999",
+                "avatar": null
+            },
+            "content": "Supa Hot",
+            "timestamp": "2017-07-11T17:27:07.299000+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "pinned": false,
+            "type": 0
+        });
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from(message.to_string()))
+            .unwrap()
+    }
+
+    fn sent_message() -> Result<Message> {
+        Ok(message_response("2").json()?)
+    }
+
+    #[test]
+    fn edit_or_send_edits_the_existing_message() {
+        let http = HttpClient::new("token");
+        let edit = Some(async { Ok(message_response("1")) });
+        let send = async { panic!("the message was edited") };
+
+        let message = futures::executor::block_on(http._edit_or_send(edit, send)).unwrap();
+        assert_eq!(message.id, "1");
+    }
+
+    #[test]
+    fn edit_or_send_sends_a_new_message_if_unknown() {
+        let http = HttpClient::new("token");
+        let unknown = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from(r#"{"code": 10008, "message": "Unknown Message"}"#))
+            .unwrap();
+
+        let message = futures::executor::block_on(http._edit_or_send(Some(async { Ok(unknown) }), async {
+            sent_message()
+        }))
+        .unwrap();
+        assert_eq!(message.id, "2");
+
+        // Other not found errors, like an unknown channel, aren't a deleted message
+        let unknown_channel = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from(r#"{"code": 10003, "message": "Unknown Channel"}"#))
+            .unwrap();
+        let send = async { panic!("the channel doesn't exist") };
+
+        assert!(matches!(
+            futures::executor::block_on(http._edit_or_send(Some(async { Ok(unknown_channel) }), send)),
+            Err(PandaError::HttpInvalidParameters)
+        ));
+    }
+
+    #[test]
+    fn edit_or_send_sends_a_new_message_without_existing() {
+        let http = HttpClient::new("token");
+        let edit: Option<futures::future::Ready<Result<Response<Body>>>> = None;
+
+        let message = futures::executor::block_on(http._edit_or_send(edit, async { sent_message() })).unwrap();
+        assert_eq!(message.id, "2");
+    }
+
+    #[test]
+    fn http_errors_of_the_status() {
+        let http = HttpClient::new("token");
+        let response = |status| Response::builder().status(status).body(Body::empty()).unwrap();
+
+        assert!(http._catch_http_errors(&response(StatusCode::OK)).is_ok());
+        assert!(matches!(
+            http._catch_http_errors(&response(StatusCode::FORBIDDEN)),
+            Err(PandaError::HttpForbidden)
+        ));
+        assert!(matches!(
+            http._catch_http_errors(&response(StatusCode::NOT_FOUND)),
+            Err(PandaError::HttpInvalidParameters)
+        ));
+    }
+
+    #[test]
+    fn followed_channel_response() {
+        let mut res = Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from(r#"{"channel_id": "41771983423143937", "webhook_id": "41771983423143938"}"#))
+            .unwrap();
+        let followed: FollowedChannel = res.json().unwrap();
+
+        assert_eq!(followed.channel_id, "41771983423143937");
+        assert_eq!(followed.webhook_id, "41771983423143938");
+    }
+
+    #[test]
+    fn prefetch_keeps_the_result_of_each_channel() {
+        let fetch = |id: String| async move {
+            match id.as_str() {
+                "1" => Ok(vec![sent_message()?]),
+                _ => Err(PandaError::HttpForbidden),
+            }
+        };
+        let messages = futures::executor::block_on(prefetch_with(vec!["1", "2"], fetch));
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages["1"].as_ref().unwrap()[0].id, "2");
+        assert!(matches!(messages["2"], Err(PandaError::HttpForbidden)));
+    }
 }
@@ -0,0 +1,73 @@
+//! Minimal multipart/form-data encoder, used to upload files
+
+use crate::{
+    error::Result,
+    utils::{builders::AttachmentFile, random::random_u64},
+};
+
+use serde::Serialize;
+
+pub(crate) struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    pub(crate) fn new() -> Self {
+        // The boundary can't appear in the content, so it's randomized
+        Multipart {
+            boundary: format!("panda-boundary-{:016x}", random_u64()),
+            body: Vec::new(),
+        }
+    }
+
+    /// Add the json payload, as the "payload_json" field
+    pub(crate) fn add_json(&mut self, payload: &impl Serialize) -> Result<()> {
+        let json = serde_json::to_vec(payload)?;
+
+        self.add_part(
+            "Content-Disposition: form-data; name=\"payload_json\"\r\nContent-Type: application/json",
+            &json,
+        );
+
+        Ok(())
+    }
+
+    /// Add a text field
+    pub(crate) fn add_text(&mut self, name: &str, value: &str) {
+        let headers = format!("Content-Disposition: form-data; name=\"{}\"", name);
+
+        self.add_part(&headers, value.as_bytes());
+    }
+
+    /// Add a file, as the "files[index]" field
+    pub(crate) fn add_file(&mut self, index: usize, file: &AttachmentFile) {
+        self.add_file_field(&format!("files[{}]", index), file);
+    }
+
+    /// Add a file with a custom field name
+    pub(crate) fn add_file_field(&mut self, name: &str, file: &AttachmentFile) {
+        // Quotes would break the header
+        let filename = file.filename.replace('"', "");
+        let headers = format!(
+            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: application/octet-stream",
+            name, filename
+        );
+
+        self.add_part(&headers, &file.data);
+    }
+
+    fn add_part(&mut self, headers: &str, content: &[u8]) {
+        self.body.extend_from_slice(format!("--{}\r\n{}\r\n\r\n", self.boundary, headers).as_bytes());
+        self.body.extend_from_slice(content);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    /// Returns the content type header value and the body
+    pub(crate) fn finish(mut self) -> (String, Vec<u8>) {
+        self.body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        let content_type = format!("multipart/form-data; boundary={}", self.boundary);
+
+        (content_type, self.body)
+    }
+}
@@ -1,14 +1,22 @@
-use crate::runtime;
+use crate::{
+    error::{PandaError, Result},
+    runtime,
+};
 use std::{
     collections::HashMap,
     default::Default,
     sync::Arc,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use futures::lock::Mutex;
 
-use isahc::{http::Response, Body};
+use isahc::{
+    http::{HeaderMap, Response, StatusCode},
+    prelude::*,
+    Body,
+};
+use serde::Deserialize;
 
 #[derive(Default)]
 pub(crate) struct RateLimit {
@@ -17,6 +25,17 @@ pub(crate) struct RateLimit {
     // - "channels:639562328521703445"
     // - "channels/messages:639562328521703445"
     buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+    // When the global rate limit ends, all the requests wait until then
+    global_reset: Mutex<Option<Instant>>,
+}
+
+/// Body of a 429 response
+#[derive(Deserialize)]
+struct RateLimitedBody {
+    /// Seconds to wait, with milliseconds
+    retry_after: f64,
+    #[serde(default)]
+    global: bool,
 }
 
 #[derive(Default)]
@@ -27,11 +46,30 @@ struct Bucket {
 }
 
 impl RateLimit {
-    pub(crate) async fn check_and_sleep(&self, bucket_key: &str) {
+    /// Returns the time it waited, if the limit was reached
+    pub(crate) async fn check_and_sleep(&self, bucket_key: &str) -> Option<Duration> {
+        let global_wait = self.wait_global().await;
+
+        self.check_bucket(bucket_key).await.or(global_wait)
+    }
+
+    /// Sleep until the global rate limit ends, if it was reached
+    async fn wait_global(&self) -> Option<Duration> {
+        let reset = *self.global_reset.lock().await;
+        let wait = reset?.checked_duration_since(Instant::now())?;
+
+        runtime::sleep(wait).await;
+
+        Some(wait)
+    }
+
+    async fn check_bucket(&self, bucket_key: &str) -> Option<Duration> {
         // Get bucket from key
         let mut buckets_hm = self.buckets.lock().await;
         let bucket = buckets_hm.get_mut(bucket_key);
 
+        let mut waited = None;
+
         // If it exists, check
         // If not exists, we assume that it's safe to make the api call
         if let Some(b) = bucket {
@@ -46,17 +84,50 @@ impl RateLimit {
                 b.remaining += 1;
             } else if b.remaining == 0 {
                 runtime::sleep(difference).await;
+                waited = Some(difference);
 
                 // Add the default remaining
                 b.remaining = b.limit;
             }
             // If passes all then it's safe to make the api call
 
-            b.remaining -= 1;
+            b.remaining = b.remaining.saturating_sub(1);
         }
+
+        waited
     }
 
-    pub(crate) async fn update(&self, bucket_key: String, response: &Response<Body>) {
+    /// Update the bucket with the response headers. A 429 returns [`PandaError::HttpRateLimited`],
+    /// a global one blocks all the requests until `retry_after`, and a route one only the bucket
+    ///
+    /// [`PandaError::HttpRateLimited`]: ../../enum.PandaError.html#variant.HttpRateLimited
+    pub(crate) async fn update(&self, bucket_key: String, response: &mut Response<Body>) -> Result<()> {
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let body = response.json::<RateLimitedBody>().ok();
+            let (retry_after, global) = rate_limited_by(body, response.headers());
+            let reset = Instant::now() + retry_after;
+
+            if global {
+                log::warn!("Global rate limit reached, all requests wait {:?}", retry_after);
+                *self.global_reset.lock().await = Some(reset);
+            } else {
+                log::warn!("Rate limit of {} reached, retry after {:?}", bucket_key, retry_after);
+                let mut buckets_hm = self.buckets.lock().await;
+                let bucket = buckets_hm.entry(bucket_key).or_default();
+                let current = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+                // Keep the limit, so the bucket refills after the reset
+                if let Some(limit) = headers_limit(response.headers()) {
+                    bucket.limit = limit;
+                }
+
+                bucket.remaining = 0;
+                bucket.reset = (current + retry_after).as_secs_f64().ceil() as u64;
+            }
+
+            return Err(PandaError::HttpRateLimited { retry_after, global });
+        }
+
         let headers = response.headers();
         let mut buckets_hm = self.buckets.lock().await;
 
@@ -72,7 +143,156 @@ impl RateLimit {
         }
 
         if let Some(reset) = headers.get("x-ratelimit-reset") {
-            bucket.reset = reset.to_str().unwrap().parse::<u64>().unwrap();
+            // Discord sends the reset with milliseconds, like 1470173023.123
+            bucket.reset = reset.to_str().unwrap().parse::<f64>().unwrap().ceil() as u64;
         }
+
+        Ok(())
+    }
+}
+
+/// Returns the `X-RateLimit-Limit` header, if it's present and valid
+fn headers_limit(headers: &HeaderMap) -> Option<u32> {
+    headers
+        .get("x-ratelimit-limit")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u32>().ok())
+}
+
+/// Returns how long to wait after a 429 and if the limit is global, from the body or, if it
+/// couldn't be parsed, from the `Retry-After` and `X-RateLimit-Global` headers
+fn rate_limited_by(body: Option<RateLimitedBody>, headers: &HeaderMap) -> (Duration, bool) {
+    let (retry_after, global) = match body {
+        Some(body) => (body.retry_after, body.global),
+        None => {
+            let retry_after = headers
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(1.0);
+
+            (retry_after, headers.contains_key("x-ratelimit-global"))
+        }
+    };
+
+    (Duration::from_secs_f64(retry_after.max(0.0)), global)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use isahc::http::HeaderValue;
+
+    fn rate_limited_response(body: &'static str) -> Response<Body> {
+        Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[test]
+    fn rate_limit_from_body() {
+        let body = RateLimitedBody {
+            retry_after: 1.5,
+            global: true,
+        };
+        let (retry_after, global) = rate_limited_by(Some(body), &HeaderMap::new());
+
+        assert_eq!(retry_after, Duration::from_millis(1500));
+        assert!(global);
+    }
+
+    #[test]
+    fn rate_limit_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(rate_limited_by(None, &headers), (Duration::from_secs(3), false));
+
+        headers.insert("x-ratelimit-global", HeaderValue::from_static("true"));
+        assert_eq!(rate_limited_by(None, &headers), (Duration::from_secs(3), true));
+
+        // Without headers, it waits 1 second
+        assert_eq!(rate_limited_by(None, &HeaderMap::new()), (Duration::from_secs(1), false));
+    }
+
+    #[test]
+    fn route_rate_limit_blocks_the_bucket() {
+        let rate_limit = RateLimit::default();
+        let mut response = rate_limited_response(r#"{"message": "You are being rate limited.", "retry_after": 2.5, "global": false}"#);
+
+        let result = block_on(rate_limit.update("channels:1".into(), &mut response));
+        assert!(matches!(
+            result,
+            Err(PandaError::HttpRateLimited { retry_after, global: false }) if retry_after == Duration::from_millis(2500)
+        ));
+
+        let buckets = block_on(rate_limit.buckets.lock());
+        assert_eq!(buckets["channels:1"].remaining, 0);
+        assert!(block_on(rate_limit.global_reset.lock()).is_none());
+    }
+
+    #[test]
+    fn global_rate_limit_blocks_all_requests() {
+        let rate_limit = RateLimit::default();
+        let mut response = rate_limited_response(r#"{"message": "You are being rate limited.", "retry_after": 0.5, "global": true}"#);
+
+        let result = block_on(rate_limit.update("channels:1".into(), &mut response));
+        assert!(matches!(result, Err(PandaError::HttpRateLimited { global: true, .. })));
+
+        assert!(block_on(rate_limit.global_reset.lock()).is_some());
+        assert!(block_on(rate_limit.buckets.lock()).is_empty());
+    }
+
+    #[test]
+    fn headers_update_the_bucket() {
+        let rate_limit = RateLimit::default();
+        let mut response = Response::builder()
+            .status(StatusCode::OK)
+            .header("x-ratelimit-limit", "5")
+            .header("x-ratelimit-remaining", "4")
+            .header("x-ratelimit-reset", "1470173023.123")
+            .body(Body::empty())
+            .unwrap();
+
+        assert!(block_on(rate_limit.update("channels:1".into(), &mut response)).is_ok());
+
+        let buckets = block_on(rate_limit.buckets.lock());
+        let bucket = &buckets["channels:1"];
+        assert_eq!((bucket.limit, bucket.remaining, bucket.reset), (5, 4, 1470173024));
+    }
+
+    #[test]
+    fn route_rate_limit_keeps_the_limit() {
+        let rate_limit = RateLimit::default();
+        let mut response = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header("x-ratelimit-limit", "5")
+            .body(Body::from(r#"{"message": "You are being rate limited.", "retry_after": 0.1, "global": false}"#))
+            .unwrap();
+
+        assert!(block_on(rate_limit.update("channels:1".into(), &mut response)).is_err());
+        assert_eq!(block_on(rate_limit.buckets.lock())["channels:1"].limit, 5);
+    }
+
+    #[test]
+    fn route_rate_limit_on_a_fresh_bucket_does_not_underflow() {
+        let mut rt = tokio::runtime::Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+        let rate_limit = RateLimit::default();
+        let mut response = rate_limited_response(r#"{"message": "You are being rate limited.", "retry_after": 0.1, "global": false}"#);
+
+        let result = rt.block_on(rate_limit.update("channels:1".into(), &mut response));
+        assert!(matches!(result, Err(PandaError::HttpRateLimited { global: false, .. })));
+
+        // The 429 didn't send a limit, so the bucket refills to 0 after the wait
+        assert!(rt.block_on(rate_limit.check_and_sleep("channels:1")).is_some());
+
+        let buckets = rt.block_on(rate_limit.buckets.lock());
+        assert_eq!(buckets["channels:1"].remaining, 0);
     }
 }
@@ -1,6 +1,7 @@
 use isahc::{http::Method, Body};
 
-// const DISCORD_URL: &'static str = "https://discord.com/api/v6";
+/// Base url of the API, the version is added by the HttpClient
+pub(crate) const DISCORD_API_URL: &str = "https://discord.com/api";
 
 macro_rules! bucket_key {
     (channel: $id: expr) => {
@@ -12,11 +13,20 @@ macro_rules! bucket_key {
     (emoji: $id: expr) => {
         format!("emoji:{}", $id.as_ref());
     };
+    (interaction: $id: expr) => {
+        format!("interaction:{}", $id.as_ref());
+    };
+    (sticker: $id: expr) => {
+        format!("sticker:{}", $id.as_ref());
+    };
+    (webhook: $id: expr) => {
+        format!("webhook:{}", $id.as_ref());
+    };
 }
 
 macro_rules! api_request {
     ($url: expr, $($rest: expr),*) => {
-        format!(concat!("https://discord.com/api/v6", $url), $($rest),*)
+        format!($url, $($rest),*)
     };
 }
 pub(crate) struct Route<B> {
@@ -56,6 +66,20 @@ impl Route<()> {
         }
     }
 
+    // GET/channels/{channel.id}/messages
+    pub(crate) fn get_latest_channel_messages(channel_id: impl AsRef<str>, limit: u8) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/channels/{}/messages?limit={}", channel_id.as_ref(), limit);
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
     // GET/channels/{channel.id}/messages
     pub(crate) fn get_channel_messages(
         kind: &str,
@@ -250,7 +274,7 @@ impl Route<()> {
     }
 
     // GET/channels/{channel.id}/invites
-    pub(crate) fn get_channel_invite(channel_id: impl AsRef<str>) -> Self {
+    pub(crate) fn get_channel_invites(channel_id: impl AsRef<str>) -> Self {
         let method = Method::GET;
         let uri = api_request!("/channels/{}/invites", channel_id.as_ref());
         let bucket_key = bucket_key!(channel: channel_id);
@@ -263,12 +287,25 @@ impl Route<()> {
         }
     }
 
-    // POST/channels/{channel.id}/typing
-    pub(crate) fn trigger_typing_indicator(channel_id: impl AsRef<str>) -> Route<()> {
-        let method = Method::POST;
-        let uri = api_request!("/channels/{}/typing", channel_id.as_ref());
+    // GET/guilds/{guild.id}
+    pub(crate) fn get_guild(guild_id: impl AsRef<str>, with_counts: bool) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}?with_counts={}", guild_id.as_ref(), with_counts);
+        let bucket_key = bucket_key!(guild: guild_id);
 
-        let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/preview
+    pub(crate) fn get_guild_preview(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/preview", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
         Route {
             method,
@@ -278,12 +315,25 @@ impl Route<()> {
         }
     }
 
-    // GET/channels/{channel.id}/pins
-    pub(crate) fn get_pinned_messages(channel_id: impl AsRef<str>) -> Route<()> {
+    // GET/guilds/{guild.id}/widget
+    pub(crate) fn get_guild_widget_settings(guild_id: impl AsRef<str>) -> Self {
         let method = Method::GET;
-        let uri = api_request!("/channels/{}/pins", channel_id.as_ref());
+        let uri = api_request!("/guilds/{}/widget", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
-        let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/widget.json
+    pub(crate) fn get_guild_widget(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/widget.json", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
         Route {
             method,
@@ -293,12 +343,25 @@ impl Route<()> {
         }
     }
 
-    // PUT/channels/{channel.id}/pins/{message.id}
-    pub(crate) fn add_pinned_channel_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>) -> Route<()> {
-        let method = Method::PUT;
-        let uri = api_request!("/channels/{}/pins/{}", channel_id.as_ref(), msg_id.as_ref());
+    // GET/guilds/{guild.id}/vanity-url
+    pub(crate) fn get_guild_vanity_url(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/vanity-url", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
-        let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/integrations
+    pub(crate) fn get_guild_integrations(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/integrations", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
         Route {
             method,
@@ -308,12 +371,25 @@ impl Route<()> {
         }
     }
 
-    // DELETE/channels/{channel.id}/pins/{message.id}
-    pub(crate) fn delete_pinned_channel_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>) -> Route<()> {
+    // DELETE/guilds/{guild.id}/integrations/{integration.id}
+    pub(crate) fn delete_guild_integration(guild_id: impl AsRef<str>, integration_id: impl AsRef<str>) -> Self {
         let method = Method::DELETE;
-        let uri = api_request!("/channels/{}/pins/{}", channel_id.as_ref(), msg_id.as_ref());
+        let uri = api_request!("/guilds/{}/integrations/{}", guild_id.as_ref(), integration_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
-        let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/threads/active
+    pub(crate) fn list_active_threads(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/threads/active", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
         Route {
             method,
@@ -322,143 +398,967 @@ impl Route<()> {
             body: (),
         }
     }
-}
 
-// Routes with body
-impl<B: Into<Body>> Route<B> {
-    // pub(crate) fn as_request(self, token: &str) -> (String, Request<Body>) {
-    //     // let request = match self.method {
-    //     //     Method::GET | Method::PUT | Method::DELETE => Request::builder()
-    //     //         .method(&self.method)
-    //     //         .uri(&self.uri)
-    //     //         .header("Authorization", token)
-    //     //         .body(())
-    //     //         .unwrap(),
-    //     //     Method::POST | Method::PATCH => Request::builder()
-    //     //         .method(&self.method)
-    //     //         .uri(&self.uri)
-    //     //         .header("Authorization", token)
-    //     //         .header("Content-Type", "application/json")
-    //     //         .body(self.body.into())
-    //     //         .unwrap(),
-    //     //     _ => unimplemented!(),
-    //     // };
+    // GET/channels/{channel.id}/thread-members
+    pub(crate) fn list_thread_members(channel_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/channels/{}/thread-members", channel_id.as_ref());
+        let bucket_key = bucket_key!(channel: channel_id);
 
-    //     let request = match self.body {
-    //         () => Request::builder()
-    //             .method(&self.method)
-    //             .uri(&self.uri)
-    //             .header("Authorization", token)
-    //             .body(())
-    //             .unwrap(),
-    //         _ => Request::builder()
-    //             .method(&self.method)
-    //             .uri(&self.uri)
-    //             .header("Authorization", token)
-    //             .header("Content-Type", "application/json")
-    //             .body(self.body)
-    //             .unwrap(),
-    //     };
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
-    //     println!("{:?}", request);
+    // DELETE/users/@me/guilds/{guild.id}
+    pub(crate) fn leave_guild(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::DELETE;
+        let uri = api_request!("/users/@me/guilds/{}", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
-    //     (self.bucket_key, request)
-    // }
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
-    // PATCH/channels/{channel.id}
-    pub(crate) fn edit_channel(channel_id: impl AsRef<str>, body: B) -> Self {
-        let method = Method::PATCH;
-        let uri = api_request!("/channels/{}", channel_id.as_ref());
-        let bucket_key = bucket_key!(channel: channel_id);
+    // GET/users/{user.id}
+    pub(crate) fn get_user(user_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/users/{}", user_id.as_ref());
+        let bucket_key = "users".into();
 
-        Self {
+        Route {
             method,
             uri,
             bucket_key,
-            body,
+            body: (),
         }
     }
 
-    // POST/channels/{channel.id}/messages
-    pub(crate) fn create_message(channel_id: impl AsRef<str>, body: B) -> Self {
-        let method = Method::POST;
-        let uri = api_request!("/channels/{}/messages", channel_id.as_ref());
-        let bucket_key = bucket_key!(channel: channel_id);
+    // GET/users/@me/guilds
+    pub(crate) fn get_current_user_guilds(after: Option<&str>, limit: u8) -> Self {
+        let method = Method::GET;
+        let mut uri = api_request!("/users/@me/guilds?limit={}", limit);
+        if let Some(after) = after {
+            uri.push_str(&format!("&after={}", after));
+        }
+        let bucket_key = "users/@me/guilds".into();
 
-        Self {
+        Route {
             method,
             uri,
             bucket_key,
-            body,
+            body: (),
         }
     }
 
-    // PATCH/channels/{channel.id}/messages/{message.id}
-    pub(crate) fn edit_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>, body: B) -> Self {
-        let method = Method::PATCH;
-        let uri = api_request!("/channels/{}/messages/{}", channel_id.as_ref(), msg_id.as_ref());
+    // GET/guilds/{guild.id}/roles
+    pub(crate) fn get_guild_roles(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/roles", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
 
-        let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
-        Self {
+    // GET/guilds/{guild.id}/onboarding
+    pub(crate) fn get_guild_onboarding(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/onboarding", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
             method,
             uri,
             bucket_key,
-            body,
+            body: (),
         }
     }
 
-    // POST/channels/{channel.id}/messages/bulk-delete
-    pub(crate) fn bulk_delete_messages(channel_id: impl AsRef<str>, body: B) -> Self {
+    // POST/channels/{channel.id}/polls/{message.id}/expire
+    pub(crate) fn end_poll(channel_id: impl AsRef<str>, message_id: impl AsRef<str>) -> Self {
         let method = Method::POST;
-        let uri = api_request!("/channels/{}/messages/bulk-delete", channel_id.as_ref());
+        let uri = api_request!("/channels/{}/polls/{}/expire", channel_id.as_ref(), message_id.as_ref());
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
+    // GET/channels/{channel.id}/polls/{message.id}/answers/{answer_id}
+    pub(crate) fn get_answer_voters(channel_id: impl AsRef<str>, message_id: impl AsRef<str>, answer_id: u64) -> Self {
+        let method = Method::GET;
+        let uri = api_request!(
+            "/channels/{}/polls/{}/answers/{}",
+            channel_id.as_ref(),
+            message_id.as_ref(),
+            answer_id
+        );
         let bucket_key = bucket_key!(channel: channel_id);
 
         Route {
             method,
             uri,
             bucket_key,
-            body,
+            body: (),
         }
     }
 
-    // PUT/channels/{channel.id}/permissions/{overwrite.id}
-    // TODO: Check {overwrite.id}
-    // pub(crate) fn edit_channel_permissions(
-    //     channel_id: impl AsRef<str>,
-    //     overwrite_id: impl AsRef<str>,
-    //     body: B,
-    // ) -> Self {
-    //     let method = Method::PUT;
-    //     let uri = api_request!(
-    //         "/channels/{}/permissions/{}",
-    //         channel_id.as_ref(),
-    //         overwrite_id.as_ref()
-    //     );
+    // GET/applications/{application.id}/guilds/{guild.id}/commands/permissions
+    pub(crate) fn get_guild_application_command_permissions(
+        application_id: impl AsRef<str>,
+        guild_id: impl AsRef<str>,
+    ) -> Self {
+        let method = Method::GET;
+        let uri = api_request!(
+            "/applications/{}/guilds/{}/commands/permissions",
+            application_id.as_ref(),
+            guild_id.as_ref()
+        );
+        let bucket_key = bucket_key!(guild: guild_id);
 
-    //     let bucket_key = bucket_key!(channel: channel_id);
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
-    //     Route {
-    //         method,
-    //         uri,
-    //         bucket_key,
-    //         body,
-    //     }
-    // }
-}
+    // GET/applications/{application.id}/guilds/{guild.id}/commands/{command.id}/permissions
+    pub(crate) fn get_application_command_permissions(
+        application_id: impl AsRef<str>,
+        guild_id: impl AsRef<str>,
+        command_id: impl AsRef<str>,
+    ) -> Self {
+        let method = Method::GET;
+        let uri = api_request!(
+            "/applications/{}/guilds/{}/commands/{}/permissions",
+            application_id.as_ref(),
+            guild_id.as_ref(),
+            command_id.as_ref()
+        );
+        let bucket_key = bucket_key!(guild: guild_id);
 
-/// Used to encode emoji as a valid char in URL
-fn encode(data: impl AsRef<str>) -> String {
-    let mut escaped = String::new();
-    for b in data.as_ref().as_bytes().iter() {
-        match *b as char {
-            // Accepted characters
-            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => escaped.push(*b as char),
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
 
-            // Everything else is percent-encoded
-            b => escaped.push_str(format!("%{:02X}", b as u32).as_str()),
-        };
+    // GET/guilds/templates/{template.code}
+    pub(crate) fn get_guild_template(code: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/templates/{}", code.as_ref());
+        let bucket_key = "guilds/templates".into();
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/invites
+    pub(crate) fn get_guild_invites(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/invites", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // POST/channels/{channel.id}/typing
+    pub(crate) fn trigger_typing_indicator(channel_id: impl AsRef<str>) -> Route<()> {
+        let method = Method::POST;
+        let uri = api_request!("/channels/{}/typing", channel_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/channels/{channel.id}/pins
+    pub(crate) fn get_pinned_messages(channel_id: impl AsRef<str>) -> Route<()> {
+        let method = Method::GET;
+        let uri = api_request!("/channels/{}/pins", channel_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/members/{user.id}
+    pub(crate) fn get_guild_member(guild_id: impl AsRef<str>, user_id: impl AsRef<str>) -> Route<()> {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/members/{}", guild_id.as_ref(), user_id.as_ref());
+
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // PUT/guilds/{guild.id}/members/{user.id}/roles/{role.id}
+    pub(crate) fn add_guild_member_role(
+        guild_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+        role_id: impl AsRef<str>,
+    ) -> Route<()> {
+        let method = Method::PUT;
+        let uri = api_request!(
+            "/guilds/{}/members/{}/roles/{}",
+            guild_id.as_ref(),
+            user_id.as_ref(),
+            role_id.as_ref()
+        );
+
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // DELETE/guilds/{guild.id}/members/{user.id}/roles/{role.id}
+    pub(crate) fn remove_guild_member_role(
+        guild_id: impl AsRef<str>,
+        user_id: impl AsRef<str>,
+        role_id: impl AsRef<str>,
+    ) -> Route<()> {
+        let method = Method::DELETE;
+        let uri = api_request!(
+            "/guilds/{}/members/{}/roles/{}",
+            guild_id.as_ref(),
+            user_id.as_ref(),
+            role_id.as_ref()
+        );
+
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // PUT/channels/{channel.id}/pins/{message.id}
+    pub(crate) fn add_pinned_channel_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>) -> Route<()> {
+        let method = Method::PUT;
+        let uri = api_request!("/channels/{}/pins/{}", channel_id.as_ref(), msg_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // DELETE/channels/{channel.id}/pins/{message.id}
+    pub(crate) fn delete_pinned_channel_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>) -> Route<()> {
+        let method = Method::DELETE;
+        let uri = api_request!("/channels/{}/pins/{}", channel_id.as_ref(), msg_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // POST/oauth2/token
+    pub(crate) fn oauth2_token() -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/oauth2/token",);
+        let bucket_key = "oauth2:token".into();
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/gateway/bot
+    pub(crate) fn get_gateway_bot() -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/gateway/bot",);
+        let bucket_key = "gateway:bot".into();
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/stickers/{sticker.id}
+    pub(crate) fn get_sticker(sticker_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/stickers/{}", sticker_id.as_ref());
+        let bucket_key = bucket_key!(sticker: sticker_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/stickers
+    pub(crate) fn list_guild_stickers(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/stickers", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // POST/guilds/{guild.id}/stickers
+    pub(crate) fn create_guild_sticker(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/{}/stickers", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // GET/guilds/{guild.id}/auto-moderation/rules
+    pub(crate) fn list_auto_moderation_rules(guild_id: impl AsRef<str>) -> Self {
+        let method = Method::GET;
+        let uri = api_request!("/guilds/{}/auto-moderation/rules", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+
+    // DELETE/webhooks/{webhook.id}/{webhook.token}/messages/{message.id}
+    pub(crate) fn delete_webhook_message(
+        webhook_id: impl AsRef<str>,
+        token: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        thread_id: Option<&str>,
+    ) -> Self {
+        let method = Method::DELETE;
+        let mut uri = api_request!(
+            "/webhooks/{}/{}/messages/{}",
+            webhook_id.as_ref(),
+            token.as_ref(),
+            message_id.as_ref()
+        );
+        if let Some(thread_id) = thread_id {
+            uri.push_str(&format!("?thread_id={}", thread_id));
+        }
+        let bucket_key = bucket_key!(webhook: webhook_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body: (),
+        }
+    }
+}
+
+// Routes with body
+impl<B: Into<Body>> Route<B> {
+    // pub(crate) fn as_request(self, token: &str) -> (String, Request<Body>) {
+    //     // let request = match self.method {
+    //     //     Method::GET | Method::PUT | Method::DELETE => Request::builder()
+    //     //         .method(&self.method)
+    //     //         .uri(&self.uri)
+    //     //         .header("Authorization", token)
+    //     //         .body(())
+    //     //         .unwrap(),
+    //     //     Method::POST | Method::PATCH => Request::builder()
+    //     //         .method(&self.method)
+    //     //         .uri(&self.uri)
+    //     //         .header("Authorization", token)
+    //     //         .header("Content-Type", "application/json")
+    //     //         .body(self.body.into())
+    //     //         .unwrap(),
+    //     //     _ => unimplemented!(),
+    //     // };
+
+    //     let request = match self.body {
+    //         () => Request::builder()
+    //             .method(&self.method)
+    //             .uri(&self.uri)
+    //             .header("Authorization", token)
+    //             .body(())
+    //             .unwrap(),
+    //         _ => Request::builder()
+    //             .method(&self.method)
+    //             .uri(&self.uri)
+    //             .header("Authorization", token)
+    //             .header("Content-Type", "application/json")
+    //             .body(self.body)
+    //             .unwrap(),
+    //     };
+
+    //     println!("{:?}", request);
+
+    //     (self.bucket_key, request)
+    // }
+
+    // PATCH/channels/{channel.id}
+    pub(crate) fn edit_channel(channel_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/channels/{}", channel_id.as_ref());
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/channels/{channel.id}/messages
+    pub(crate) fn create_message(channel_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/channels/{}/messages", channel_id.as_ref());
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/channels/{channel.id}/messages/{message.id}
+    pub(crate) fn edit_message(channel_id: impl AsRef<str>, msg_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/channels/{}/messages/{}", channel_id.as_ref(), msg_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/guilds/{guild.id}/auto-moderation/rules
+    pub(crate) fn create_auto_moderation_rule(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/{}/auto-moderation/rules", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/guilds/{guild.id}/channels
+    pub(crate) fn modify_guild_channel_positions(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/guilds/{}/channels", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/guilds/{guild.id}/widget
+    pub(crate) fn modify_guild_widget(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/guilds/{}/widget", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PUT/guilds/{guild.id}/onboarding
+    pub(crate) fn modify_guild_onboarding(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PUT;
+        let uri = api_request!("/guilds/{}/onboarding", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/guilds/{guild.id}
+    pub(crate) fn modify_guild(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/guilds/{}", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/guilds/{guild.id}/emojis
+    pub(crate) fn create_guild_emoji(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/{}/emojis", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/guilds/{guild.id}/roles
+    pub(crate) fn create_guild_role(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/{}/roles", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/guilds/{guild.id}/roles/{role.id}
+    pub(crate) fn modify_guild_role(guild_id: impl AsRef<str>, role_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/guilds/{}/roles/{}", guild_id.as_ref(), role_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PUT/applications/{application.id}/guilds/{guild.id}/commands/{command.id}/permissions
+    pub(crate) fn edit_application_command_permissions(
+        application_id: impl AsRef<str>,
+        guild_id: impl AsRef<str>,
+        command_id: impl AsRef<str>,
+        body: B,
+    ) -> Self {
+        let method = Method::PUT;
+        let uri = api_request!(
+            "/applications/{}/guilds/{}/commands/{}/permissions",
+            application_id.as_ref(),
+            guild_id.as_ref(),
+            command_id.as_ref()
+        );
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/guilds/templates/{template.code}
+    pub(crate) fn create_guild_from_template(code: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/templates/{}", code.as_ref());
+        let bucket_key = "guilds/templates".into();
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/guilds/{guild.id}/templates
+    pub(crate) fn create_guild_template(guild_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/guilds/{}/templates", guild_id.as_ref());
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Self {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/channels/{channel.id}/messages/bulk-delete
+    pub(crate) fn bulk_delete_messages(channel_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/channels/{}/messages/bulk-delete", channel_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/channels/{channel.id}/followers
+    pub(crate) fn follow_news_channel(channel_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::POST;
+        let uri = api_request!("/channels/{}/followers", channel_id.as_ref());
+
+        let bucket_key = bucket_key!(channel: channel_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/webhooks/{webhook.id}/{webhook.token}
+    pub(crate) fn execute_webhook(
+        webhook_id: impl AsRef<str>,
+        token: impl AsRef<str>,
+        wait: bool,
+        thread_id: Option<&str>,
+        body: B,
+    ) -> Self {
+        let method = Method::POST;
+        let mut uri = api_request!("/webhooks/{}/{}?wait={}", webhook_id.as_ref(), token.as_ref(), wait);
+        if let Some(thread_id) = thread_id {
+            uri.push_str(&format!("&thread_id={}", thread_id));
+        }
+
+        let bucket_key = bucket_key!(webhook: webhook_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/webhooks/{webhook.id}/{webhook.token}/messages/{message.id}
+    pub(crate) fn edit_webhook_message(
+        webhook_id: impl AsRef<str>,
+        token: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        thread_id: Option<&str>,
+        body: B,
+    ) -> Self {
+        let method = Method::PATCH;
+        let mut uri = api_request!(
+            "/webhooks/{}/{}/messages/{}",
+            webhook_id.as_ref(),
+            token.as_ref(),
+            message_id.as_ref()
+        );
+        if let Some(thread_id) = thread_id {
+            uri.push_str(&format!("?thread_id={}", thread_id));
+        }
+
+        let bucket_key = bucket_key!(webhook: webhook_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PUT/guilds/{guild.id}/members/{user.id}
+    pub(crate) fn add_guild_member(guild_id: impl AsRef<str>, user_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PUT;
+        let uri = api_request!("/guilds/{}/members/{}", guild_id.as_ref(), user_id.as_ref());
+
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PATCH/guilds/{guild.id}/members/{user.id}
+    pub(crate) fn modify_guild_member(guild_id: impl AsRef<str>, user_id: impl AsRef<str>, body: B) -> Self {
+        let method = Method::PATCH;
+        let uri = api_request!("/guilds/{}/members/{}", guild_id.as_ref(), user_id.as_ref());
+
+        let bucket_key = bucket_key!(guild: guild_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // POST/interactions/{interaction.id}/{interaction.token}/callback
+    pub(crate) fn create_interaction_response(
+        interaction_id: impl AsRef<str>,
+        interaction_token: impl AsRef<str>,
+        body: B,
+    ) -> Self {
+        let method = Method::POST;
+        let uri = api_request!(
+            "/interactions/{}/{}/callback",
+            interaction_id.as_ref(),
+            interaction_token.as_ref()
+        );
+
+        let bucket_key = bucket_key!(interaction: interaction_id);
+
+        Route {
+            method,
+            uri,
+            bucket_key,
+            body,
+        }
+    }
+
+    // PUT/channels/{channel.id}/permissions/{overwrite.id}
+    // TODO: Check {overwrite.id}
+    // pub(crate) fn edit_channel_permissions(
+    //     channel_id: impl AsRef<str>,
+    //     overwrite_id: impl AsRef<str>,
+    //     body: B,
+    // ) -> Self {
+    //     let method = Method::PUT;
+    //     let uri = api_request!(
+    //         "/channels/{}/permissions/{}",
+    //         channel_id.as_ref(),
+    //         overwrite_id.as_ref()
+    //     );
+
+    //     let bucket_key = bucket_key!(channel: channel_id);
+
+    //     Route {
+    //         method,
+    //         uri,
+    //         bucket_key,
+    //         body,
+    //     }
+    // }
+}
+
+/// Used to encode emoji as a valid char in URL
+fn encode(data: impl AsRef<str>) -> String {
+    let mut escaped = String::new();
+    for b in data.as_ref().as_bytes().iter() {
+        match *b as char {
+            // Accepted characters
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => escaped.push(*b as char),
+
+            // Everything else is percent-encoded
+            b => escaped.push_str(format!("%{:02X}", b as u32).as_str()),
+        };
+    }
+    return escaped;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_routes_with_thread_id() {
+        let route = Route::execute_webhook("1", "token", true, Some("2"), String::new());
+        assert_eq!(route.uri, "/webhooks/1/token?wait=true&thread_id=2");
+        assert_eq!(route.bucket_key, "webhook:1");
+
+        let route = Route::edit_webhook_message("1", "token", "3", Some("2"), String::new());
+        assert_eq!(route.method, Method::PATCH);
+        assert_eq!(route.uri, "/webhooks/1/token/messages/3?thread_id=2");
+
+        let route = Route::delete_webhook_message("1", "token", "3", None);
+        assert_eq!(route.uri, "/webhooks/1/token/messages/3");
+    }
+
+    #[test]
+    fn get_guild_with_counts() {
+        assert_eq!(Route::get_guild("1", true).uri, "/guilds/1?with_counts=true");
+        assert_eq!(Route::get_guild("1", false).uri, "/guilds/1?with_counts=false");
+    }
+
+    #[test]
+    fn current_user_guilds_pages() {
+        assert_eq!(Route::get_current_user_guilds(None, 200).uri, "/users/@me/guilds?limit=200");
+        assert_eq!(
+            Route::get_current_user_guilds(Some("10"), 100).uri,
+            "/users/@me/guilds?limit=100&after=10"
+        );
+    }
+
+    #[test]
+    fn command_permissions_routes() {
+        let route = Route::get_guild_application_command_permissions("1", "2");
+        assert_eq!(route.uri, "/applications/1/guilds/2/commands/permissions");
+        assert_eq!(route.bucket_key, "guild:2");
+
+        let route = Route::edit_application_command_permissions("1", "2", "3", String::new());
+        assert_eq!(route.method, Method::PUT);
+        assert_eq!(route.uri, "/applications/1/guilds/2/commands/3/permissions");
+    }
+
+    #[test]
+    fn guild_template_routes() {
+        let route = Route::get_guild_template("code");
+        assert_eq!(route.uri, "/guilds/templates/code");
+        assert_eq!(route.bucket_key, "guilds/templates");
+
+        let route = Route::create_guild_template("1", String::new());
+        assert_eq!(route.method, Method::POST);
+        assert_eq!(route.uri, "/guilds/1/templates");
+        assert_eq!(route.bucket_key, "guild:1");
+    }
+
+    #[test]
+    fn oauth2_token_route() {
+        let route = Route::oauth2_token();
+        assert_eq!(route.method, Method::POST);
+        assert_eq!(route.uri, "/oauth2/token");
+        assert_eq!(route.bucket_key, "oauth2:token");
+    }
+
+    #[test]
+    fn channel_positions_route() {
+        let route = Route::modify_guild_channel_positions("1", String::new());
+        assert_eq!(route.method, Method::PATCH);
+        assert_eq!(route.uri, "/guilds/1/channels");
+        assert_eq!(route.bucket_key, "guild:1");
+    }
+
+    #[test]
+    fn modify_guild_member_route() {
+        let route = Route::modify_guild_member("1", "2", String::new());
+        assert_eq!(route.method, Method::PATCH);
+        assert_eq!(route.uri, "/guilds/1/members/2");
+        assert_eq!(route.bucket_key, "guild:1");
+    }
+
+    #[test]
+    fn invite_listing_routes() {
+        assert_eq!(Route::get_channel_invites("1").uri, "/channels/1/invites");
+
+        let route = Route::get_guild_invites("2");
+        assert_eq!(route.uri, "/guilds/2/invites");
+        assert_eq!(route.bucket_key, "guild:2");
+    }
+
+    #[test]
+    fn get_guild_member_route() {
+        let route = Route::get_guild_member("1", "2");
+        assert_eq!(route.method, Method::GET);
+        assert_eq!(route.uri, "/guilds/1/members/2");
+        assert_eq!(route.bucket_key, "guild:1");
+    }
+
+    #[test]
+    fn follow_news_channel_route() {
+        let route = Route::follow_news_channel("1", String::new());
+        assert_eq!(route.method, Method::POST);
+        assert_eq!(route.uri, "/channels/1/followers");
+    }
+
+    #[test]
+    fn leave_guild_route() {
+        let route = Route::leave_guild("1");
+        assert_eq!(route.method, Method::DELETE);
+        assert_eq!(route.uri, "/users/@me/guilds/1");
+        assert_eq!(route.bucket_key, "guild:1");
     }
-    return escaped;
 }
@@ -75,8 +75,8 @@ define_cfg! {
     mod http;
     mod runtime;
 
-    pub use error::PandaError;
-    pub use http::HttpClient;
+    pub use error::{PandaError, ResourceKind};
+    pub use http::{HttpClient, HttpMetrics, RequestInfo};
 
     // Re-exports
     pub use models::gateway::events;
@@ -1,6 +1,9 @@
+use crate::{error::Result, http::HttpClient};
+
+use isahc::Body;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Attachment {
     pub id: String,
     pub filename: String,
@@ -10,3 +13,26 @@ pub struct Attachment {
     pub height: Option<u64>,
     pub width: Option<u64>,
 }
+
+impl Attachment {
+    /// Shortcut for [`HttpClient.download_attachment`]
+    ///
+    /// [`HttpClient.download_attachment`]: ../../../struct.HttpClient.html#method.download_attachment
+    pub async fn download(&self, http: &HttpClient) -> Result<Vec<u8>> {
+        http.download_attachment(self, None).await
+    }
+
+    /// Shortcut for [`HttpClient.download_attachment`], with a max size in bytes
+    ///
+    /// [`HttpClient.download_attachment`]: ../../../struct.HttpClient.html#method.download_attachment
+    pub async fn download_with_limit(&self, http: &HttpClient, max_size: u64) -> Result<Vec<u8>> {
+        http.download_attachment(self, Some(max_size)).await
+    }
+
+    /// Shortcut for [`HttpClient.download_attachment_stream`]
+    ///
+    /// [`HttpClient.download_attachment_stream`]: ../../../struct.HttpClient.html#method.download_attachment_stream
+    pub async fn download_stream(&self, http: &HttpClient) -> Result<Body> {
+        http.download_attachment_stream(self, None).await
+    }
+}
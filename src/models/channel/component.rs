@@ -0,0 +1,164 @@
+use crate::models::emoji::Emoji;
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+/// A message component, an action row contains buttons or a select menu. In modal submits
+/// action rows contain the text inputs with the value written by the user.
+/// [Discord Documentation](https://discord.com/developers/docs/interactions/message-components)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Component {
+    #[serde(rename = "type")]
+    pub kind: ComponentKind,
+
+    // Action rows
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<Component>,
+
+    // Buttons and select menus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+
+    // Buttons
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<ButtonStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<Emoji>,
+    /// Only for `Link` buttons, they don't have a custom_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    // Select menus
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<SelectOption>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_values: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_values: Option<u64>,
+
+    // Text inputs, only received in modal submits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum ComponentKind {
+    ActionRow = 1,
+    Button = 2,
+    SelectMenu = 3,
+    TextInput = 4,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum ButtonStyle {
+    Primary = 1,
+    Secondary = 2,
+    Success = 3,
+    Danger = 4,
+    Link = 5,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum TextInputStyle {
+    /// Single line input
+    Short = 1,
+    /// Multi line input
+    Paragraph = 2,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SelectOption {
+    pub label: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<Emoji>,
+    #[serde(default)]
+    pub default: bool,
+}
+
+impl Component {
+    fn empty(kind: ComponentKind) -> Self {
+        Component {
+            kind,
+            components: Vec::new(),
+            custom_id: None,
+            disabled: None,
+            style: None,
+            label: None,
+            emoji: None,
+            url: None,
+            options: Vec::new(),
+            placeholder: None,
+            min_values: None,
+            max_values: None,
+            value: None,
+        }
+    }
+
+    /// Create an action row with the given buttons or select menu
+    pub fn action_row(components: Vec<Component>) -> Self {
+        Component {
+            components,
+            ..Component::empty(ComponentKind::ActionRow)
+        }
+    }
+
+    /// Create a button, for links use [`Component::link_button`]
+    ///
+    /// [`Component::link_button`]: #method.link_button
+    pub fn button(style: ButtonStyle, custom_id: impl Into<String>, label: impl Into<String>) -> Self {
+        Component {
+            style: Some(style),
+            custom_id: Some(custom_id.into()),
+            label: Some(label.into()),
+            ..Component::empty(ComponentKind::Button)
+        }
+    }
+
+    /// Create a button that opens the url, it doesn't send an interaction
+    pub fn link_button(url: impl Into<String>, label: impl Into<String>) -> Self {
+        Component {
+            style: Some(ButtonStyle::Link),
+            url: Some(url.into()),
+            label: Some(label.into()),
+            ..Component::empty(ComponentKind::Button)
+        }
+    }
+
+    /// Create a select menu with the given options
+    pub fn select_menu(custom_id: impl Into<String>, options: Vec<SelectOption>) -> Self {
+        Component {
+            custom_id: Some(custom_id.into()),
+            options,
+            ..Component::empty(ComponentKind::SelectMenu)
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = Some(disabled);
+
+        self
+    }
+}
+
+impl SelectOption {
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        SelectOption {
+            label: label.into(),
+            value: value.into(),
+            description: None,
+            emoji: None,
+            default: false,
+        }
+    }
+}
@@ -1,73 +1,136 @@
+use crate::error::{PandaError, Result};
+
 use serde::{Deserialize, Serialize};
 use std::default::Default;
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+// Embed limits
+// https://discord.com/developers/docs/resources/channel#embed-limits
+pub(crate) const EMBED_TITLE_LIMIT: usize = 256;
+pub(crate) const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+pub(crate) const EMBED_FIELDS_LIMIT: usize = 25;
+pub(crate) const EMBED_FIELD_NAME_LIMIT: usize = 256;
+pub(crate) const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+pub(crate) const EMBED_FOOTER_TEXT_LIMIT: usize = 2048;
+pub(crate) const EMBED_AUTHOR_NAME_LIMIT: usize = 256;
+pub(crate) const EMBED_TOTAL_LIMIT: usize = 6000;
+
+const ELLIPSIS: char = '…';
+const CODE_FENCE: &str = "```";
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct Embed {
     /// Title of the embed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<u64>, // TODO: COLOR ENUM
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub footer: Option<EmbedFooter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<EmbedImage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thumbnail: Option<EmbedThumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<EmbedVideo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub provider: Option<EmbedProvider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<EmbedAuthor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<EmbedField>,
+
+    // True when the color or the footer were set (or cleared), so the defaults aren't applied
+    #[serde(skip)]
+    color_set: bool,
+    #[serde(skip)]
+    footer_set: bool,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+/// Color and footer used for the embeds that don't set them
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EmbedDefaults {
+    pub(crate) color: Option<u64>,
+    pub(crate) footer: Option<EmbedFooter>,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedFooter {
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_icon_url: Option<String>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedImage {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<u64>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedThumbnail {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<u64>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedVideo {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<u64>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedProvider {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedAuthor {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub proxy_icon_url: Option<String>,
 }
 
-#[derive(Default, Debug, Deserialize, Serialize)]
+#[derive(Clone, Default, Debug, Deserialize, Serialize)]
 pub struct EmbedField {
     pub name: String,
     pub value: String,
+    #[serde(default)]
     pub inline: bool,
 }
 
@@ -88,22 +151,54 @@ impl Embed {
         self
     }
 
+    /// Set the description from markdown text, if it's longer than the limit it's truncated
+    /// without cutting a link or a code block line, and open code blocks are closed
+    pub fn set_description_markdown(&mut self, description: impl AsRef<str>) -> &mut Self {
+        self.description = Some(fit_markdown(description.as_ref(), EMBED_DESCRIPTION_LIMIT));
+
+        self
+    }
+
     pub fn set_url(&mut self, url: impl Into<String>) -> &mut Self {
         self.url = Some(url.into());
 
         self
     }
 
-    pub fn set_color(&mut self, _color: u64) -> &mut Self {
-        unimplemented!();
+    /// Set the color, `None` removes it, so the default embed color isn't used either
+    pub fn set_color(&mut self, color: impl Into<Option<u64>>) -> &mut Self {
+        self.color = color.into();
+        self.color_set = true;
+
+        self
     }
 
     pub fn add_footer(&mut self, footer: EmbedFooter) -> &mut Self {
         self.footer = Some(footer);
+        self.footer_set = true;
+
+        self
+    }
+
+    /// Remove the footer, so the default embed footer isn't used either
+    pub fn remove_footer(&mut self) -> &mut Self {
+        self.footer = None;
+        self.footer_set = true;
 
         self
     }
 
+    /// Use the default color and footer, if they weren't set for this embed
+    pub(crate) fn apply_defaults(&mut self, defaults: &EmbedDefaults) {
+        if !self.color_set && self.color.is_none() {
+            self.color = defaults.color;
+        }
+
+        if !self.footer_set && self.footer.is_none() {
+            self.footer = defaults.footer.clone();
+        }
+    }
+
     pub fn add_image(&mut self, image: EmbedImage) -> &mut Self {
         self.image = Some(image);
 
@@ -144,4 +239,364 @@ impl Embed {
 
         self
     }
+
+    /// Trims the title, description, footer, author and fields to the Discord limits, adding an
+    /// ellipsis at the end of the trimmed text. Fields beyond 25 are dropped.
+    pub fn truncate(&mut self) -> &mut Self {
+        if let Some(title) = &mut self.title {
+            truncate_with_ellipsis(title, EMBED_TITLE_LIMIT);
+        }
+
+        if let Some(description) = &mut self.description {
+            *description = fit_markdown(description, EMBED_DESCRIPTION_LIMIT);
+        }
+
+        if let Some(footer) = &mut self.footer {
+            truncate_with_ellipsis(&mut footer.text, EMBED_FOOTER_TEXT_LIMIT);
+        }
+
+        if let Some(name) = self.author.as_mut().and_then(|a| a.name.as_mut()) {
+            truncate_with_ellipsis(name, EMBED_AUTHOR_NAME_LIMIT);
+        }
+
+        self.fields.truncate(EMBED_FIELDS_LIMIT);
+        for field in self.fields.iter_mut() {
+            truncate_with_ellipsis(&mut field.name, EMBED_FIELD_NAME_LIMIT);
+            truncate_with_ellipsis(&mut field.value, EMBED_FIELD_VALUE_LIMIT);
+        }
+
+        self
+    }
+
+    /// Returns the number of characters of the title, description, footer, author and fields,
+    /// Discord limits it to 6000 for all the embeds of a message
+    pub(crate) fn text_length(&self) -> usize {
+        let count = |text: Option<&String>| text.map_or(0, |t| t.chars().count());
+
+        let fields: usize = self
+            .fields
+            .iter()
+            .map(|f| f.name.chars().count() + f.value.chars().count())
+            .sum();
+
+        count(self.title.as_ref())
+            + count(self.description.as_ref())
+            + count(self.footer.as_ref().map(|f| &f.text))
+            + count(self.author.as_ref().and_then(|a| a.name.as_ref()))
+            + fields
+    }
+
+    /// Check the embed against the Discord limits, used by the strict validation of the http client
+    pub(crate) fn validate(&self) -> Result<()> {
+        check_length("embed.title", self.title.as_deref(), EMBED_TITLE_LIMIT)?;
+        check_length("embed.description", self.description.as_deref(), EMBED_DESCRIPTION_LIMIT)?;
+        check_length(
+            "embed.footer.text",
+            self.footer.as_ref().map(|f| f.text.as_str()),
+            EMBED_FOOTER_TEXT_LIMIT,
+        )?;
+        check_length(
+            "embed.author.name",
+            self.author.as_ref().and_then(|a| a.name.as_deref()),
+            EMBED_AUTHOR_NAME_LIMIT,
+        )?;
+
+        if self.fields.len() > EMBED_FIELDS_LIMIT {
+            return Err(PandaError::Validation {
+                field: "embed.fields",
+                reason: format!("an embed can't have more than {} fields", EMBED_FIELDS_LIMIT),
+            });
+        }
+
+        for field in &self.fields {
+            if field.name.is_empty() || field.value.is_empty() {
+                return Err(PandaError::Validation {
+                    field: "embed.fields",
+                    reason: "the name and value of a field can't be empty".into(),
+                });
+            }
+            check_length("embed.fields.name", Some(&field.name), EMBED_FIELD_NAME_LIMIT)?;
+            check_length("embed.fields.value", Some(&field.value), EMBED_FIELD_VALUE_LIMIT)?;
+        }
+
+        if self.text_length() > EMBED_TOTAL_LIMIT {
+            return Err(PandaError::Validation {
+                field: "embed",
+                reason: format!("the text of an embed can't be longer than {} characters", EMBED_TOTAL_LIMIT),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a validation error if the text is longer than `max` characters
+fn check_length(field: &'static str, text: Option<&str>, max: usize) -> Result<()> {
+    let len = text.map_or(0, |t| t.chars().count());
+    if len > max {
+        return Err(PandaError::Validation {
+            field,
+            reason: format!("it has {} characters, the limit is {}", len, max),
+        });
+    }
+
+    Ok(())
+}
+
+/// Trims the string to `max` characters, the last one being an ellipsis
+fn truncate_with_ellipsis(text: &mut String, max: usize) {
+    if text.chars().count() <= max {
+        return;
+    }
+
+    // Discord counts characters, not bytes
+    let end = text.char_indices().nth(max - 1).map(|(i, _)| i).unwrap_or(text.len());
+    text.truncate(end);
+    text.push(ELLIPSIS);
+}
+
+/// Fits markdown text in `max` characters. If it needs to be truncated, links are never cut,
+/// code blocks are cut at the end of a line and closed, and an ellipsis is added at the end
+fn fit_markdown(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+
+    // Reserve space for the ellipsis
+    let mut end = markdown_cut_index(text, max.saturating_sub(1));
+    let (mut fence_start, mut link_start) = scan_markdown(&text[..end]);
+
+    // Reserve space for "\n```\n…" too, if a code block needs to be closed
+    if fence_start.is_some() {
+        end = markdown_cut_index(text, max.saturating_sub(CODE_FENCE.len() + 3));
+        let (fence, link) = scan_markdown(&text[..end]);
+        fence_start = fence;
+        link_start = link;
+    }
+
+    let mut result = match (fence_start, link_start) {
+        // Cut inside a code block, keep only complete lines and close it
+        (Some(fence), _) => {
+            let cut = match text[..end].rfind('\n') {
+                Some(line_end) if line_end > fence => line_end,
+                // The block doesn't have complete lines, so it's removed
+                _ => fence,
+            };
+
+            let mut result = text[..cut].trim_end().to_string();
+            if cut != fence {
+                result.push('\n');
+                result.push_str(CODE_FENCE);
+                result.push('\n');
+            }
+            result
+        }
+        // Cut inside a link, remove it
+        (None, Some(link)) => text[..link].trim_end().to_string(),
+        (None, None) => text[..end].to_string(),
+    };
+
+    result.push(ELLIPSIS);
+
+    result
+}
+
+/// Returns the byte index of the character `n`, without splitting a run of backticks
+fn markdown_cut_index(text: &str, n: usize) -> usize {
+    let mut end = text.char_indices().nth(n).map(|(i, _)| i).unwrap_or(text.len());
+
+    while end > 0 && text[..end].ends_with('`') && text[end..].starts_with('`') {
+        end -= 1;
+    }
+
+    end
+}
+
+/// Returns the start of the code block and the start of the link that are open
+/// at the end of the text
+fn scan_markdown(text: &str) -> (Option<usize>, Option<usize>) {
+    let mut fence_start = None;
+    let mut link_start = None;
+    let mut i = 0;
+
+    while i < text.len() {
+        let rest = &text[i..];
+
+        if rest.starts_with(CODE_FENCE) {
+            fence_start = match fence_start {
+                Some(_) => None,
+                None => Some(i),
+            };
+            link_start = None;
+            i += CODE_FENCE.len();
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        if fence_start.is_none() {
+            match c {
+                '[' => link_start = Some(i),
+                ')' | '\n' => link_start = None,
+                _ => {}
+            }
+        }
+        i += c.len_utf8();
+    }
+
+    (fence_start, link_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(n: usize) -> String {
+        "a".repeat(n)
+    }
+
+    #[test]
+    fn truncate_long_text() {
+        let mut embed = Embed::new();
+        embed.set_title(text(300)).add_footer(EmbedFooter {
+            text: "é".repeat(3000),
+            ..Default::default()
+        });
+        for i in 0..30 {
+            embed.add_field(format!("field {}", i), text(2000), false);
+        }
+        embed.truncate();
+
+        let title = embed.title.as_ref().unwrap();
+        assert_eq!(title.chars().count(), EMBED_TITLE_LIMIT);
+        assert!(title.ends_with(ELLIPSIS));
+
+        // Characters are counted, not bytes
+        let footer = &embed.footer.as_ref().unwrap().text;
+        assert_eq!(footer.chars().count(), EMBED_FOOTER_TEXT_LIMIT);
+
+        assert_eq!(embed.fields.len(), EMBED_FIELDS_LIMIT);
+        assert_eq!(embed.fields[0].value.chars().count(), EMBED_FIELD_VALUE_LIMIT);
+        assert_eq!(embed.fields[0].name, "field 0");
+    }
+
+    #[test]
+    fn truncate_keeps_short_text() {
+        let mut embed = Embed::new();
+        embed.set_title("title").set_description("description").truncate();
+
+        assert_eq!(embed.title.as_deref(), Some("title"));
+        assert_eq!(embed.description.as_deref(), Some("description"));
+    }
+
+    #[test]
+    fn fit_short_markdown() {
+        assert_eq!(fit_markdown("**hello**", 20), "**hello**");
+        assert_eq!(fit_markdown("hello world", 5), "hell…");
+    }
+
+    #[test]
+    fn fit_markdown_removes_cut_links() {
+        assert_eq!(fit_markdown("see [link](https://example.com) now", 15), "see…");
+        assert_eq!(fit_markdown("[a](b) and more text here", 10), "[a](b) an…");
+    }
+
+    #[test]
+    fn fit_markdown_closes_code_blocks() {
+        let text = "```\nline1\nline2\nline3\n```";
+        let fitted = fit_markdown(text, 20);
+
+        assert_eq!(fitted, "```\nline1\n```\n…");
+        assert!(fitted.chars().count() <= 20);
+    }
+
+    #[test]
+    fn fit_markdown_doesnt_split_backticks() {
+        assert_eq!(fit_markdown("text ```verylongline```", 12), "text …");
+    }
+
+    #[test]
+    fn fit_markdown_is_within_limit() {
+        let text = format!("intro\n```rust\n{}```\n[link](https://example.com)", "let a = 1;\n".repeat(50));
+        for max in [10, 50, 100, 200].iter() {
+            assert!(fit_markdown(&text, *max).chars().count() <= *max);
+        }
+    }
+
+    #[test]
+    fn description_markdown_is_fitted() {
+        let mut embed = Embed::new();
+        embed.set_description_markdown(text(5000));
+
+        assert_eq!(embed.description.unwrap().chars().count(), EMBED_DESCRIPTION_LIMIT);
+    }
+
+    #[test]
+    fn defaults_are_applied_when_unset() {
+        let defaults = EmbedDefaults {
+            color: Some(0xff0000),
+            footer: Some(EmbedFooter {
+                text: "footer".into(),
+                ..Default::default()
+            }),
+        };
+
+        let mut embed = Embed::new();
+        embed.apply_defaults(&defaults);
+        assert_eq!(embed.color, Some(0xff0000));
+        assert_eq!(embed.footer.unwrap().text, "footer");
+
+        let mut embed = Embed::new();
+        embed.set_color(0x00ff00).add_footer(EmbedFooter {
+            text: "own".into(),
+            ..Default::default()
+        });
+        embed.apply_defaults(&defaults);
+        assert_eq!(embed.color, Some(0x00ff00));
+        assert_eq!(embed.footer.unwrap().text, "own");
+    }
+
+    #[test]
+    fn cleared_fields_dont_use_defaults() {
+        let defaults = EmbedDefaults {
+            color: Some(0xff0000),
+            footer: Some(EmbedFooter::default()),
+        };
+
+        let mut embed = Embed::new();
+        embed.set_color(None).remove_footer();
+        embed.apply_defaults(&defaults);
+
+        assert_eq!(embed.color, None);
+        assert!(embed.footer.is_none());
+    }
+
+    #[test]
+    fn received_embed_is_sent_unchanged() {
+        let received = serde_json::json!({
+            "type": "video",
+            "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "title": "Panda",
+            "video": {
+                "url": "https://www.youtube.com/embed/dQw4w9WgXcQ",
+                "proxy_url": "https://images-ext-1.discordapp.net/external/video",
+                "width": 1280,
+                "height": 720
+            },
+            "provider": {"name": "YouTube", "url": "https://www.youtube.com"}
+        });
+        let embed: Embed = serde_json::from_value(received.clone()).unwrap();
+
+        assert!(embed.fields.is_empty());
+        assert_eq!(serde_json::to_value(&embed).unwrap(), received);
+    }
+
+    #[test]
+    fn field_inline_defaults_to_false() {
+        let embed: Embed = serde_json::from_value(serde_json::json!({
+            "fields": [{"name": "Bamboo", "value": "Tasty"}]
+        }))
+        .unwrap();
+
+        assert!(!embed.fields[0].inline);
+    }
 }
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// Returned when an announcement channel is followed.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/channel#followed-channel-object)
+pub struct FollowedChannel {
+    /// ID of the source channel
+    pub channel_id: String,
+
+    /// Created target webhook ID
+    pub webhook_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_followed_channel() {
+        let followed: FollowedChannel =
+            serde_json::from_str(r#"{"channel_id": "1", "webhook_id": "2"}"#).unwrap();
+
+        assert_eq!(followed.channel_id, "1");
+        assert_eq!(followed.webhook_id, "2");
+    }
+}
@@ -1,17 +1,33 @@
 use crate::{
     error::Result,
-    http::HttpClient,
-    models::{guild::GuildMember, user::User},
+    http::{routing::Route, HttpClient},
+    models::{
+        gateway::Intents,
+        guild::{Guild, GuildMember, Role},
+        sticker::StickerItem,
+        user::User,
+    },
+    utils::builders::{AllowedMentions, CreateMessage},
 };
 
-use super::{Embed, MentionChannel, MessageReference, Attachment, Reaction, MessageApplication};
+use super::{
+    Attachment, Channel, Component, Embed, MentionChannel, MessageActivity, MessageApplication, MessageInteraction,
+    MessageInteractionMetadata, MessageReference, MessageSnapshot, Poll, Reaction,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_repr::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 /// Represents a message sent in a channel within Discord.
 /// [Discord Documentation](https://discord.com/developers/docs/resources/channel#message-object)
+///
+/// **Note:** when the bot doesn't have the privileged message content intent, `content`, `embed`
+/// and `attachments` are sent empty for messages that don't mention the bot, see
+/// [`Message::content_available`].
+///
+/// [`Message::content_available`]: #method.content_available
 pub struct Message {
     /// ID of the message
     pub id: String,
@@ -28,7 +44,7 @@ pub struct Message {
     /// Member properties for this message's author in case it was sent in a guild
     pub member: Option<GuildMember>,
 
-    /// Contents of the message
+    /// Contents of the message, it can be empty if the bot can't read message content
     pub content: String,
 
     /// When this message was sent, as string
@@ -49,8 +65,8 @@ pub struct Message {
     /// Roles specifically mentioned in this message
     pub mention_roles: Vec<String>,
     
-    /// Channels specifically mentioned in this message
-    #[serde(default)]
+    /// Channels specifically mentioned in this message, only sent for crossposted messages
+    #[serde(rename = "mention_channels", default)]
     pub mentions_channels: Vec<MentionChannel>,
 
     /// Any attached files
@@ -60,12 +76,16 @@ pub struct Message {
     #[serde(default)]
     pub embed: Vec<Embed>,
 
+    /// The stickers sent with the message
+    #[serde(default)]
+    pub sticker_items: Vec<StickerItem>,
+
     /// Reactions to the message
     #[serde(default)]
     pub reactions: Vec<Reaction>,
 
     /// Used for validating a message was sent
-    pub nonce: Option<String>,
+    pub nonce: Option<Nonce>,
 
     /// Whether this message is pinned
     pub pinned: bool,
@@ -73,23 +93,61 @@ pub struct Message {
     /// If the message is generated by a webhook, this is the webhook's id
     pub webhook_id: Option<String>,
 
-    /// Type of message
-    #[serde(rename = "type")]
-    pub kind: Option<MessageKind>,
+    /// Type of message, `Regular` if Discord didn't send it
+    #[serde(rename = "type", default)]
+    pub kind: MessageKind,
 
-    // activity: MessageActivity,
+    /// Sent with Rich Presence-related chat embeds
+    pub activity: Option<MessageActivity>,
 
     /// Sent with Rich Presence-related chat embeds
     pub application: Option<MessageApplication>,
 
-    /// Reference data sent with crossposted messages
+    /// Reference data sent with crossposted messages and replies
     pub message_reference: Option<MessageReference>,
 
+    /// The message this message replies to, `None` if it wasn't sent and `Some(None)` if
+    /// the referenced message was deleted. Discord doesn't always send it, see
+    /// [`Message::resolve_reference`]
+    ///
+    /// [`Message::resolve_reference`]: #method.resolve_reference
+    #[serde(
+        default,
+        deserialize_with = "deserialize_referenced_message",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub referenced_message: Option<Option<Box<Message>>>,
+
     /// Message flags ORd together, describes extra features of the message
     pub flags: Option<u64>,
+
+    /// Snapshots of the forwarded messages, in case this message is a forward
+    #[serde(default)]
+    pub message_snapshots: Vec<MessageSnapshot>,
+
+    /// Action rows with the buttons and select menus of the message
+    #[serde(default)]
+    pub components: Vec<Component>,
+
+    /// The poll of the message, if it has one
+    pub poll: Option<Poll>,
+
+    /// The interaction of the message, in case it's an interaction response
+    pub interaction: Option<MessageInteraction>,
+
+    /// Metadata of the interaction of the message, in case it's an interaction response
+    pub interaction_metadata: Option<MessageInteractionMetadata>,
+}
+
+/// The nonce of a message, Discord sends it as it was sent, a string or an integer
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Nonce {
+    Int(i64),
+    Str(String),
 }
 
-#[derive(Debug, Serialize_repr, Deserialize_repr)]
+#[derive(Clone, Debug, Serialize_repr, Deserialize_repr, PartialEq)]
 #[repr(u8)]
 pub enum MessageKind {
     Regular = 0,
@@ -106,10 +164,128 @@ pub enum MessageKind {
     UserPremiumGuildSubT3 = 11,
     ChannelFollowAdd = 12,
     GuildDiscoveryDisqualified = 14,
-    GuildDiscoveryRequalified = 15
+    GuildDiscoveryRequalified = 15,
+    GuildDiscoveryGracePeriodInitialWarning = 16,
+    GuildDiscoveryGracePeriodFinalWarning = 17,
+    ThreadCreated = 18,
+    Reply = 19,
+    ChatInputCommand = 20,
+    ThreadStarterMessage = 21,
+    GuildInviteReminder = 22,
+    ContextMenuCommand = 23,
+    AutoModerationAction = 24,
+}
+
+// A null referenced_message means that it was deleted, it's kept as Some(None) to tell it
+// apart from a missing field, that is None with serde(default)
+fn deserialize_referenced_message<'de, D>(deserializer: D) -> std::result::Result<Option<Option<Box<Message>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+impl Default for MessageKind {
+    fn default() -> Self {
+        MessageKind::Regular
+    }
 }
 
 impl Message {
+    /// Returns true if the author of the message is a bot
+    pub fn is_from_bot(&self) -> bool {
+        self.author.bot
+    }
+
+    /// Returns true if it's a system message, like a member join or a pinned message notification.
+    ///
+    /// It's true for every kind except `Regular`, and also except `Reply`, `ChatInputCommand`
+    /// and `ContextMenuCommand`: these are sent by users and bots like regular messages, so
+    /// ignoring them as system messages would drop replies and command responses
+    pub fn is_system(&self) -> bool {
+        !matches!(
+            self.kind,
+            MessageKind::Regular | MessageKind::Reply | MessageKind::ChatInputCommand | MessageKind::ContextMenuCommand
+        )
+    }
+
+    /// Returns true if the message is the response of an interaction, like a slash command
+    pub fn is_interaction_response(&self) -> bool {
+        self.interaction_metadata.is_some() || self.interaction.is_some()
+    }
+
+    /// Returns the user who invoked the interaction of this message
+    pub fn interaction_user(&self) -> Option<&User> {
+        match (&self.interaction_metadata, &self.interaction) {
+            (Some(metadata), _) => Some(&metadata.user),
+            (None, Some(interaction)) => Some(&interaction.user),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns true if the message was sent by a webhook
+    pub fn is_webhook(&self) -> bool {
+        self.webhook_id.is_some()
+    }
+
+    /// Returns true if the message was edited, Discord sends an `edited_timestamp` for it
+    pub fn was_edited(&self) -> bool {
+        self.edited_timestamp.is_some()
+    }
+
+    /// Returns how long ago the message was sent, it's zero if the timestamp is in the future
+    /// because of the local clock
+    pub fn age(&self) -> Duration {
+        parse_timestamp(&self.timestamp).map_or(Duration::default(), elapsed_since)
+    }
+
+    /// Returns how long ago the message was last edited, or `None` if it wasn't edited
+    pub fn time_since_edit(&self) -> Option<Duration> {
+        let edited_at = parse_timestamp(self.edited_timestamp.as_ref()?)?;
+
+        Some(elapsed_since(edited_at))
+    }
+
+    /// Returns the URL that opens the message in the client, `@me` is used as guild for DM messages
+    pub fn jump_url(&self) -> String {
+        let guild_id = self.guild_id.as_deref().unwrap_or("@me");
+
+        format!("https://discord.com/channels/{}/{}/{}", guild_id, self.channel_id, self.id)
+    }
+
+    /// Returns the roles of the guild mentioned in this message, the ids that aren't
+    /// roles of the guild are skipped
+    pub fn mentioned_roles<'a>(&self, guild: &'a Guild) -> Vec<&'a Role> {
+        self.mention_roles
+            .iter()
+            .filter_map(|id| guild.roles.iter().find(|role| &role.id == id))
+            .collect()
+    }
+
+    /// Returns the channels of the guild mentioned in this message, the channels that
+    /// aren't in the guild are skipped
+    pub fn mentioned_channels_resolved<'a>(&self, guild: &'a Guild) -> Vec<&'a Channel> {
+        self.mentions_channels
+            .iter()
+            .filter_map(|mention| guild.channels.iter().find(|channel| channel.id == mention.id))
+            .collect()
+    }
+
+    /// Returns false when the content of the message was removed by Discord because the bot
+    /// doesn't have the **MESSAGE_CONTENT** intent, the `intents` are the ones of the gateway
+    /// connection. Without the intent, the content of guild messages that don't mention the bot
+    /// is sent empty, so a guild message with no content, attachments, embeds or stickers
+    /// that isn't a system message is considered gated
+    pub fn content_available(&self, intents: &Intents) -> bool {
+        if intents.contains(Intents::MESSAGE_CONTENT) || self.guild_id.is_none() || self.is_system() {
+            return true;
+        }
+
+        !self.content.is_empty()
+            || !self.embed.is_empty()
+            || !self.attachments.is_empty()
+            || !self.sticker_items.is_empty()
+    }
 
     /// Shortcut for [`HttpClient.send_message`]
     ///
@@ -118,6 +294,107 @@ impl Message {
         http.send_message(&self.channel_id, content).await
     }
 
+    /// Reply to this message, the author will not be pinged. Shortcut for [`HttpClient.create_message`]
+    ///
+    /// [`HttpClient.create_message`]: ../../../struct.HttpClient.html#method.create_message
+    pub async fn reply(&self, http: &HttpClient, content: impl Into<String>) -> Result<Message> {
+        let builder = CreateMessage::new().content(content).reply_to(&self.id);
+
+        http.create_message(&self.channel_id, builder).await
+    }
+
+    /// Reply to this message and ping the author. Shortcut for [`HttpClient.create_message`]
+    ///
+    /// [`HttpClient.create_message`]: ../../../struct.HttpClient.html#method.create_message
+    pub async fn reply_ping(&self, http: &HttpClient, content: impl Into<String>) -> Result<Message> {
+        let builder = self.reply_builder(content, true);
+
+        http.create_message(&self.channel_id, builder).await
+    }
+
+    /// Reply to this message without pinging the author, other mentions in the content are
+    /// still parsed. Same as [`reply`]
+    ///
+    /// [`reply`]: #method.reply
+    pub async fn reply_silent(&self, http: &HttpClient, content: impl Into<String>) -> Result<Message> {
+        let builder = self.reply_builder(content, false);
+
+        http.create_message(&self.channel_id, builder).await
+    }
+
+    fn reply_builder(&self, content: impl Into<String>, ping: bool) -> CreateMessage {
+        CreateMessage::new()
+            .content(content)
+            .reply_to(&self.id)
+            .allowed_mentions(AllowedMentions::new().replied_user(ping))
+    }
+
+    /// Fetch this message again, to get the current content and reactions.
+    /// Shortcut for [`HttpClient.get_message`]
+    ///
+    /// [`HttpClient.get_message`]: ../../../struct.HttpClient.html#method.get_message
+    pub async fn refetch(&self, http: &HttpClient) -> Result<Message> {
+        http._get_message(self.refetch_route()).await
+    }
+
+    /// The route to get this message from its channel
+    fn refetch_route(&self) -> Route<()> {
+        Route::get_channel_message(&self.channel_id, &self.id)
+    }
+
+    /// Fetch the channel where this message was sent.
+    /// Shortcut for [`HttpClient.get_channel`]
+    ///
+    /// [`HttpClient.get_channel`]: ../../../struct.HttpClient.html#method.get_channel
+    pub async fn channel(&self, http: &HttpClient) -> Result<Channel> {
+        http.get_channel(&self.channel_id).await
+    }
+
+    /// Fetch the guild where this message was sent, returns `None` for messages sent in a DM.
+    /// Shortcut for [`HttpClient.get_guild`]
+    ///
+    /// [`HttpClient.get_guild`]: ../../../struct.HttpClient.html#method.get_guild
+    pub async fn guild(&self, http: &HttpClient) -> Result<Option<Guild>> {
+        match &self.guild_id {
+            Some(guild_id) => http.get_guild(guild_id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns true if Discord sent that the referenced message was deleted
+    pub fn is_reference_deleted(&self) -> bool {
+        matches!(self.referenced_message, Some(None))
+    }
+
+    /// Returns the message referenced by this one, like the message it replies to. The
+    /// `referenced_message` sent by Discord is used if present, otherwise it's fetched with
+    /// [`HttpClient.get_message`]. Returns `None` if this message has no reference, or the
+    /// referenced message was deleted
+    ///
+    /// [`HttpClient.get_message`]: ../../../struct.HttpClient.html#method.get_message
+    pub async fn resolve_reference(&self, http: &HttpClient) -> Result<Option<Message>> {
+        match &self.referenced_message {
+            Some(Some(message)) => return Ok(Some(message.as_ref().clone())),
+            Some(None) => return Ok(None),
+            None => {}
+        }
+
+        let reference = match &self.message_reference {
+            Some(reference) => reference,
+            None => return Ok(None),
+        };
+
+        let message_id = match &reference.message_id {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        // Replies don't always include the channel, it's the same channel of this message
+        let channel_id = reference.channel_id.as_ref().unwrap_or(&self.channel_id);
+
+        http.get_message(channel_id, message_id).await.map(Some)
+    }
+
     /// Shortcut for [`HttpClient.send_embed`]
     ///
     /// [`HttpClient.send_embed`]: ../../../struct.HttpClient.html#method.send_embed
@@ -132,6 +409,22 @@ impl Message {
         http.add_reaction(&self.channel_id, &self.id, emoji).await
     }
 
+    /// Add the reactions to this message in order, one after another so the reaction rate limit
+    /// is respected. It stops at the first error, returns the number of reactions added and the error
+    ///
+    /// ```rust,ignore
+    /// let (added, result) = msg.add_reactions(&s.http, &["1️⃣", "2️⃣", "3️⃣"]).await;
+    /// ```
+    pub async fn add_reactions(&self, http: &HttpClient, emojis: &[impl AsRef<str>]) -> (usize, Result<()>) {
+        for (added, emoji) in emojis.iter().enumerate() {
+            if let Err(e) = http.add_reaction(&self.channel_id, &self.id, emoji).await {
+                return (added, Err(e));
+            }
+        }
+
+        (emojis.len(), Ok(()))
+    }
+
     /// Shortcut for [`HttpClient.add_reaction`]
     ///
     /// [`HttpClient.add_reaction`]: ../../../struct.HttpClient.html#method.add_reaction
@@ -160,3 +453,406 @@ impl Message {
         http.unpin_message(&self.channel_id, &self.id).await
     }
 }
+
+fn elapsed_since(time: SystemTime) -> Duration {
+    SystemTime::now().duration_since(time).unwrap_or_default()
+}
+
+/// Parses an ISO 8601 timestamp sent by Discord, like `2020-06-12T18:32:15.271000+00:00`
+fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let (date, time) = timestamp.split_at(timestamp.find('T')?);
+    let time = &time[1..];
+
+    let mut date = date.split('-').map(|n| n.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    // The offset starts after the seconds, with Z, + or -
+    let offset_start = time.find(|c| c == 'Z' || c == '+' || c == '-').unwrap_or(time.len());
+    let (clock, offset) = time.split_at(offset_start);
+
+    let (clock, fraction) = match clock.find('.') {
+        Some(i) => (&clock[..i], &clock[i + 1..]),
+        None => (clock, ""),
+    };
+    let mut clock = clock.split(':').map(|n| n.parse::<i64>().ok());
+    let (hours, minutes, seconds) = (clock.next()??, clock.next()??, clock.next()??);
+
+    // Only the milliseconds are kept
+    let millis = match fraction {
+        "" => 0,
+        f => format!("{:0<3}", &f[..f.len().min(3)]).parse::<u64>().ok()?,
+    };
+
+    let offset_seconds = match offset {
+        "" | "Z" => 0,
+        o => {
+            let sign = if o.starts_with('-') { -1 } else { 1 };
+            let mut parts = o[1..].split(':').map(|n| n.parse::<i64>().ok());
+            let (hours, minutes) = (parts.next()??, parts.next().unwrap_or(Some(0))?);
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86400 + hours * 3600 + minutes * 60 + seconds - offset_seconds;
+    if seconds < 0 {
+        return None;
+    }
+
+    Some(UNIX_EPOCH + Duration::from_secs(seconds as u64) + Duration::from_millis(millis))
+}
+
+/// Number of days between 1970-01-01 and the date, from Howard Hinnant's algorithm
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::channel::MessageActivityType;
+
+    use futures::executor::block_on;
+    use serde_json::{json, Value};
+
+    /// A message sent by Discord, with the given fields replaced
+    fn message_with(fields: Value) -> Message {
+        let mut message = json!({
+            "id": "334385199974967042",
+            "channel_id": "290926798999357250",
+            "guild_id": "290926798626357999",
+            "author": {
+                "id": "53908099506183680",
+                "username": "Mason",
+                "discriminator": "9999",
+                "avatar": null
+            },
+            "content": "Supa Hot",
+            "timestamp": "2017-07-11T17:27:07.299000+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "pinned": false,
+            "type": 0
+        });
+        message.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+
+        serde_json::from_value(message).unwrap()
+    }
+
+    #[test]
+    fn missing_referenced_message_is_none() {
+        let message = message_with(json!({}));
+        assert!(message.referenced_message.is_none());
+        assert!(!message.is_reference_deleted());
+    }
+
+    #[test]
+    fn null_referenced_message_is_deleted() {
+        let message = message_with(json!({"type": 19, "referenced_message": null}));
+        assert!(matches!(message.referenced_message, Some(None)));
+        assert!(message.is_reference_deleted());
+    }
+
+    #[test]
+    fn referenced_message_is_parsed() {
+        let referenced = serde_json::to_value(message_with(json!({"id": "1", "content": "ping"}))).unwrap();
+        let message = message_with(json!({"type": 19, "referenced_message": referenced}));
+
+        match &message.referenced_message {
+            Some(Some(referenced)) => assert_eq!(referenced.content, "ping"),
+            other => panic!("expected a referenced message, got {:?}", other),
+        }
+        assert!(!message.is_reference_deleted());
+    }
+
+    #[test]
+    fn missing_referenced_message_is_not_serialized() {
+        let value = serde_json::to_value(message_with(json!({}))).unwrap();
+        assert!(value.get("referenced_message").is_none());
+
+        let value = serde_json::to_value(message_with(json!({"referenced_message": null}))).unwrap();
+        assert_eq!(value.get("referenced_message"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn parse_discord_timestamps() {
+        let expected = UNIX_EPOCH + Duration::from_millis(1_499_794_027_299);
+        assert_eq!(parse_timestamp("2017-07-11T17:27:07.299000+00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2017-07-11T17:27:07.299Z"), Some(expected));
+        assert_eq!(parse_timestamp("2017-07-11T19:27:07.299+02:00"), Some(expected));
+        assert_eq!(parse_timestamp("2017-07-11T14:57:07.299-02:30"), Some(expected));
+
+        let seconds = UNIX_EPOCH + Duration::from_secs(1_499_794_027);
+        assert_eq!(parse_timestamp("2017-07-11T17:27:07+00:00"), Some(seconds));
+        assert_eq!(parse_timestamp("2017-07-11T17:27:07.2+00:00"), Some(seconds + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn parse_leap_day_and_epoch() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00+00:00"), Some(UNIX_EPOCH));
+        assert_eq!(
+            parse_timestamp("2020-02-29T00:00:00+00:00"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_582_934_400))
+        );
+    }
+
+    #[test]
+    fn invalid_timestamps() {
+        for timestamp in ["", "2017-07-11", "2017-07-11T17:27", "yesterday", "1969-12-31T23:59:59+00:00"].iter() {
+            assert_eq!(parse_timestamp(timestamp), None, "{} must be invalid", timestamp);
+        }
+    }
+
+    #[test]
+    fn edited_message() {
+        let message = message_with(json!({}));
+        assert!(!message.was_edited());
+        assert_eq!(message.time_since_edit(), None);
+        assert!(message.age() > Duration::from_secs(0));
+
+        let message = message_with(json!({"edited_timestamp": "2017-07-11T17:30:00.000000+00:00"}));
+        assert!(message.was_edited());
+        assert!(message.time_since_edit().unwrap() < message.age());
+    }
+
+    #[test]
+    fn future_timestamp_has_no_age() {
+        let message = message_with(json!({"timestamp": "2999-01-01T00:00:00+00:00"}));
+        assert_eq!(message.age(), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn content_is_gated_without_intent() {
+        let intents = Intents::GUILD_MESSAGES;
+        let message = message_with(json!({"content": ""}));
+        assert!(!message.content_available(&intents));
+        assert!(message.content_available(&(intents | Intents::MESSAGE_CONTENT)));
+
+        assert!(message_with(json!({})).content_available(&intents));
+    }
+
+    #[test]
+    fn content_is_available_with_attachments_or_stickers() {
+        let intents = Intents::GUILD_MESSAGES;
+        let attachment = json!({
+            "id": "1",
+            "filename": "image.png",
+            "size": 10,
+            "url": "https://cdn.discordapp.com/image.png",
+            "proxy_url": "https://media.discordapp.net/image.png"
+        });
+        let message = message_with(json!({"content": "", "attachments": [attachment]}));
+        assert!(message.content_available(&intents));
+
+        let sticker = json!({"id": "2", "name": "wave", "format_type": 1});
+        let message = message_with(json!({"content": "", "sticker_items": [sticker]}));
+        assert!(message.content_available(&intents));
+    }
+
+    #[test]
+    fn content_of_dm_and_system_messages_is_available() {
+        let intents = Intents::DIRECT_MESSAGES;
+        assert!(message_with(json!({"content": "", "guild_id": null})).content_available(&intents));
+        assert!(message_with(json!({"content": "", "type": 7})).content_available(&intents));
+    }
+
+    #[test]
+    fn system_messages() {
+        assert!(!message_with(json!({})).is_system());
+        assert!(message_with(json!({"type": 7})).is_system());
+        assert!(message_with(json!({"type": 6})).is_system());
+
+        // Sent by users and bots like regular messages
+        for kind in [19, 20, 23].iter() {
+            assert!(!message_with(json!({ "type": kind })).is_system());
+        }
+    }
+
+    #[test]
+    fn bot_and_webhook_messages() {
+        let message = message_with(json!({}));
+        assert!(!message.is_from_bot());
+        assert!(!message.is_webhook());
+
+        let message = message_with(json!({
+            "author": {"id": "1", "username": "hook", "discriminator": "0000", "avatar": null, "bot": true},
+            "webhook_id": "1"
+        }));
+        assert!(message.is_from_bot());
+        assert!(message.is_webhook());
+    }
+
+    #[test]
+    fn string_and_integer_nonces() {
+        assert_eq!(message_with(json!({"nonce": "abc"})).nonce, Some(Nonce::Str("abc".into())));
+        assert_eq!(message_with(json!({"nonce": 1234})).nonce, Some(Nonce::Int(1234)));
+        assert_eq!(message_with(json!({})).nonce, None);
+
+        assert_eq!(serde_json::to_value(Nonce::Int(1234)).unwrap(), json!(1234));
+        assert_eq!(serde_json::to_value(Nonce::Str("abc".into())).unwrap(), json!("abc"));
+    }
+
+    #[test]
+    fn jump_urls() {
+        assert_eq!(
+            message_with(json!({})).jump_url(),
+            "https://discord.com/channels/290926798626357999/290926798999357250/334385199974967042"
+        );
+        assert_eq!(
+            message_with(json!({"guild_id": null})).jump_url(),
+            "https://discord.com/channels/@me/290926798999357250/334385199974967042"
+        );
+    }
+
+    #[test]
+    fn interaction_user_prefers_metadata() {
+        let user = |id: &str| json!({"id": id, "username": "user", "discriminator": "0001", "avatar": null});
+
+        let message = message_with(json!({}));
+        assert!(!message.is_interaction_response());
+        assert!(message.interaction_user().is_none());
+
+        let message = message_with(json!({
+            "interaction": {"id": "10", "type": 2, "name": "ping", "user": user("1")}
+        }));
+        assert!(message.is_interaction_response());
+        assert_eq!(message.interaction_user().unwrap().id, "1");
+
+        let message = message_with(json!({
+            "interaction": {"id": "10", "type": 2, "name": "ping", "user": user("1")},
+            "interaction_metadata": {"id": "10", "type": 2, "user": user("2")}
+        }));
+        assert_eq!(message.interaction_user().unwrap().id, "2");
+    }
+
+    #[test]
+    fn newer_message_kinds() {
+        assert_eq!(message_with(json!({"type": 18})).kind, MessageKind::ThreadCreated);
+        assert_eq!(message_with(json!({"type": 21})).kind, MessageKind::ThreadStarterMessage);
+        assert_eq!(message_with(json!({"type": 24})).kind, MessageKind::AutoModerationAction);
+        assert!(message_with(json!({"type": 24})).is_system());
+    }
+
+    #[test]
+    fn message_activity() {
+        assert!(message_with(json!({})).activity.is_none());
+
+        let message = message_with(json!({"activity": {"type": 3, "party_id": "spotify:80351110224678912"}}));
+        let activity = message.activity.unwrap();
+        assert_eq!(activity.kind, MessageActivityType::Listen);
+        assert_eq!(activity.party_id.as_deref(), Some("spotify:80351110224678912"));
+    }
+
+    #[test]
+    fn resolve_mentions_in_guild() {
+        let guild: Guild = serde_json::from_value(json!({
+            "id": "290926798626357999",
+            "name": "panda",
+            "icon": null,
+            "splash": null,
+            "owner_id": "2",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 1,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "mfa_level": 0,
+            "premium_tier": 0,
+            "preferred_locale": "en-US",
+            "roles": [{
+                "id": "41771983423143936",
+                "name": "mods",
+                "color": 0,
+                "hoist": false,
+                "position": 1,
+                "permissions": 0,
+                "managed": false,
+                "mentionable": true
+            }],
+            "channels": [{"id": "290926798999357250", "type": 0, "name": "general"}]
+        }))
+        .unwrap();
+
+        let message = message_with(json!({
+            "mention_roles": ["41771983423143936", "41771983423143937"],
+            "mention_channels": [
+                {"id": "290926798999357250", "guild_id": "290926798626357999", "type": 0, "name": "general"},
+                {"id": "290926798999357251", "guild_id": "290926798626357998", "type": 0, "name": "other"}
+            ]
+        }));
+
+        let roles = message.mentioned_roles(&guild);
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].name, "mods");
+
+        let channels = message.mentioned_channels_resolved(&guild);
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].id, "290926798999357250");
+    }
+
+    #[test]
+    fn resolve_reference_without_fetching() {
+        let http = HttpClient::new("token");
+
+        let message = message_with(json!({}));
+        assert!(block_on(message.resolve_reference(&http)).unwrap().is_none());
+
+        // Crossposted messages can reference only the channel
+        let message = message_with(json!({"message_reference": {"channel_id": "290926798999357251"}}));
+        assert!(block_on(message.resolve_reference(&http)).unwrap().is_none());
+
+        let message = message_with(json!({
+            "message_reference": {"message_id": "334385199974967041"},
+            "referenced_message": message_with(json!({"id": "334385199974967041"}))
+        }));
+        let reference = block_on(message.resolve_reference(&http)).unwrap().unwrap();
+        assert_eq!(reference.id, "334385199974967041");
+    }
+
+    #[test]
+    fn dm_message_has_no_guild() {
+        let message = message_with(json!({"guild_id": null}));
+
+        assert!(block_on(message.guild(&HttpClient::new("token"))).unwrap().is_none());
+    }
+
+    #[test]
+    fn add_no_reactions() {
+        let message = message_with(json!({}));
+        let (added, result) = block_on(message.add_reactions(&HttpClient::new("token"), &[] as &[&str]));
+
+        assert_eq!(added, 0);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reply_ping_and_silent() {
+        let message = message_with(json!({}));
+
+        for ping in [true, false].iter() {
+            let reply = serde_json::to_value(message.reply_builder("hi", *ping)).unwrap();
+            assert_eq!(reply["message_reference"], json!({"message_id": "334385199974967042"}));
+            assert_eq!(reply["allowed_mentions"]["replied_user"], *ping);
+        }
+    }
+
+    #[test]
+    fn refetch_gets_the_same_message() {
+        let message = message_with(json!({}));
+        let route = message.refetch_route();
+
+        assert_eq!(route.method, isahc::http::Method::GET);
+        assert_eq!(route.uri, "/channels/290926798999357250/messages/334385199974967042");
+        assert_eq!(route.bucket_key, "channels:290926798999357250");
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// Sent with Rich Presence-related chat embeds.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/channel#message-object-message-activity-structure)
+pub struct MessageActivity {
+    /// Type of message activity
+    #[serde(rename = "type")]
+    pub kind: MessageActivityType,
+
+    /// Party ID from a Rich Presence event
+    pub party_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum MessageActivityType {
+    Join = 1,
+    Spectate = 2,
+    Listen = 3,
+    JoinRequest = 5,
+}
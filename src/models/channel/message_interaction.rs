@@ -0,0 +1,34 @@
+use crate::models::{interaction::InteractionKind, user::User};
+
+use serde::{Deserialize, Serialize};
+
+/// Sent with the response message of an interaction, like a slash command response.
+/// Deprecated by Discord in favor of [`MessageInteractionMetadata`], but still sent
+///
+/// [`MessageInteractionMetadata`]: struct.MessageInteractionMetadata.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageInteraction {
+    /// ID of the interaction
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: InteractionKind,
+    /// Name of the application command, with the sub command
+    pub name: String,
+    /// The user who invoked the interaction
+    pub user: User,
+}
+
+/// Metadata of the interaction that created the message
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MessageInteractionMetadata {
+    /// ID of the interaction
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: InteractionKind,
+    /// The user who invoked the interaction
+    pub user: User,
+    /// ID of the original response message, only sent for followup messages
+    pub original_response_message_id: Option<String>,
+    /// ID of the message with the component, only sent for component interactions
+    pub interacted_message_id: Option<String>,
+}
@@ -0,0 +1,95 @@
+use crate::models::user::User;
+
+use super::{Attachment, Embed, MessageKind};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// A snapshot of a forwarded message.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/message#message-snapshot-object)
+pub struct MessageSnapshot {
+    /// Partial data of the forwarded message
+    pub message: SnapshotMessage,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// The subset of [`Message`] fields sent in a [`MessageSnapshot`]
+///
+/// [`Message`]: struct.Message.html
+/// [`MessageSnapshot`]: struct.MessageSnapshot.html
+pub struct SnapshotMessage {
+    /// Type of message, `Regular` if Discord didn't send it
+    #[serde(rename = "type", default)]
+    pub kind: MessageKind,
+
+    /// Contents of the message
+    #[serde(default)]
+    pub content: String,
+
+    /// Any embedded content
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+
+    /// Any attached files
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+
+    /// When this message was sent, as string
+    pub timestamp: String,
+
+    /// When this message was edited (or `None` if never)
+    pub edited_timestamp: Option<String>,
+
+    /// Message flags ORd together, describes extra features of the message
+    pub flags: Option<u64>,
+
+    /// Users specifically mentioned in the message
+    #[serde(default)]
+    pub mentions: Vec<User>,
+
+    /// Roles specifically mentioned in this message
+    #[serde(default)]
+    pub mention_roles: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_snapshot() {
+        let snapshot: MessageSnapshot = serde_json::from_value(json!({
+            "message": {
+                "type": 0,
+                "content": "forwarded",
+                "embeds": [],
+                "attachments": [],
+                "timestamp": "2024-08-01T10:00:00.000000+00:00",
+                "edited_timestamp": null,
+                "flags": 0,
+                "mentions": [],
+                "mention_roles": ["1"]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(snapshot.message.content, "forwarded");
+        assert_eq!(snapshot.message.mention_roles, vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn deserialize_partial_snapshot() {
+        let snapshot: SnapshotMessage = serde_json::from_value(json!({
+            "timestamp": "2024-08-01T10:00:00.000000+00:00",
+            "edited_timestamp": null
+        }))
+        .unwrap();
+
+        assert_eq!(snapshot.kind, MessageKind::Regular);
+        assert!(snapshot.content.is_empty());
+        assert!(snapshot.embeds.is_empty());
+        assert!(snapshot.flags.is_none());
+    }
+}
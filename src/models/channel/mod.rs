@@ -1,23 +1,42 @@
 //! Channel related models
 
 mod attachment;
+mod component;
 mod embed;
+mod followed_channel;
 mod message;
+mod message_activity;
 mod message_application;
+mod message_interaction;
 mod message_reference;
+mod message_snapshot;
 mod overwrite;
+mod poll;
 mod reaction;
+mod thread;
 
 // Re-exports
 pub use attachment::Attachment;
+pub use component::{ButtonStyle, Component, ComponentKind, SelectOption, TextInputStyle};
+pub(crate) use embed::{EmbedDefaults, EMBED_TOTAL_LIMIT};
 pub use embed::{Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage, EmbedProvider, EmbedThumbnail, EmbedVideo};
-pub use message::Message;
+pub use followed_channel::FollowedChannel;
+pub use message::{Message, MessageKind, Nonce};
+pub use message_activity::{MessageActivity, MessageActivityType};
 pub use message_application::MessageApplication;
+pub use message_interaction::{MessageInteraction, MessageInteractionMetadata};
 pub use message_reference::MessageReference;
+pub use message_snapshot::{MessageSnapshot, SnapshotMessage};
 pub use overwrite::Overwrite;
+pub use poll::{Poll, PollAnswer, PollAnswerCount, PollLayoutType, PollMedia, PollResults};
 pub use reaction::Reaction;
+pub use thread::{ActiveThreads, ThreadMember, ThreadMetadata};
 
-use crate::models::user::*;
+use crate::{
+    error::{PandaError, Result},
+    http::HttpClient,
+    models::{gateway::events::ChannelUpdate, user::*},
+};
 
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
@@ -49,12 +68,26 @@ pub struct Channel {
 
     pub parent_id: Option<String>,
     pub last_pin_timestamp: Option<String>,
+
+    // For threads
+    pub thread_metadata: Option<ThreadMetadata>,
+    pub message_count: Option<u64>,
+    pub member_count: Option<u64>,
+}
+
+/// A channel with only the id, name and kind, like the one sent with an invite
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PartialChannel {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: ChannelKind,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MentionChannel {
-    id: String,
-    guild_id: String,
+    pub id: String,
+    pub guild_id: String,
     #[serde(rename = "type")]
     kind: u64, // TODO
     name: String,
@@ -70,4 +103,100 @@ pub enum ChannelKind {
     GuildCategory = 4,
     GuildNews = 5,
     GuildStore = 6,
+    GuildNewsThread = 10,
+    GuildPublicThread = 11,
+    GuildPrivateThread = 12,
+}
+
+impl ChannelKind {
+    /// Returns true if this kind is a thread
+    pub fn is_thread(&self) -> bool {
+        matches!(self, Self::GuildNewsThread | Self::GuildPublicThread | Self::GuildPrivateThread)
+    }
+
+    /// Returns true if messages can be sent to this kind of channel
+    pub fn is_messageable(&self) -> bool {
+        match self {
+            Self::GuildText
+            | Self::DM
+            | Self::GroupDM
+            | Self::GuildNews
+            | Self::GuildNewsThread
+            | Self::GuildPublicThread
+            | Self::GuildPrivateThread => true,
+            Self::GuildVoice | Self::GuildCategory | Self::GuildStore => false,
+        }
+    }
+}
+
+impl Channel {
+    /// Shortcut for [`HttpClient.send_message`], it returns [`PandaError::NotTextChannel`]
+    /// without making the request if messages can't be sent to this channel
+    ///
+    /// [`HttpClient.send_message`]: ../../../struct.HttpClient.html#method.send_message
+    /// [`PandaError::NotTextChannel`]: ../../../enum.PandaError.html#variant.NotTextChannel
+    pub async fn send(&self, http: &HttpClient, content: impl AsRef<str>) -> Result<Message> {
+        if !self.kind.is_messageable() {
+            return Err(PandaError::NotTextChannel);
+        }
+
+        http.send_message(&self.id, content).await
+    }
+
+    /// Apply a [`ChannelUpdate`] to this channel, it's ignored if the updated channel is
+    /// a different one
+    ///
+    /// [`ChannelUpdate`]: ../gateway/events/struct.ChannelUpdate.html
+    pub fn apply_update(&mut self, update: &ChannelUpdate) {
+        if self.id == update.id {
+            *self = update.0.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    fn channel(fields: Value) -> Channel {
+        let mut channel = json!({"id": "1", "type": 0, "guild_id": "2", "name": "general"});
+        channel.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+
+        serde_json::from_value(channel).unwrap()
+    }
+
+    #[test]
+    fn messageable_channels() {
+        assert!(ChannelKind::GuildText.is_messageable());
+        assert!(ChannelKind::DM.is_messageable());
+        assert!(ChannelKind::GuildPublicThread.is_messageable());
+        assert!(!ChannelKind::GuildVoice.is_messageable());
+        assert!(!ChannelKind::GuildCategory.is_messageable());
+    }
+
+    #[test]
+    fn reject_sends_to_non_messageable_channels() {
+        let http = HttpClient::new("token");
+        let category = channel(json!({"type": 4}));
+
+        let result = futures::executor::block_on(category.send(&http, "hello"));
+        assert!(matches!(result, Err(PandaError::NotTextChannel)));
+    }
+
+    #[test]
+    fn apply_channel_update() {
+        let mut general = channel(json!({"topic": "old"}));
+
+        let update = ChannelUpdate(channel(json!({"name": "renamed", "topic": null})));
+        general.apply_update(&update);
+        assert_eq!(general.name.as_deref(), Some("renamed"));
+        assert!(general.topic.is_none());
+
+        // Updates of other channels are ignored
+        let update = ChannelUpdate(channel(json!({"id": "9", "name": "other"})));
+        general.apply_update(&update);
+        assert_eq!(general.name.as_deref(), Some("renamed"));
+    }
 }
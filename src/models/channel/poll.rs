@@ -0,0 +1,110 @@
+use crate::models::emoji::Emoji;
+
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// A poll attached to a message.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/poll#poll-object)
+pub struct Poll {
+    pub question: PollMedia,
+    pub answers: Vec<PollAnswer>,
+    /// When the poll ends, as string. `None` for polls without expiry
+    pub expiry: Option<String>,
+    pub allow_multiselect: bool,
+    pub layout_type: PollLayoutType,
+    /// Not sent when the results were not calculated yet
+    pub results: Option<PollResults>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The text and emoji of a question or an answer
+pub struct PollMedia {
+    pub text: Option<String>,
+    pub emoji: Option<Emoji>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PollAnswer {
+    pub answer_id: u64,
+    pub poll_media: PollMedia,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The votes of each answer, they may not be accurate until the poll is finalized
+pub struct PollResults {
+    pub is_finalized: bool,
+    /// Answers without votes are not included
+    pub answer_counts: Vec<PollAnswerCount>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PollAnswerCount {
+    /// The `answer_id` of the answer
+    pub id: u64,
+    pub count: u64,
+    /// If the bot voted for this answer
+    pub me_voted: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum PollLayoutType {
+    Default = 1,
+}
+
+impl Default for PollLayoutType {
+    fn default() -> Self {
+        PollLayoutType::Default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_poll() {
+        let poll: Poll = serde_json::from_value(json!({
+            "question": {"text": "Best pet?"},
+            "answers": [
+                {"answer_id": 1, "poll_media": {"text": "Cat", "emoji": {"id": null, "name": "🐱"}}},
+                {"answer_id": 2, "poll_media": {"text": "Dog"}}
+            ],
+            "expiry": "2024-05-01T00:00:00.000000+00:00",
+            "allow_multiselect": false,
+            "layout_type": 1,
+            "results": {
+                "is_finalized": false,
+                "answer_counts": [{"id": 1, "count": 3, "me_voted": true}]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(poll.question.text.as_deref(), Some("Best pet?"));
+        assert_eq!(poll.answers.len(), 2);
+        assert_eq!(poll.answers[0].poll_media.emoji.as_ref().unwrap().name.as_deref(), Some("🐱"));
+        assert!(poll.answers[1].poll_media.emoji.is_none());
+        assert_eq!(poll.layout_type, PollLayoutType::Default);
+
+        let results = poll.results.unwrap();
+        assert_eq!(results.answer_counts[0], PollAnswerCount { id: 1, count: 3, me_voted: true });
+    }
+
+    #[test]
+    fn deserialize_poll_without_results() {
+        let poll: Poll = serde_json::from_value(json!({
+            "question": {"text": "?"},
+            "answers": [],
+            "expiry": null,
+            "allow_multiselect": true,
+            "layout_type": 1
+        }))
+        .unwrap();
+
+        assert!(poll.expiry.is_none());
+        assert!(poll.results.is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::models::Emoji;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Reaction {
     pub count: u64,
     pub me: bool,
@@ -0,0 +1,74 @@
+use super::Channel;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// Thread specific fields of a thread channel.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/channel#thread-metadata-object)
+pub struct ThreadMetadata {
+    pub archived: bool,
+    /// Minutes of inactivity until the thread is archived: 60, 1440, 4320 or 10080
+    pub auto_archive_duration: u64,
+    /// When the archive status was last changed, as string
+    pub archive_timestamp: String,
+    /// A locked thread can only be unarchived by members with the **MANAGE_THREADS** permission
+    #[serde(default)]
+    pub locked: bool,
+    /// Only for private threads, if non moderators can add other non moderators
+    pub invitable: Option<bool>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// A user that joined a thread.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/channel#thread-member-object)
+pub struct ThreadMember {
+    /// ID of the thread, not sent in GUILD_CREATE
+    pub id: Option<String>,
+    /// ID of the user, not sent in GUILD_CREATE
+    pub user_id: Option<String>,
+    /// When the user joined the thread, as string
+    pub join_timestamp: String,
+    /// User thread settings, used for notifications
+    pub flags: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The active threads of a guild, and the thread members of the bot
+pub struct ActiveThreads {
+    pub threads: Vec<Channel>,
+    /// A thread member for each thread the bot has joined
+    pub members: Vec<ThreadMember>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_thread_metadata() {
+        let metadata: ThreadMetadata = serde_json::from_value(json!({
+            "archived": false,
+            "auto_archive_duration": 1440,
+            "archive_timestamp": "2021-04-12T23:40:39.855793+00:00"
+        }))
+        .unwrap();
+
+        assert!(!metadata.locked);
+        assert_eq!(metadata.auto_archive_duration, 1440);
+        assert_eq!(metadata.invitable, None);
+    }
+
+    #[test]
+    fn deserialize_thread_member_of_guild_create() {
+        let member: ThreadMember = serde_json::from_value(json!({
+            "join_timestamp": "2021-04-12T23:40:39.855793+00:00",
+            "flags": 1
+        }))
+        .unwrap();
+
+        assert_eq!(member.id, None);
+        assert_eq!(member.user_id, None);
+        assert_eq!(member.flags, 1);
+    }
+}
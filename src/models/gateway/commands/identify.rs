@@ -1,4 +1,4 @@
-use crate::models::gateway::payload::Opcode;
+use crate::models::gateway::{payload::Opcode, Intents};
 use serde::Serialize;
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -17,12 +17,14 @@ pub(crate) struct IdentifyContent {
     pub(crate) shard: Option<[u64; 2]>,
     pub(crate) presence: Option<()>,
     pub(crate) guild_subscriptions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) intents: Option<Intents>,
 }
 
 #[derive(Debug, Serialize, PartialEq)]
 pub(crate) struct IdentifyProperties {
     #[serde(rename = "$os")]
-    pub(crate) os: &'static str,
+    pub(crate) os: String,
 
     #[serde(rename = "$browser")]
     pub(crate) browser: String,
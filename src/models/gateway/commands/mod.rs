@@ -1,21 +1,27 @@
 // commands
 mod identify;
-use identify::{Identify, IdentifyContent, IdentifyProperties};
+use identify::{Identify, IdentifyContent};
+pub(crate) use identify::IdentifyProperties;
 
 mod heartbeat;
 use heartbeat::Heartbeat;
 
+mod request_guild_members;
+use request_guild_members::{RequestGuildMembers, RequestGuildMembersContent};
+
 mod resume;
 use resume::{Resume, ResumeContent};
 
 mod status_update;
 use status_update::StatusUpdatePayload;
 
-use super::payload::{Opcode, Payload};
+use super::{
+    payload::{Opcode, Payload},
+    Intents,
+};
 use crate::models::user;
 
 use async_tungstenite::tungstenite::Message as TungsteniteMessage;
-use std::env::consts::OS;
 
 #[allow(dead_code)] // TODO: Add support for Voice
 #[derive(Debug, PartialEq)]
@@ -23,7 +29,7 @@ pub(crate) enum Command {
     Identify(Identify),
     Resume(Resume),
     Heartbeat(Heartbeat),
-    RequestGuildMembers(Payload),
+    RequestGuildMembers(RequestGuildMembers),
     UpdateVoiceState(Payload),
     StatusUpdate(StatusUpdatePayload),
     Close,
@@ -48,6 +54,10 @@ impl Command {
                 let cmd_str = serde_json::to_string(&r).unwrap();
                 TungsteniteMessage::Text(cmd_str)
             }
+            Self::RequestGuildMembers(r) => {
+                let cmd_str = serde_json::to_string(&r).unwrap();
+                TungsteniteMessage::Text(cmd_str)
+            }
             Self::StatusUpdate(p) => {
                 let cmd_str = serde_json::to_string(&p).unwrap();
                 TungsteniteMessage::Text(cmd_str)
@@ -62,13 +72,9 @@ impl Command {
         large_threshold: u8,
         guild_subscriptions: bool,
         shard: [u64; 2],
+        identify_properties: IdentifyProperties,
+        intents: Option<Intents>,
     ) -> Command {
-        let identify_properties = IdentifyProperties {
-            os: OS,
-            browser: "discord".into(),
-            device: "discord".into(),
-        };
-
         let identify = Identify {
             op: Opcode::Identify,
             d: IdentifyContent {
@@ -79,6 +85,7 @@ impl Command {
                 shard: Some(shard),
                 presence: None,
                 guild_subscriptions: Some(guild_subscriptions),
+                intents,
             },
             s: None,
         };
@@ -106,6 +113,20 @@ impl Command {
         Command::Resume(resume)
     }
 
+    /// Returns a RequestGuildMembers command that requests all the members of the guild
+    pub(crate) fn new_request_guild_members(guild_id: impl Into<String>) -> Command {
+        let content = RequestGuildMembersContent {
+            guild_id: guild_id.into(),
+            query: String::new(),
+            limit: 0,
+        };
+
+        Command::RequestGuildMembers(RequestGuildMembers {
+            op: Opcode::RequestGuildMember,
+            d: content,
+        })
+    }
+
     pub(crate) fn new_status_update(status_update: user::StatusUpdate) -> Command {
         let status_update = StatusUpdatePayload {
             op: Opcode::StatusUpdate,
@@ -115,3 +136,43 @@ impl Command {
         Command::StatusUpdate(status_update)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    fn to_json(command: Command, sequence: Option<u64>) -> Value {
+        match command.to_tungstenite_message(sequence) {
+            TungsteniteMessage::Text(text) => serde_json::from_str(&text).unwrap(),
+            message => panic!("Expected a text message, got {:?}", message),
+        }
+    }
+
+    #[test]
+    fn identify_properties() {
+        let properties = IdentifyProperties {
+            os: "linux".into(),
+            browser: "my bot".into(),
+            device: "my bot".into(),
+        };
+        let identify = to_json(Command::new_identify("token", 50, true, [0, 1], properties, None), Some(4));
+
+        assert_eq!(identify["op"], 2);
+        assert_eq!(identify["s"], 4);
+        assert_eq!(
+            identify["d"]["properties"],
+            json!({"$os": "linux", "$browser": "my bot", "$device": "my bot"})
+        );
+        assert!(identify["d"].get("intents").is_none());
+    }
+
+    #[test]
+    fn request_all_guild_members() {
+        assert_eq!(
+            to_json(Command::new_request_guild_members("290926798626357999"), Some(4)),
+            json!({"op": 8, "d": {"guild_id": "290926798626357999", "query": "", "limit": 0}})
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+use super::Opcode;
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct RequestGuildMembers {
+    pub(crate) op: Opcode,
+    pub(crate) d: RequestGuildMembersContent,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub(crate) struct RequestGuildMembersContent {
+    pub(crate) guild_id: String,
+    // An empty query and 0 limit requests all the members
+    pub(crate) query: String,
+    pub(crate) limit: u64,
+}
@@ -0,0 +1,21 @@
+use crate::models::guild::{AutoModerationAction, AutoModerationTriggerType};
+use serde::{Deserialize, Serialize};
+
+/// Sent when a rule is triggered and an action is executed
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AutoModerationActionExecution {
+    pub guild_id: String,
+    pub action: AutoModerationAction,
+    pub rule_id: String,
+    pub rule_trigger_type: AutoModerationTriggerType,
+    pub user_id: String,
+    pub channel_id: Option<String>,
+    /// It's None if the message was blocked
+    pub message_id: Option<String>,
+    pub alert_system_message_id: Option<String>,
+    /// Empty without the MESSAGE_CONTENT intent
+    #[serde(default)]
+    pub content: String,
+    pub matched_keyword: Option<String>,
+    pub matched_content: Option<String>,
+}
@@ -0,0 +1,15 @@
+use crate::models::guild::AutoModerationRule;
+use serde::{Deserialize, Serialize};
+
+use std::ops::Deref;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AutoModerationRuleUpdate(pub AutoModerationRule);
+
+impl Deref for AutoModerationRuleUpdate {
+    type Target = AutoModerationRule;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
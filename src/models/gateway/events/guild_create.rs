@@ -1,15 +1,100 @@
 use crate::models::guild::*;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use std::ops::Deref;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct GuildCreate(pub Guild);
+#[derive(Clone, Debug)]
+pub struct GuildCreate {
+    pub guild: Guild,
+    /// If the bot joined the guild, or the guild was unavailable. It's set by the client,
+    /// using the unavailable guilds of READY and GUILD_DELETE
+    pub kind: GuildCreateKind,
+}
+
+// Implemented by hand instead of with flatten, which buffers the whole guild in a map first
+impl<'de> Deserialize<'de> for GuildCreate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let guild = Guild::deserialize(deserializer)?;
+
+        Ok(GuildCreate {
+            guild,
+            kind: GuildCreateKind::default(),
+        })
+    }
+}
+
+impl Serialize for GuildCreate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.guild.serialize(serializer)
+    }
+}
+
+/// Why a [`GuildCreate`] was received
+///
+/// [`GuildCreate`]: struct.GuildCreate.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuildCreateKind {
+    /// The bot was added to a new guild
+    Joined,
+    /// The guild was unavailable, because the bot just connected or there was an outage
+    Available,
+}
+
+impl Default for GuildCreateKind {
+    fn default() -> Self {
+        GuildCreateKind::Joined
+    }
+}
+
+impl GuildCreate {
+    /// Returns true if the bot was added to a new guild, and false if the guild was unavailable
+    pub fn is_new_join(&self) -> bool {
+        self.kind == GuildCreateKind::Joined
+    }
+}
 
 impl Deref for GuildCreate {
     type Target = Guild;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.guild
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn guild_create_is_the_guild() {
+        let guild = json!({
+            "id": "1",
+            "name": "panda",
+            "icon": null,
+            "splash": null,
+            "owner_id": "2",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 1,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "mfa_level": 0,
+            "premium_tier": 0,
+            "preferred_locale": "en-US"
+        });
+        let mut event: GuildCreate = serde_json::from_value(guild).unwrap();
+
+        assert_eq!(event.name, "panda");
+        assert!(event.is_new_join());
+
+        event.kind = GuildCreateKind::Available;
+        assert!(!event.is_new_join());
+
+        // The kind is not part of the guild object
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value, serde_json::to_value(&event.guild).unwrap());
+        assert!(value.get("kind").is_none());
     }
 }
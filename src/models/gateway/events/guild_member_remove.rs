@@ -1,8 +1,39 @@
-use crate::models::user::User;
+use crate::models::guild::PartialGuildMember;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct GuildMemberRemove {
-    pub guild_id: String,
-    pub user: User,
+use std::ops::Deref;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GuildMemberRemove(pub PartialGuildMember);
+
+impl Deref for GuildMemberRemove {
+    type Target = PartialGuildMember;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_member_remove() {
+        let event: GuildMemberRemove = serde_json::from_value(json!({
+            "guild_id": "290926798626357999",
+            "user": {
+                "id": "80351110224678912",
+                "username": "Nelly",
+                "discriminator": "1337",
+                "avatar": null
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(event.guild_id, "290926798626357999");
+        assert_eq!(event.user.id, "80351110224678912");
+    }
 }
@@ -1,10 +1,18 @@
-use crate::models::user::User;
+use crate::models::{guild::GuildMemberFlags, user::User};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GuildMemberUpdate {
     pub guild_id: String,
-    pub roles: Vec<String>,
+    #[serde(default)]
+    pub roles: Option<Vec<String>>,
     pub user: User,
-    pub nick: String,
+    /// Discord sends it with every update, `None` means the nickname was removed
+    pub nick: Option<String>,
+    /// Discord sends it with every update, `None` means the member is not boosting the guild
+    pub premium_since: Option<String>,
+    pub joined_at: Option<String>,
+    pub deaf: Option<bool>,
+    pub mute: Option<bool>,
+    pub flags: Option<GuildMemberFlags>,
 }
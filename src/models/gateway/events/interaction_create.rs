@@ -0,0 +1,15 @@
+use crate::models::interaction::Interaction;
+use serde::{Deserialize, Serialize};
+
+use std::ops::Deref;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InteractionCreate(pub Interaction);
+
+impl Deref for InteractionCreate {
+    type Target = Interaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -1,3 +1,9 @@
+// Auto moderation
+mod auto_moderation_action_execution;
+mod auto_moderation_rule_create;
+mod auto_moderation_rule_delete;
+mod auto_moderation_rule_update;
+
 // Channel
 mod channel_create;
 mod channel_delete;
@@ -19,6 +25,9 @@ mod guild_role_delete;
 mod guild_role_update;
 mod guild_update;
 
+// Interaction
+mod interaction_create;
+
 // Message
 mod message_create;
 mod message_delete;
@@ -37,8 +46,18 @@ mod voice_server_update;
 mod voice_state_update;
 
 mod ready;
+mod reconnected;
+
+#[cfg(feature = "raw-events")]
+mod raw_event;
 
 // Re-exports
+// AUTO MODERATION
+pub use auto_moderation_action_execution::AutoModerationActionExecution;
+pub use auto_moderation_rule_create::AutoModerationRuleCreate;
+pub use auto_moderation_rule_delete::AutoModerationRuleDelete;
+pub use auto_moderation_rule_update::AutoModerationRuleUpdate;
+
 // CHANNEL
 pub use channel_create::ChannelCreate;
 pub use channel_delete::ChannelDelete;
@@ -48,7 +67,7 @@ pub use channel_update::ChannelUpdate;
 // GUILDS
 pub use guild_ban::GuildBanAdd;
 pub use guild_ban::GuildBanRemove;
-pub use guild_create::GuildCreate;
+pub use guild_create::{GuildCreate, GuildCreateKind};
 pub use guild_delete::GuildDelete;
 pub use guild_emojis_update::GuildEmojisUpdate;
 pub use guild_integrations_update::GuildIntegrationsUpdate;
@@ -61,6 +80,9 @@ pub use guild_role_delete::GuildRoleDelete;
 pub use guild_role_update::GuildRoleUpdate;
 pub use guild_update::GuildUpdate;
 
+// INTERACTION
+pub use interaction_create::InteractionCreate;
+
 // MESSAGE
 pub use message_create::MessageCreate;
 pub use message_delete::MessageDelete;
@@ -82,17 +104,23 @@ pub use voice_state_update::VoiceStateUpdate;
 
 // READY
 pub use ready::Ready;
+pub use reconnected::Reconnected;
+
+// RAW
+#[cfg(feature = "raw-events")]
+pub use raw_event::RawEvent;
 
 // crate
 use super::payload::{Opcode, Payload};
 use crate::error::{PandaError, Result};
 
+use serde::Deserializer;
 use serde_json::Value;
 use std::convert::TryFrom;
 
 macro_rules! parse_dispatch {
     ($event: expr, $name: expr) => {
-        serde_json::from_value($event).map_err(|_| PandaError::InvalidPayloadFormat($name))
+        serde::Deserialize::deserialize($event).map_err(|_| PandaError::InvalidPayloadFormat($name))
     };
 }
 
@@ -100,18 +128,35 @@ macro_rules! parse_dispatch {
 pub(crate) enum Event {
     // Discord events
     Dispatch(DispatchEvent), // op: 0
+    HeartbeatRequest,        // op: 1
+    #[cfg(feature = "raw-events")]
+    RawDispatch(Option<DispatchEvent>, RawEvent), // op: 0, None if the event couldn't be parsed
     Reconnect,               // op: 7
+    Resumed(u64),            // op: 0, RESUMED with the number of replayed events
     InvalidSession(bool),    // op: 9
     Hello(u64),              // op: 10
     HeartbeatACK,            // op: 11
     Close(PandaError),
 }
 
+/// A dispatch event received from the gateway, each event can be converted to its type with
+/// `TryFrom`:
+///
+/// ```rust,ignore
+/// let msg = MessageCreate::try_from(event)?;
+/// ```
 #[derive(Debug)]
-pub(crate) enum DispatchEvent {
+pub enum DispatchEvent {
     Ready(Ready),
     Resumed,
     Reconnect,
+
+    // auto moderation
+    AutoModerationRuleCreate(AutoModerationRuleCreate),
+    AutoModerationRuleUpdate(AutoModerationRuleUpdate),
+    AutoModerationRuleDelete(AutoModerationRuleDelete),
+    AutoModerationActionExecution(AutoModerationActionExecution),
+
     ChannelCreate(ChannelCreate),
     ChannelUpdate(ChannelUpdate),
     ChannelDelete(ChannelDelete),
@@ -133,6 +178,9 @@ pub(crate) enum DispatchEvent {
     GuildRoleUpdate(GuildRoleUpdate),
     GuildRoleDelete(GuildRoleDelete),
 
+    // interaction
+    InteractionCreate(InteractionCreate),
+
     // message
     MessageCreate(MessageCreate),
     MessageUpdate(MessageUpdate),
@@ -153,12 +201,42 @@ pub(crate) enum DispatchEvent {
     VoiceServerUpdate(VoiceServerUpdate),
 }
 
+impl DispatchEvent {
+    /// Parse the data of a dispatch event with its name, like `MESSAGE_CREATE`
+    pub fn parse(name: &str, data: Value) -> Result<DispatchEvent> {
+        parse_dispatch_event(name, data)
+    }
+}
+
+/// Parses the raw event to the dispatch event
+#[cfg(feature = "raw-events")]
+impl TryFrom<RawEvent> for DispatchEvent {
+    type Error = PandaError;
+
+    fn try_from(raw: RawEvent) -> Result<Self> {
+        parse_dispatch_event(&raw.name, raw.data)
+    }
+}
+
+impl Event {
+    /// Returns true if it's the RESUMED dispatch event
+    pub(crate) fn is_resumed(&self) -> bool {
+        match self {
+            Event::Dispatch(DispatchEvent::Resumed) => true,
+            #[cfg(feature = "raw-events")]
+            Event::RawDispatch(Some(DispatchEvent::Resumed), _) => true,
+            _ => false,
+        }
+    }
+}
+
 impl TryFrom<Payload> for Event {
     type Error = PandaError;
 
     fn try_from(p: Payload) -> Result<Event> {
         match p.op {
-            Opcode::Dispatch => Ok(Event::Dispatch(handle_dispatch(p)?)),
+            Opcode::Dispatch => handle_dispatch(p),
+            Opcode::Heartbeat => Ok(Event::HeartbeatRequest),
             Opcode::Reconnect => Ok(Event::Reconnect),
             Opcode::InvalidSessionData => {
                 let d =
@@ -186,18 +264,59 @@ impl TryFrom<Payload> for Event {
     }
 }
 
-///
-fn handle_dispatch(p: Payload) -> Result<DispatchEvent> {
+/// Parse the dispatch event of the payload
+#[cfg(not(feature = "raw-events"))]
+fn handle_dispatch(p: Payload) -> Result<Event> {
     let d = p.d.ok_or_else(|| PandaError::InvalidPayloadFormat("D"))?;
     let t = p.t.ok_or_else(|| PandaError::InvalidPayloadFormat("T"))?;
 
-    match t.as_str() {
+    Ok(Event::Dispatch(parse_dispatch_event(&t, d)?))
+}
+
+/// Same as above, but the event is parsed from a reference so the json is kept with it,
+/// the json of the events that can't be parsed is sent too
+#[cfg(feature = "raw-events")]
+fn handle_dispatch(p: Payload) -> Result<Event> {
+    let data = p.d.ok_or_else(|| PandaError::InvalidPayloadFormat("D"))?;
+    let name = p.t.ok_or_else(|| PandaError::InvalidPayloadFormat("T"))?;
+
+    let event = match parse_dispatch_event(&name, &data) {
+        Ok(event) => Some(event),
+        Err(e) => {
+            log::warn!("Couldn't parse the {} event, only the raw event is sent: {}", name, e);
+            None
+        }
+    };
+
+    Ok(Event::RawDispatch(event, RawEvent { name, data }))
+}
+
+/// Parse the data of a dispatch event with the given name, from a `Value` or a `&Value`
+fn parse_dispatch_event<'de, D: Deserializer<'de>>(t: &str, d: D) -> Result<DispatchEvent> {
+    match t {
         "READY" => {
             let event = parse_dispatch!(d, "READY")?;
             Ok(DispatchEvent::Ready(event))
         }
         "RESUMED" => Ok(DispatchEvent::Resumed),
         "RECONNECT" => Ok(DispatchEvent::Reconnect),
+        // Auto moderation
+        "AUTO_MODERATION_RULE_CREATE" => {
+            let event = parse_dispatch!(d, "AUTO_MODERATION_RULE_CREATE")?;
+            Ok(DispatchEvent::AutoModerationRuleCreate(event))
+        }
+        "AUTO_MODERATION_RULE_UPDATE" => {
+            let event = parse_dispatch!(d, "AUTO_MODERATION_RULE_UPDATE")?;
+            Ok(DispatchEvent::AutoModerationRuleUpdate(event))
+        }
+        "AUTO_MODERATION_RULE_DELETE" => {
+            let event = parse_dispatch!(d, "AUTO_MODERATION_RULE_DELETE")?;
+            Ok(DispatchEvent::AutoModerationRuleDelete(event))
+        }
+        "AUTO_MODERATION_ACTION_EXECUTION" => {
+            let event = parse_dispatch!(d, "AUTO_MODERATION_ACTION_EXECUTION")?;
+            Ok(DispatchEvent::AutoModerationActionExecution(event))
+        }
         // Channel
         "CHANNEL_CREATE" => {
             let event = parse_dispatch!(d, "CHANNEL_CREATE")?;
@@ -257,8 +376,8 @@ fn handle_dispatch(p: Payload) -> Result<DispatchEvent> {
             let event = parse_dispatch!(d, "GUILD_MEMBER_REMOVE")?;
             Ok(DispatchEvent::GuildMemberRemove(event))
         }
-        "GUILD_MEMBER_CHUNK" => {
-            let event = parse_dispatch!(d, "GUILD_MEMBER_CHUNK")?;
+        "GUILD_MEMBERS_CHUNK" => {
+            let event = parse_dispatch!(d, "GUILD_MEMBERS_CHUNK")?;
             Ok(DispatchEvent::GuildMembersChunk(event))
         }
         "GUILD_ROLE_CREATE" => {
@@ -274,6 +393,12 @@ fn handle_dispatch(p: Payload) -> Result<DispatchEvent> {
             Ok(DispatchEvent::GuildRoleDelete(event))
         }
 
+        // Interaction
+        "INTERACTION_CREATE" => {
+            let event = parse_dispatch!(d, "INTERACTION_CREATE")?;
+            Ok(DispatchEvent::InteractionCreate(event))
+        }
+
         // Message
         "MESSAGE_CREATE" => {
             let event = parse_dispatch!(d, "MESSAGE_CREATE")?;
@@ -334,3 +459,196 @@ fn handle_dispatch(p: Payload) -> Result<DispatchEvent> {
         _ => Err(PandaError::InvalidPayloadFormat("Unkown D event")),
     }
 }
+
+/// Implements `TryFrom<DispatchEvent>` for the events, so a [`DispatchEvent`] can be converted
+/// to the event type. It fails with `UnexpectedPayloadReceived` if it's another event
+macro_rules! impl_try_from_dispatch_event {
+    ($($event: ident),*) => {
+        $(
+            impl TryFrom<DispatchEvent> for $event {
+                type Error = PandaError;
+
+                fn try_from(event: DispatchEvent) -> Result<Self> {
+                    match event {
+                        DispatchEvent::$event(event) => Ok(event),
+                        _ => Err(PandaError::UnexpectedPayloadReceived),
+                    }
+                }
+            }
+
+            /// Parses the raw event, and converts it to the event type
+            #[cfg(feature = "raw-events")]
+            impl TryFrom<RawEvent> for $event {
+                type Error = PandaError;
+
+                fn try_from(raw: RawEvent) -> Result<Self> {
+                    $event::try_from(DispatchEvent::try_from(raw)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_dispatch_event!(
+    Ready,
+    AutoModerationRuleCreate,
+    AutoModerationRuleUpdate,
+    AutoModerationRuleDelete,
+    AutoModerationActionExecution,
+    ChannelCreate,
+    ChannelUpdate,
+    ChannelDelete,
+    ChannelPinsUpdate,
+    GuildCreate,
+    GuildUpdate,
+    GuildDelete,
+    GuildBanAdd,
+    GuildBanRemove,
+    GuildEmojisUpdate,
+    GuildIntegrationsUpdate,
+    GuildMemberAdd,
+    GuildMemberUpdate,
+    GuildMemberRemove,
+    GuildMembersChunk,
+    GuildRoleCreate,
+    GuildRoleUpdate,
+    GuildRoleDelete,
+    InteractionCreate,
+    MessageCreate,
+    MessageUpdate,
+    MessageDelete,
+    MessageDeleteBulk,
+    MessageReactionAdd,
+    MessageReactionRemove,
+    MessageReactionRemoveAll,
+    MessageReactionRemoveEmoji,
+    PresenceUpdate,
+    TypingStart,
+    UserUpdate,
+    VoiceStateUpdate,
+    VoiceServerUpdate
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn message() -> Value {
+        json!({
+            "id": "1",
+            "channel_id": "2",
+            "author": {"id": "3", "username": "user", "discriminator": "0001", "avatar": null},
+            "content": "hello",
+            "timestamp": "2020-06-12T18:32:15.271000+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "pinned": false
+        })
+    }
+
+    #[test]
+    fn convert_dispatch_event() {
+        let event = DispatchEvent::parse("MESSAGE_CREATE", message()).unwrap();
+        let msg = MessageCreate::try_from(event).unwrap();
+
+        assert_eq!(msg.content, "hello");
+    }
+
+    #[test]
+    fn convert_mismatched_dispatch_event() {
+        let event = DispatchEvent::parse("MESSAGE_CREATE", message()).unwrap();
+
+        assert!(matches!(
+            MessageUpdate::try_from(event),
+            Err(PandaError::UnexpectedPayloadReceived)
+        ));
+        assert!(matches!(
+            Ready::try_from(DispatchEvent::Resumed),
+            Err(PandaError::UnexpectedPayloadReceived)
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_dispatch_events() {
+        assert!(matches!(
+            DispatchEvent::parse("MESSAGE_CREATE", json!({"id": "1"})),
+            Err(PandaError::InvalidPayloadFormat("MESSAGE_CREATE"))
+        ));
+        assert!(DispatchEvent::parse("UNKNOWN_EVENT", json!({})).is_err());
+    }
+
+    #[cfg(feature = "raw-events")]
+    fn dispatch(name: &str, data: Value) -> Payload {
+        Payload {
+            op: Opcode::Dispatch,
+            d: Some(data),
+            s: Some(1),
+            t: Some(name.into()),
+        }
+    }
+
+    #[cfg(feature = "raw-events")]
+    #[test]
+    fn raw_json_is_kept_with_the_event() {
+        let mut data = message();
+        data["unknown_field"] = json!(42);
+
+        match Event::try_from(dispatch("MESSAGE_CREATE", data.clone())).unwrap() {
+            Event::RawDispatch(Some(DispatchEvent::MessageCreate(msg)), raw) => {
+                assert_eq!(msg.content, "hello");
+                assert_eq!(raw.name, "MESSAGE_CREATE");
+                assert_eq!(raw.data, data);
+                assert_eq!(raw.get("unknown_field"), Some(&json!(42)));
+            }
+            other => panic!("expected a raw MESSAGE_CREATE, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "raw-events")]
+    #[test]
+    fn raw_json_of_unparsed_events_is_kept() {
+        match Event::try_from(dispatch("NEW_EVENT", json!({"a": 1}))).unwrap() {
+            Event::RawDispatch(None, raw) => assert_eq!(raw.data, json!({"a": 1})),
+            other => panic!("expected an unparsed raw event, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "raw-events")]
+    #[test]
+    fn convert_raw_event() {
+        let raw = RawEvent {
+            name: "MESSAGE_CREATE".into(),
+            data: message(),
+        };
+        assert_eq!(MessageCreate::try_from(raw.clone()).unwrap().content, "hello");
+        assert!(matches!(
+            MessageDelete::try_from(raw),
+            Err(PandaError::UnexpectedPayloadReceived)
+        ));
+    }
+
+    #[cfg(feature = "raw-events")]
+    #[test]
+    fn raw_resumed_is_resumed() {
+        let event = Event::try_from(dispatch("RESUMED", json!({}))).unwrap();
+        assert!(event.is_resumed());
+    }
+
+    #[test]
+    fn heartbeat_request() {
+        let payload = Payload {
+            op: Opcode::Heartbeat,
+            d: None,
+            s: None,
+            t: None,
+        };
+
+        assert!(matches!(Event::try_from(payload), Ok(Event::HeartbeatRequest)));
+    }
+}
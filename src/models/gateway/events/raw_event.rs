@@ -0,0 +1,28 @@
+use serde_json::Value;
+
+/// The json of a dispatch event as it was received, useful to read fields that aren't
+/// modeled yet. Only available with the `raw-events` feature
+///
+/// It can be parsed to the event type with `TryFrom`:
+///
+/// ```rust,ignore
+/// client.on_raw_event(|_, raw| async move {
+///     if raw.name == "MESSAGE_CREATE" {
+///         let msg = MessageCreate::try_from(raw)?;
+///     }
+///     Ok(())
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct RawEvent {
+    /// Name of the event, like MESSAGE_CREATE
+    pub name: String,
+    pub data: Value,
+}
+
+impl RawEvent {
+    /// Returns the value of a field of the event, if it exists
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.data.get(field)
+    }
+}
@@ -8,8 +8,8 @@ pub struct Ready {
     pub v: u8,
     pub user: User,
     // pub private_channels: Option<Vec<()>>,
-    #[serde(skip_deserializing)]
-    pub guilds: Vec<Guild>,
+    /// The guilds of the bot, they are unavailable until their GUILD_CREATE is received
+    pub guilds: Vec<UnavailableGuild>,
 
     pub session_id: String,
     pub shard: Option<[u64; 2]>,
@@ -0,0 +1,13 @@
+/// Sent by panda after the gateway connection was recovered, it isn't a Discord event.
+/// When the session is resumed the missed events are replayed, with a new session they are
+/// lost, so the state should be fetched again
+#[derive(Clone, Debug)]
+pub struct Reconnected {
+    /// True if the session was resumed (RESUMED), false if a new session was started (READY)
+    pub resumed: bool,
+    /// The id of the session, the new one if it wasn't resumed
+    pub session_id: String,
+    /// The number of missed events replayed by Discord before RESUMED, always 0 for a new session.
+    /// The events dropped by `set_drop_replayed_events` are counted too
+    pub replayed_events: u64,
+}
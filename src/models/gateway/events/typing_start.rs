@@ -1,4 +1,7 @@
+use crate::{error::Result, http::HttpClient, models::guild::GuildMember};
+
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TypingStart {
@@ -6,4 +9,82 @@ pub struct TypingStart {
     pub guild_id: Option<String>,
     pub user_id: String,
     pub timestamp: u64, // Unix time in seconds
+    /// Only sent when the user is typing in a guild
+    pub member: Option<GuildMember>,
+}
+
+impl TypingStart {
+    /// Returns the `timestamp` as a [`SystemTime`]
+    ///
+    /// [`SystemTime`]: https://doc.rust-lang.org/std/time/struct.SystemTime.html
+    pub fn started_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.timestamp)
+    }
+
+    /// Returns the member that started typing, it uses the member sent with the event when it
+    /// includes the user, and fetches it otherwise. Returns `None` if the user is typing in a DM
+    pub async fn resolve_member(&self, http: &HttpClient) -> Result<Option<GuildMember>> {
+        let guild_id = match &self.guild_id {
+            Some(guild_id) => guild_id,
+            None => return Ok(None),
+        };
+
+        if let Some(member) = &self.member {
+            if member.user.is_some() {
+                return Ok(Some(member.clone()));
+            }
+        }
+
+        http.get_guild_member(guild_id, &self.user_id).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    #[test]
+    fn typing_start_in_dm() {
+        let event: TypingStart = serde_json::from_value(json!({
+            "channel_id": "290926798999357250",
+            "user_id": "80351110224678912",
+            "timestamp": 1_600_000_000
+        }))
+        .unwrap();
+
+        assert!(event.member.is_none());
+        assert_eq!(event.started_at(), UNIX_EPOCH + Duration::from_secs(1_600_000_000));
+        // Nothing is fetched outside guilds
+        assert!(block_on(event.resolve_member(&HttpClient::new("token"))).unwrap().is_none());
+    }
+
+    #[test]
+    fn typing_start_member_is_used() {
+        let event: TypingStart = serde_json::from_value(json!({
+            "channel_id": "290926798999357250",
+            "guild_id": "290926798626357999",
+            "user_id": "80351110224678912",
+            "timestamp": 1_600_000_000,
+            "member": {
+                "user": {
+                    "id": "80351110224678912",
+                    "username": "Nelly",
+                    "discriminator": "1337",
+                    "avatar": null
+                },
+                "nick": "panda",
+                "roles": [],
+                "joined_at": "2015-04-26T06:26:56.936000+00:00",
+                "deaf": false,
+                "mute": false
+            }
+        }))
+        .unwrap();
+
+        let member = block_on(event.resolve_member(&HttpClient::new("token"))).unwrap().unwrap();
+        assert_eq!(member.nick.as_deref(), Some("panda"));
+    }
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// Gateway information for a bot.
+/// [Discord Documentation](https://discord.com/developers/docs/topics/gateway#get-gateway-bot)
+pub struct GatewayBot {
+    pub url: String,
+    /// Recommended number of shards
+    pub shards: u64,
+    pub session_start_limit: SessionStartLimit,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SessionStartLimit {
+    /// Total number of session starts allowed
+    pub total: u64,
+    /// Remaining number of session starts
+    pub remaining: u64,
+    /// Milliseconds until the limit resets
+    pub reset_after: u64,
+    /// Number of IDENTIFY allowed every 5 seconds
+    pub max_concurrency: u64,
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{BitOr, BitOrAssign};
+
+/// Gateway intents, used to select which events the bot will receive.
+/// [Discord Documentation](https://discord.com/developers/docs/topics/gateway#gateway-intents)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Intents(u64);
+
+impl Intents {
+    pub const GUILDS: Intents = Intents(1 << 0);
+    /// Privileged intent
+    pub const GUILD_MEMBERS: Intents = Intents(1 << 1);
+    pub const GUILD_BANS: Intents = Intents(1 << 2);
+    pub const GUILD_EMOJIS: Intents = Intents(1 << 3);
+    pub const GUILD_INTEGRATIONS: Intents = Intents(1 << 4);
+    pub const GUILD_WEBHOOKS: Intents = Intents(1 << 5);
+    pub const GUILD_INVITES: Intents = Intents(1 << 6);
+    pub const GUILD_VOICE_STATES: Intents = Intents(1 << 7);
+    /// Privileged intent
+    pub const GUILD_PRESENCES: Intents = Intents(1 << 8);
+    pub const GUILD_MESSAGES: Intents = Intents(1 << 9);
+    pub const GUILD_MESSAGE_REACTIONS: Intents = Intents(1 << 10);
+    pub const GUILD_MESSAGE_TYPING: Intents = Intents(1 << 11);
+    pub const DIRECT_MESSAGES: Intents = Intents(1 << 12);
+    pub const DIRECT_MESSAGE_REACTIONS: Intents = Intents(1 << 13);
+    pub const DIRECT_MESSAGE_TYPING: Intents = Intents(1 << 14);
+    /// Privileged intent
+    pub const MESSAGE_CONTENT: Intents = Intents(1 << 15);
+    pub const AUTO_MODERATION_CONFIGURATION: Intents = Intents(1 << 20);
+    pub const AUTO_MODERATION_EXECUTION: Intents = Intents(1 << 21);
+
+    /// No intents
+    pub const fn empty() -> Intents {
+        Intents(0)
+    }
+
+    /// All intents, including the privileged ones
+    pub const fn all() -> Intents {
+        Intents(((1 << 16) - 1) | Intents::AUTO_MODERATION_CONFIGURATION.0 | Intents::AUTO_MODERATION_EXECUTION.0)
+    }
+
+    pub const fn from_bits(bits: u64) -> Intents {
+        Intents(bits)
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if all the intents of other are in self
+    pub const fn contains(self, other: Intents) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if at least one of the intents of other is in self
+    pub const fn intersects(self, other: Intents) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Returns the intents needed to receive the event, the event is received if at least one
+    /// of them was requested. Returns `None` for events that are always received
+    pub(crate) fn for_event(event: &str) -> Option<Intents> {
+        let intents = match event {
+            "GUILD_CREATE" | "GUILD_UPDATE" | "GUILD_DELETE" | "GUILD_ROLE_CREATE" | "GUILD_ROLE_UPDATE"
+            | "GUILD_ROLE_DELETE" | "CHANNEL_CREATE" | "CHANNEL_UPDATE" | "CHANNEL_DELETE" => Intents::GUILDS,
+            // Pins update is also sent for DM channels
+            "CHANNEL_PINS_UPDATE" => Intents::GUILDS | Intents::DIRECT_MESSAGES,
+            "GUILD_MEMBER_ADD" | "GUILD_MEMBER_UPDATE" | "GUILD_MEMBER_REMOVE" => Intents::GUILD_MEMBERS,
+            "GUILD_BAN_ADD" | "GUILD_BAN_REMOVE" => Intents::GUILD_BANS,
+            "GUILD_EMOJIS_UPDATE" => Intents::GUILD_EMOJIS,
+            "GUILD_INTEGRATIONS_UPDATE" => Intents::GUILD_INTEGRATIONS,
+            "WEBHOOKS_UPDATE" => Intents::GUILD_WEBHOOKS,
+            "INVITE_CREATE" | "INVITE_DELETE" => Intents::GUILD_INVITES,
+            "VOICE_STATE_UPDATE" => Intents::GUILD_VOICE_STATES,
+            "PRESENCE_UPDATE" => Intents::GUILD_PRESENCES,
+            "MESSAGE_CREATE" | "MESSAGE_UPDATE" | "MESSAGE_DELETE" => {
+                Intents::GUILD_MESSAGES | Intents::DIRECT_MESSAGES
+            }
+            "MESSAGE_DELETE_BULK" => Intents::GUILD_MESSAGES,
+            "MESSAGE_REACTION_ADD"
+            | "MESSAGE_REACTION_REMOVE"
+            | "MESSAGE_REACTION_REMOVE_ALL"
+            | "MESSAGE_REACTION_REMOVE_EMOJI" => Intents::GUILD_MESSAGE_REACTIONS | Intents::DIRECT_MESSAGE_REACTIONS,
+            "TYPING_START" => Intents::GUILD_MESSAGE_TYPING | Intents::DIRECT_MESSAGE_TYPING,
+            "AUTO_MODERATION_RULE_CREATE" | "AUTO_MODERATION_RULE_UPDATE" | "AUTO_MODERATION_RULE_DELETE" => {
+                Intents::AUTO_MODERATION_CONFIGURATION
+            }
+            "AUTO_MODERATION_ACTION_EXECUTION" => Intents::AUTO_MODERATION_EXECUTION,
+            _ => return None,
+        };
+
+        Some(intents)
+    }
+}
+
+impl BitOr for Intents {
+    type Output = Intents;
+
+    fn bitor(self, rhs: Intents) -> Intents {
+        Intents(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Intents {
+    fn bitor_assign(&mut self, rhs: Intents) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_and_check_intents() {
+        let intents = Intents::GUILDS | Intents::GUILD_MESSAGES;
+        assert_eq!(intents.bits(), 1 | 1 << 9);
+        assert!(intents.contains(Intents::GUILDS));
+        assert!(!intents.contains(Intents::GUILDS | Intents::GUILD_BANS));
+        assert!(intents.intersects(Intents::GUILDS | Intents::GUILD_BANS));
+        assert!(!intents.intersects(Intents::GUILD_BANS));
+    }
+
+    #[test]
+    fn all_contains_every_intent() {
+        let all = Intents::all();
+        assert!(all.contains(Intents::MESSAGE_CONTENT));
+        assert!(all.contains(Intents::AUTO_MODERATION_EXECUTION));
+        assert!(!Intents::empty().intersects(all));
+    }
+
+    #[test]
+    fn required_intents_of_events() {
+        assert_eq!(Intents::for_event("GUILD_BAN_ADD"), Some(Intents::GUILD_BANS));
+        assert_eq!(
+            Intents::for_event("TYPING_START"),
+            Some(Intents::GUILD_MESSAGE_TYPING | Intents::DIRECT_MESSAGE_TYPING)
+        );
+        assert_eq!(Intents::for_event("READY"), None);
+    }
+
+    #[test]
+    fn serialize_as_bits() {
+        let intents = Intents::GUILDS | Intents::DIRECT_MESSAGES;
+        assert_eq!(serde_json::to_string(&intents).unwrap(), "4097");
+        assert_eq!(serde_json::from_str::<Intents>("4097").unwrap(), intents);
+    }
+}
@@ -0,0 +1,77 @@
+//! JSON backend used to parse the gateway payloads, GUILD_CREATE of large guilds can be
+//! several MB so the parser is kept behind a trait to allow faster implementations
+
+use crate::error::{PandaError, Result};
+
+use serde::de::DeserializeOwned;
+
+/// A JSON parser, it takes the data as mutable bytes because parsers like simd-json
+/// parse in place
+pub(crate) trait JsonBackend {
+    fn from_slice<T: DeserializeOwned>(data: &mut [u8]) -> Result<T>;
+}
+
+/// The default backend, using serde_json
+pub(crate) struct SerdeJson;
+
+impl JsonBackend for SerdeJson {
+    fn from_slice<T: DeserializeOwned>(data: &mut [u8]) -> Result<T> {
+        serde_json::from_slice(data).map_err(|_| PandaError::UnknownPayloadReceived)
+    }
+}
+
+/// Backend using simd-json, faster for big payloads, enabled with the `simd-json` feature
+#[cfg(feature = "simd-json")]
+pub(crate) struct SimdJson;
+
+#[cfg(feature = "simd-json")]
+impl JsonBackend for SimdJson {
+    fn from_slice<T: DeserializeOwned>(data: &mut [u8]) -> Result<T> {
+        simd_json::serde::from_slice(data).map_err(|_| PandaError::UnknownPayloadReceived)
+    }
+}
+
+/// The backend used by the gateway
+#[cfg(not(feature = "simd-json"))]
+pub(crate) type Backend = SerdeJson;
+
+/// The backend used by the gateway
+#[cfg(feature = "simd-json")]
+pub(crate) type Backend = SimdJson;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    const PAYLOADS: [&str; 3] = [
+        r#"{"op":10,"d":{"heartbeat_interval":41250},"s":null,"t":null}"#,
+        r#"{"op":0,"s":42,"t":"MESSAGE_CREATE","d":{"id":"1","content":"h\u00e9llo \"world\"","tts":false,"mentions":[],"nonce":12.5}}"#,
+        r#"{"op":11,"d":null}"#,
+    ];
+
+    #[test]
+    fn serde_json_parses_payloads() {
+        for payload in PAYLOADS.iter() {
+            let value: Value = SerdeJson::from_slice(&mut payload.as_bytes().to_vec()).unwrap();
+            assert_eq!(value, serde_json::from_str::<Value>(payload).unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_payload_is_unknown() {
+        let result: Result<Value> = Backend::from_slice(&mut b"{\"op\":".to_vec());
+        assert!(matches!(result, Err(PandaError::UnknownPayloadReceived)));
+    }
+
+    #[cfg(feature = "simd-json")]
+    #[test]
+    fn backends_parse_the_same() {
+        for payload in PAYLOADS.iter() {
+            let serde: Value = SerdeJson::from_slice(&mut payload.as_bytes().to_vec()).unwrap();
+            let simd: Value = SimdJson::from_slice(&mut payload.as_bytes().to_vec()).unwrap();
+            assert_eq!(serde, simd);
+        }
+    }
+}
@@ -2,6 +2,12 @@
 
 // Only crate modules
 pub(crate) mod commands;
+pub(crate) mod json;
 pub(crate) mod payload;
+mod gateway_bot;
+mod intents;
 
 pub mod events;
+
+pub use gateway_bot::{GatewayBot, SessionStartLimit};
+pub use intents::Intents;
@@ -1,13 +1,15 @@
 // crate imports
 use crate::error::PandaError;
 
+use super::json::{Backend, JsonBackend};
+
 use std::{convert::TryFrom, io::Read};
 
 use async_tungstenite::tungstenite::Message as TungsteniteMessage;
 use flate2::read::ZlibDecoder;
 
 // Serde
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use serde_repr::*;
 
@@ -26,19 +28,44 @@ pub(crate) struct Payload {
     pub t: Option<String>,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[derive(Debug, Serialize_repr, PartialEq)]
 #[repr(u8)]
 pub(crate) enum Opcode {
     Dispatch = 0,
     Heartbeat = 1,
     Identify = 2,
     StatusUpdate = 3,
+    VoiceStateUpdate = 4,
     Resume = 6,
     Reconnect = 7,
     RequestGuildMember = 8,
     InvalidSessionData = 9,
     Hello = 10,
     HeartbeatACK = 11,
+    /// An opcode that panda doesn't know, it's never sent
+    Unknown = 255,
+}
+
+// Implemented by hand, so new opcodes added by Discord don't make the payload fail to parse
+impl<'de> Deserialize<'de> for Opcode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let opcode = match u64::deserialize(deserializer)? {
+            0 => Opcode::Dispatch,
+            1 => Opcode::Heartbeat,
+            2 => Opcode::Identify,
+            3 => Opcode::StatusUpdate,
+            4 => Opcode::VoiceStateUpdate,
+            6 => Opcode::Resume,
+            7 => Opcode::Reconnect,
+            8 => Opcode::RequestGuildMember,
+            9 => Opcode::InvalidSessionData,
+            10 => Opcode::Hello,
+            11 => Opcode::HeartbeatACK,
+            _ => Opcode::Unknown,
+        };
+
+        Ok(opcode)
+    }
 }
 
 impl TryFrom<TungsteniteMessage> for Payload {
@@ -47,18 +74,18 @@ impl TryFrom<TungsteniteMessage> for Payload {
     fn try_from(value: TungsteniteMessage) -> Result<Payload, Self::Error> {
         let payload = match value {
             // Normal text
-            TungsteniteMessage::Text(v) => serde_json::from_str(&v).map_err(|_| PandaError::UnknownPayloadReceived)?,
+            TungsteniteMessage::Text(v) => Backend::from_slice(&mut v.into_bytes())?,
 
             // Compressed Text
             TungsteniteMessage::Binary(v) => {
                 let mut decoder = ZlibDecoder::new(v.as_slice());
-                let mut value = String::new();
+                let mut value = Vec::new();
 
                 decoder
-                    .read_to_string(&mut value)
+                    .read_to_end(&mut value)
                     .map_err(|_| PandaError::WrongCompression)?;
 
-                serde_json::from_str(&value).map_err(|_| PandaError::UnknownPayloadReceived)?
+                Backend::from_slice(&mut value)?
             }
 
             // Close frame, returned when Discord gateway close/refuse the connection
@@ -69,24 +96,15 @@ impl TryFrom<TungsteniteMessage> for Payload {
                 // https://discordapp.com/developers/docs/topics/opcodes-and-status-codes#gateway-gateway-close-event-codes
                 let code: u16 = reason.code.into();
 
-                match code {
-                    4000 => return Err(PandaError::ConnectionClosed),
-                    4001 => return Err(PandaError::UnknownOpcodeSent),
-                    4002 => return Err(PandaError::InvalidDecodeSent),
-                    // 4003 => this shouldn't happen
-                    4004 => return Err(PandaError::AuthenticationFailed),
-                    // 4005 => this shouldn't happen
-                    4007 => {
-                        log::error!("Panda error: Invalid seq sended");
-                        return Err(PandaError::ConnectionClosed);
-                    }
-                    4008 => return Err(PandaError::ConnectionClosed), // TODO: Improve this
-                    4009 => return Err(PandaError::ConnectionClosed), // TODO: Improve this
-                    4010 => return Err(PandaError::InvalidShard),
-                    4011 => return Err(PandaError::ShardingRequired),
-                    4012 => return Err(PandaError::InvalidApiGatewayVersion),
-                    _ => panic!("UNDEFINED Close error received"),
+                if code == 4007 {
+                    log::error!("Panda error: Invalid seq sended");
                 }
+
+                // The client decides if it reconnects, with PandaError::is_fatal_close
+                return Err(PandaError::GatewayClosed {
+                    code,
+                    reason: reason.reason.to_string(),
+                });
             }
             _ => todo!(),
         };
@@ -94,3 +112,52 @@ impl TryFrom<TungsteniteMessage> for Payload {
         Ok(payload)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_tungstenite::tungstenite::protocol::{frame::coding::CloseCode, CloseFrame};
+
+    fn close_frame(code: u16) -> TungsteniteMessage {
+        TungsteniteMessage::Close(Some(CloseFrame {
+            code: CloseCode::from(code),
+            reason: "Authentication failed.".into(),
+        }))
+    }
+
+    #[test]
+    fn close_frames_are_gateway_closed() {
+        for code in [4000, 4004, 4007, 4014].iter() {
+            match Payload::try_from(close_frame(*code)) {
+                Err(PandaError::GatewayClosed { code: received, reason }) => {
+                    assert_eq!(received, *code);
+                    assert_eq!(reason, "Authentication failed.");
+                }
+                other => panic!("expected GatewayClosed, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn close_without_frame_is_connection_closed() {
+        let result = Payload::try_from(TungsteniteMessage::Close(None));
+        assert!(matches!(result, Err(PandaError::ConnectionClosed)));
+    }
+
+    #[test]
+    fn parse_text_payload() {
+        let message = TungsteniteMessage::Text(r#"{"op":0,"d":{"a":1},"s":3,"t":"READY"}"#.into());
+        let payload = Payload::try_from(message).unwrap();
+
+        assert_eq!(payload.op, Opcode::Dispatch);
+        assert_eq!(payload.s, Some(3));
+        assert_eq!(payload.t.as_deref(), Some("READY"));
+    }
+
+    #[test]
+    fn unknown_opcodes_are_parsed() {
+        let message = TungsteniteMessage::Text(r#"{"op":42,"d":null}"#.into());
+        assert_eq!(Payload::try_from(message).unwrap().op, Opcode::Unknown);
+    }
+}
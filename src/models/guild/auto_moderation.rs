@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+/// [Discord Documentation](https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-rule-object)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoModerationRule {
+    pub id: String,
+    pub guild_id: String,
+    pub name: String,
+    pub creator_id: String,
+    pub event_type: AutoModerationEventType,
+    pub trigger_type: AutoModerationTriggerType,
+    #[serde(default)]
+    pub trigger_metadata: AutoModerationTriggerMetadata,
+    pub actions: Vec<AutoModerationAction>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub exempt_roles: Vec<String>,
+    #[serde(default)]
+    pub exempt_channels: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum AutoModerationEventType {
+    MessageSend = 1,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum AutoModerationTriggerType {
+    Keyword = 1,
+    Spam = 3,
+    KeywordPreset = 4,
+    MentionSpam = 5,
+}
+
+/// Additional data of the trigger, which fields are used depends on the trigger type
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AutoModerationTriggerMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keyword_filter: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regex_patterns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presets: Vec<KeywordPresetType>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_list: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention_total_limit: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum KeywordPresetType {
+    Profanity = 1,
+    SexualContent = 2,
+    Slurs = 3,
+}
+
+/// [Discord Documentation](https://discord.com/developers/docs/resources/auto-moderation#auto-moderation-action-object)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoModerationAction {
+    #[serde(rename = "type")]
+    pub kind: AutoModerationActionType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AutoModerationActionMetadata>,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum AutoModerationActionType {
+    BlockMessage = 1,
+    SendAlertMessage = 2,
+    Timeout = 3,
+}
+
+/// Additional data of the action, which fields are used depends on the action type
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct AutoModerationActionMetadata {
+    /// Channel where the alert is sent, for `SendAlertMessage`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<String>,
+    /// Timeout duration in seconds, for `Timeout`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    /// Message shown to the user when the message is blocked, for `BlockMessage`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_message: Option<String>,
+}
+
+impl AutoModerationAction {
+    /// Blocks the message, the custom message is shown to the user
+    pub fn block_message(custom_message: Option<String>) -> Self {
+        AutoModerationAction {
+            kind: AutoModerationActionType::BlockMessage,
+            metadata: custom_message.map(|custom_message| AutoModerationActionMetadata {
+                custom_message: Some(custom_message),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Sends an alert to the given channel
+    pub fn send_alert_message(channel_id: impl Into<String>) -> Self {
+        AutoModerationAction {
+            kind: AutoModerationActionType::SendAlertMessage,
+            metadata: Some(AutoModerationActionMetadata {
+                channel_id: Some(channel_id.into()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Timeouts the user, the duration can be at most 4 weeks
+    pub fn timeout(duration_seconds: u64) -> Self {
+        AutoModerationAction {
+            kind: AutoModerationActionType::Timeout,
+            metadata: Some(AutoModerationActionMetadata {
+                duration_seconds: Some(duration_seconds),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_rule() {
+        let rule: AutoModerationRule = serde_json::from_value(json!({
+            "id": "969707018069872670",
+            "guild_id": "613425648685547541",
+            "name": "Keyword Filter 1",
+            "creator_id": "423457898095789043",
+            "event_type": 1,
+            "trigger_type": 1,
+            "trigger_metadata": {"keyword_filter": ["cat*", "*dog"], "regex_patterns": ["(b|c)at"]},
+            "actions": [
+                {"type": 1, "metadata": {"custom_message": "Please keep financial discussions limited to the #trading channel"}},
+                {"type": 2, "metadata": {"channel_id": "123456789123456789"}},
+                {"type": 3, "metadata": {"duration_seconds": 60}}
+            ],
+            "enabled": true,
+            "exempt_roles": ["323456789123456789"]
+        }))
+        .unwrap();
+
+        assert_eq!(rule.trigger_type, AutoModerationTriggerType::Keyword);
+        assert_eq!(rule.trigger_metadata.keyword_filter, vec!["cat*", "*dog"]);
+        assert_eq!(rule.trigger_metadata.mention_total_limit, None);
+        assert_eq!(
+            rule.actions,
+            vec![
+                AutoModerationAction::block_message(Some(
+                    "Please keep financial discussions limited to the #trading channel".into()
+                )),
+                AutoModerationAction::send_alert_message("123456789123456789"),
+                AutoModerationAction::timeout(60),
+            ]
+        );
+        assert_eq!(rule.exempt_roles, vec!["323456789123456789"]);
+        assert!(rule.exempt_channels.is_empty());
+    }
+
+    #[test]
+    fn serialize_actions() {
+        assert_eq!(
+            serde_json::to_value(AutoModerationAction::block_message(None)).unwrap(),
+            json!({"type": 1})
+        );
+        assert_eq!(
+            serde_json::to_value(AutoModerationAction::timeout(60)).unwrap(),
+            json!({"type": 3, "metadata": {"duration_seconds": 60}})
+        );
+    }
+}
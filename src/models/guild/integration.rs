@@ -0,0 +1,63 @@
+use crate::models::user::User;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#integration-object)
+pub struct Integration {
+    pub id: String,
+    pub name: String,
+    /// twitch, youtube or discord
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub enabled: Option<bool>,
+    pub syncing: Option<bool>,
+    /// The role used for subscribers
+    pub role_id: Option<String>,
+    pub user: Option<User>,
+    pub account: IntegrationAccount,
+    /// Only for discord bot integrations
+    pub application: Option<IntegrationApplication>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IntegrationAccount {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IntegrationApplication {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub description: String,
+    pub bot: Option<User>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_bot_integration() {
+        let integration: Integration = serde_json::from_value(json!({
+            "id": "1",
+            "name": "panda",
+            "type": "discord",
+            "account": {"id": "2", "name": "panda"},
+            "application": {
+                "id": "2",
+                "name": "panda",
+                "icon": null,
+                "description": "A bot"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(integration.kind, "discord");
+        assert_eq!(integration.enabled, None);
+        assert_eq!(integration.application.unwrap().bot, None);
+    }
+}
@@ -1,6 +1,7 @@
-use crate::models::user::User;
+use crate::models::{gateway::events::GuildMemberUpdate, user::User};
 // use crate::models::guild::Role;
 use serde::{Deserialize, Serialize};
+use std::ops::{BitOr, BitOrAssign};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Member {
@@ -11,7 +12,179 @@ pub struct Member {
     pub premium_since: Option<String>,
     pub deaf: bool,
     pub mute: bool,
+    #[serde(default)]
+    pub flags: MemberFlags,
 
     // only for GUILD_MEMBER_ADD
     pub guild_id: Option<String>,
 }
+
+/// A member that is no longer in the guild, only the user and the guild are known
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PartialMember {
+    pub guild_id: String,
+    pub user: User,
+}
+
+/// Member flags, ORd together.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#guild-member-object-guild-member-flags)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct MemberFlags(u64);
+
+impl MemberFlags {
+    /// The member left and rejoined the guild
+    pub const DID_REJOIN: MemberFlags = MemberFlags(1 << 0);
+    /// The member completed the onboarding
+    pub const COMPLETED_ONBOARDING: MemberFlags = MemberFlags(1 << 1);
+    /// The member is exempt from the guild verification requirements
+    pub const BYPASSES_VERIFICATION: MemberFlags = MemberFlags(1 << 2);
+    /// The member started the onboarding
+    pub const STARTED_ONBOARDING: MemberFlags = MemberFlags(1 << 3);
+
+    pub const fn empty() -> MemberFlags {
+        MemberFlags(0)
+    }
+
+    pub const fn from_bits(bits: u64) -> MemberFlags {
+        MemberFlags(bits)
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns true if all the flags of other are in self
+    pub const fn contains(self, other: MemberFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn did_rejoin(self) -> bool {
+        self.contains(MemberFlags::DID_REJOIN)
+    }
+
+    pub const fn completed_onboarding(self) -> bool {
+        self.contains(MemberFlags::COMPLETED_ONBOARDING)
+    }
+
+    pub const fn bypasses_verification(self) -> bool {
+        self.contains(MemberFlags::BYPASSES_VERIFICATION)
+    }
+
+    pub const fn started_onboarding(self) -> bool {
+        self.contains(MemberFlags::STARTED_ONBOARDING)
+    }
+}
+
+impl BitOr for MemberFlags {
+    type Output = MemberFlags;
+
+    fn bitor(self, rhs: MemberFlags) -> MemberFlags {
+        MemberFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for MemberFlags {
+    fn bitor_assign(&mut self, rhs: MemberFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Member {
+    /// Apply a [`GuildMemberUpdate`] to this member, only the fields present in the update
+    /// are changed. `nick` and `premium_since` are always sent by Discord, so they are always applied
+    ///
+    /// [`GuildMemberUpdate`]: ../gateway/events/struct.GuildMemberUpdate.html
+    pub fn apply_update(&mut self, update: &GuildMemberUpdate) {
+        self.user = Some(update.user.clone());
+        self.nick = update.nick.clone();
+        self.premium_since = update.premium_since.clone();
+
+        if let Some(roles) = &update.roles {
+            self.roles = roles.clone();
+        }
+
+        if let Some(joined_at) = &update.joined_at {
+            self.joined_at = Some(joined_at.clone());
+        }
+
+        if let Some(deaf) = update.deaf {
+            self.deaf = deaf;
+        }
+
+        if let Some(mute) = update.mute {
+            self.mute = mute;
+        }
+
+        if let Some(flags) = update.flags {
+            self.flags = flags;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn member() -> Member {
+        serde_json::from_value(json!({
+            "user": {"id": "1", "username": "user", "discriminator": "0001", "avatar": null},
+            "nick": "nick",
+            "roles": ["10"],
+            "joined_at": "2020-01-01T00:00:00+00:00",
+            "premium_since": null,
+            "deaf": false,
+            "mute": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_member_update() {
+        let mut member = member();
+        let update: GuildMemberUpdate = serde_json::from_value(json!({
+            "guild_id": "2",
+            "roles": ["10", "11"],
+            "user": {"id": "1", "username": "renamed", "discriminator": "0001", "avatar": null},
+            "nick": null,
+            "premium_since": null,
+            "mute": true
+        }))
+        .unwrap();
+        member.apply_update(&update);
+
+        assert_eq!(member.user.as_ref().unwrap().username, "renamed");
+        assert_eq!(member.roles, vec!["10".to_string(), "11".to_string()]);
+        assert!(member.nick.is_none());
+        assert!(member.mute);
+
+        // Fields that weren't sent are kept
+        assert_eq!(member.joined_at.as_deref(), Some("2020-01-01T00:00:00+00:00"));
+        assert!(!member.deaf);
+    }
+
+    #[test]
+    fn member_flags() {
+        let rejoined: Member = serde_json::from_value(json!({
+            "user": null,
+            "nick": null,
+            "roles": [],
+            "joined_at": null,
+            "premium_since": null,
+            "deaf": false,
+            "mute": false,
+            "flags": 3
+        }))
+        .unwrap();
+
+        assert!(rejoined.flags.did_rejoin());
+        assert!(rejoined.flags.completed_onboarding());
+        assert!(!rejoined.flags.started_onboarding());
+        assert_eq!(rejoined.flags, MemberFlags::DID_REJOIN | MemberFlags::COMPLETED_ONBOARDING);
+
+        // Members without flags
+        assert_eq!(member().flags, MemberFlags::empty());
+    }
+}
@@ -1,11 +1,30 @@
+mod auto_moderation;
+mod integration;
 mod member;
+mod onboarding;
+mod preview;
 mod role;
+mod template;
+mod widget;
 
-use crate::models::{channel::Channel, emoji::Emoji};
+use crate::models::{
+    channel::Channel,
+    emoji::Emoji,
+    gateway::events::{GuildRoleUpdate, GuildUpdate},
+};
 use serde::{Deserialize, Serialize};
 
-pub use member::Member as GuildMember;
+pub use auto_moderation::{
+    AutoModerationAction, AutoModerationActionMetadata, AutoModerationActionType, AutoModerationEventType,
+    AutoModerationRule, AutoModerationTriggerMetadata, AutoModerationTriggerType, KeywordPresetType,
+};
+pub use integration::{Integration, IntegrationAccount, IntegrationApplication};
+pub use member::{Member as GuildMember, MemberFlags as GuildMemberFlags, PartialMember as PartialGuildMember};
+pub use onboarding::{Onboarding, OnboardingMode, OnboardingPrompt, OnboardingPromptKind, OnboardingPromptOption};
+pub use preview::GuildPreview;
 pub use role::Role;
+pub use template::GuildTemplate;
+pub use widget::{GuildWidget, GuildWidgetSettings, VanityUrl, WidgetChannel, WidgetMember};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Guild {
@@ -16,6 +35,8 @@ pub struct Guild {
     pub owner: Option<bool>,
     pub owner_id: String,
     pub permissions: Option<u64>,
+    /// Deprecated, the region is set per voice channel
+    #[serde(default)]
     pub region: String,
     pub afk_channel_id: Option<String>,
     pub afk_timeout: u64,
@@ -24,29 +45,226 @@ pub struct Guild {
     pub verification_level: u64, // maybe use enum
     pub default_message_notifications: u64,
     pub explicit_content_filter: u64,
+    #[serde(default)]
     pub roles: Vec<Role>,
+    #[serde(default)]
     pub emojis: Vec<Emoji>,
-    //pe) features: Vec<Feature>,
+    /// The enabled guild features, like `COMMUNITY` or `VANITY_URL`
+    #[serde(default)]
+    pub features: Vec<String>,
     pub mfa_level: u64,
     pub application_id: Option<String>,
     pub widget_enabled: Option<bool>,
     pub widget_channel_id: Option<String>,
     pub system_channel_id: Option<String>,
-    // te) max_presences:
+    pub rules_channel_id: Option<String>,
+    pub public_updates_channel_id: Option<String>,
+    pub max_presences: Option<u64>,
     pub max_members: Option<u64>,
     pub vanity_url_code: Option<String>,
     pub description: Option<String>,
     pub banner: Option<String>,
+    pub discovery_splash: Option<String>,
     pub premium_tier: u64,
     pub premium_subscription_count: Option<u64>,
     pub preferred_locale: String,
+    // Only sent by get_guild with counts
+    pub approximate_member_count: Option<u64>,
+    pub approximate_presence_count: Option<u64>,
     // elds are only sent within GUILD_CREATE event
+    #[serde(default)]
     pub joined_at: String,
     pub large: Option<bool>,
+    #[serde(default)]
     pub unavailable: bool,
     pub member_count: Option<u64>,
     // ce_states: Vec<Voice>,
+    #[serde(default)]
     pub members: Vec<GuildMember>,
+    #[serde(default)]
     pub channels: Vec<Channel>,
     // pub presences: Vec<Presence>,
 }
+
+/// A guild that can't be used yet, sent with READY and when there is an outage
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UnavailableGuild {
+    pub id: String,
+    #[serde(default)]
+    pub unavailable: bool,
+}
+
+impl Guild {
+    /// Apply a [`GuildUpdate`] to this guild. The fields only sent within GUILD_CREATE
+    /// (joined_at, large, unavailable, member_count, members and channels) are kept
+    ///
+    /// [`GuildUpdate`]: ../gateway/events/struct.GuildUpdate.html
+    pub fn apply_update(&mut self, update: &GuildUpdate) {
+        if self.id != update.id {
+            return;
+        }
+
+        let mut guild = update.0.clone();
+        std::mem::swap(&mut guild.joined_at, &mut self.joined_at);
+        std::mem::swap(&mut guild.members, &mut self.members);
+        std::mem::swap(&mut guild.channels, &mut self.channels);
+        guild.large = self.large;
+        guild.unavailable = self.unavailable;
+        guild.member_count = self.member_count;
+
+        *self = guild;
+    }
+
+    /// Returns the number of members of the guild, the exact count sent with GUILD_CREATE,
+    /// or the approximate count of [`get_guild_with_counts`]
+    ///
+    /// [`get_guild_with_counts`]: ../../struct.HttpClient.html#method.get_guild_with_counts
+    pub fn total_member_count(&self) -> Option<u64> {
+        self.member_count.or(self.approximate_member_count)
+    }
+
+    /// Apply a [`GuildRoleUpdate`] to the role of this guild
+    ///
+    /// [`GuildRoleUpdate`]: ../gateway/events/struct.GuildRoleUpdate.html
+    pub fn apply_role_update(&mut self, update: &GuildRoleUpdate) {
+        if self.id != update.guild_id {
+            return;
+        }
+
+        match self.roles.iter_mut().find(|r| r.id == update.role.id) {
+            Some(role) => role.apply_update(update),
+            None => self.roles.push(update.role.clone()),
+        }
+    }
+}
+
+/// A partial guild, like the one sent with invites or the user guilds list
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PartialGuild {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub splash: Option<String>,
+    pub banner: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub verification_level: Option<u64>,
+    pub vanity_url_code: Option<String>,
+    pub owner: Option<bool>,
+    pub permissions: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    fn guild(fields: Value) -> Guild {
+        let mut guild = json!({
+            "id": "1",
+            "name": "panda",
+            "icon": null,
+            "splash": null,
+            "owner_id": "2",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 1,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "mfa_level": 0,
+            "premium_tier": 0,
+            "preferred_locale": "en-US"
+        });
+        guild.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+
+        serde_json::from_value(guild).unwrap()
+    }
+
+    fn role(id: &str, name: &str) -> Role {
+        serde_json::from_value(json!({
+            "id": id,
+            "name": name,
+            "color": 0,
+            "hoist": false,
+            "position": 1,
+            "permissions": 0,
+            "managed": false,
+            "mentionable": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_guild_update_keeps_create_fields() {
+        let mut panda = guild(json!({
+            "joined_at": "2020-01-01T00:00:00+00:00",
+            "large": true,
+            "member_count": 300,
+            "channels": [{"id": "3", "type": 0, "name": "general"}]
+        }));
+
+        panda.apply_update(&GuildUpdate(guild(json!({"name": "renamed"}))));
+        assert_eq!(panda.name, "renamed");
+        assert_eq!(panda.joined_at, "2020-01-01T00:00:00+00:00");
+        assert_eq!(panda.large, Some(true));
+        assert_eq!(panda.member_count, Some(300));
+        assert_eq!(panda.channels.len(), 1);
+
+        // Updates of other guilds are ignored
+        panda.apply_update(&GuildUpdate(guild(json!({"id": "9", "name": "other"}))));
+        assert_eq!(panda.name, "renamed");
+    }
+
+    #[test]
+    fn apply_role_updates() {
+        let mut panda = guild(json!({}));
+        panda.roles.push(role("10", "mods"));
+
+        let update = |role| GuildRoleUpdate {
+            guild_id: "1".into(),
+            role,
+        };
+        panda.apply_role_update(&update(role("10", "moderators")));
+        panda.apply_role_update(&update(role("11", "new")));
+
+        let names: Vec<&str> = panda.roles.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["moderators", "new"]);
+    }
+
+    #[test]
+    fn guild_defaults() {
+        let panda = guild(json!({}));
+
+        assert!(panda.features.is_empty());
+        assert!(panda.roles.is_empty());
+        assert!(panda.region.is_empty());
+        assert!(!panda.unavailable);
+        assert!(panda.members.is_empty());
+    }
+
+    #[test]
+    fn guild_features_and_counts() {
+        let panda = guild(json!({
+            "features": ["COMMUNITY", "VANITY_URL"],
+            "premium_subscription_count": 14,
+            "max_members": 500000
+        }));
+
+        assert_eq!(panda.features, vec!["COMMUNITY".to_string(), "VANITY_URL".to_string()]);
+        assert_eq!(panda.premium_subscription_count, Some(14));
+        assert_eq!(panda.max_members, Some(500000));
+    }
+
+    #[test]
+    fn total_member_count() {
+        assert_eq!(guild(json!({"member_count": 10})).total_member_count(), Some(10));
+        assert_eq!(guild(json!({"approximate_member_count": 12})).total_member_count(), Some(12));
+        assert_eq!(
+            guild(json!({"member_count": 10, "approximate_member_count": 12})).total_member_count(),
+            Some(10)
+        );
+        assert_eq!(guild(json!({})).total_member_count(), None);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::models::emoji::Emoji;
+
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The onboarding flow shown to new members of the guild.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#guild-onboarding-object)
+pub struct Onboarding {
+    pub guild_id: String,
+    pub prompts: Vec<OnboardingPrompt>,
+    /// Channels that members get opted into automatically
+    pub default_channel_ids: Vec<String>,
+    pub enabled: bool,
+    pub mode: OnboardingMode,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// A question of the onboarding
+pub struct OnboardingPrompt {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: OnboardingPromptKind,
+    pub options: Vec<OnboardingPromptOption>,
+    pub title: String,
+    /// If the user can select only one option
+    pub single_select: bool,
+    pub required: bool,
+    /// If the prompt is shown in the onboarding, or only in the Channels & Roles tab
+    pub in_onboarding: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// An answer of a prompt, the member gets the channels and roles when selecting it
+pub struct OnboardingPromptOption {
+    pub id: String,
+    #[serde(default)]
+    pub channel_ids: Vec<String>,
+    #[serde(default)]
+    pub role_ids: Vec<String>,
+    pub emoji: Option<Emoji>,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum OnboardingPromptKind {
+    MultipleChoice = 0,
+    Dropdown = 1,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum OnboardingMode {
+    /// Only the default channels count towards the onboarding constraints
+    Default = 0,
+    /// The default channels and the questions count towards the onboarding constraints
+    Advanced = 1,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_onboarding() {
+        let onboarding: Onboarding = serde_json::from_value(json!({
+            "guild_id": "290926798626357999",
+            "prompts": [{
+                "id": "1",
+                "type": 1,
+                "options": [{
+                    "id": "2",
+                    "role_ids": ["41771983423143936"],
+                    "emoji": {"id": null, "name": "🐼"},
+                    "title": "Pandas",
+                    "description": null
+                }],
+                "title": "What do you like?",
+                "single_select": true,
+                "required": false,
+                "in_onboarding": true
+            }],
+            "default_channel_ids": ["290926798999357250"],
+            "enabled": true,
+            "mode": 1
+        }))
+        .unwrap();
+
+        assert_eq!(onboarding.mode, OnboardingMode::Advanced);
+
+        let prompt = &onboarding.prompts[0];
+        assert_eq!(prompt.kind, OnboardingPromptKind::Dropdown);
+        assert!(prompt.options[0].channel_ids.is_empty());
+        assert_eq!(prompt.options[0].role_ids, vec!["41771983423143936"]);
+        assert_eq!(prompt.options[0].emoji.as_ref().unwrap().name.as_deref(), Some("🐼"));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::{emoji::Emoji, sticker::Sticker};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The preview of a discoverable guild, it can be fetched without being a member.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#guild-preview-object)
+pub struct GuildPreview {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub splash: Option<String>,
+    pub discovery_splash: Option<String>,
+    #[serde(default)]
+    pub emojis: Vec<Emoji>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub approximate_member_count: u64,
+    pub approximate_presence_count: u64,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stickers: Vec<Sticker>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::sticker::StickerFormat;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_preview() {
+        let preview: GuildPreview = serde_json::from_value(json!({
+            "id": "197038439483310086",
+            "name": "Discord Testers",
+            "icon": "f64c482b807da4f539cff778d174971c",
+            "splash": null,
+            "discovery_splash": null,
+            "features": ["DISCOVERABLE", "VANITY_URL"],
+            "approximate_member_count": 60814,
+            "approximate_presence_count": 20034,
+            "description": "The official place to report Discord Bugs!",
+            "stickers": [{
+                "id": "749054660769218631",
+                "name": "Wave",
+                "description": "Wumpus waves hello",
+                "tags": "wumpus, hello, sup, hi, oi, heyo, heya, yo, wave",
+                "type": 1,
+                "format_type": 3,
+                "pack_id": "847199849233514549"
+            }]
+        }))
+        .unwrap();
+
+        assert!(preview.emojis.is_empty());
+        assert_eq!(preview.features, vec!["DISCOVERABLE", "VANITY_URL"]);
+        assert_eq!(preview.stickers[0].format_type, StickerFormat::Lottie);
+        assert_eq!(preview.stickers[0].guild_id, None);
+    }
+}
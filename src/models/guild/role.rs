@@ -1,5 +1,6 @@
 //! Guild related models
 
+use crate::models::gateway::events::GuildRoleUpdate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -12,4 +13,76 @@ pub struct Role {
     pub permissions: u64,
     pub managed: bool,
     pub mentionable: bool,
+    /// Hash of the role icon
+    pub icon: Option<String>,
+    /// Unicode emoji used as the role icon
+    pub unicode_emoji: Option<String>,
+}
+
+impl Role {
+    /// Returns the url of the role icon, with the given size in pixels. The size must be a power
+    /// of 2 between 16 and 4096. Returns `None` if the role doesn't have an icon
+    pub fn icon_url(&self, size: u16) -> Option<String> {
+        self.icon
+            .as_ref()
+            .map(|icon| format!("https://cdn.discordapp.com/role-icons/{}/{}.png?size={}", self.id, icon, size))
+    }
+
+    /// Apply a [`GuildRoleUpdate`] to this role, it's ignored if the updated role is
+    /// a different one
+    ///
+    /// [`GuildRoleUpdate`]: ../gateway/events/struct.GuildRoleUpdate.html
+    pub fn apply_update(&mut self, update: &GuildRoleUpdate) {
+        if self.id == update.role.id {
+            *self = update.role.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn role(name: &str, icon: Option<&str>) -> Role {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": name,
+            "color": 0,
+            "hoist": false,
+            "position": 1,
+            "permissions": 0,
+            "managed": false,
+            "mentionable": false,
+            "icon": icon
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn apply_role_update() {
+        let mut mods = role("mods", None);
+
+        let update = GuildRoleUpdate {
+            guild_id: "2".into(),
+            role: role("moderators", None),
+        };
+        mods.apply_update(&update);
+        assert_eq!(mods.name, "moderators");
+
+        let mut other = role("other", None);
+        other.id = "3".into();
+        other.apply_update(&update);
+        assert_eq!(other.name, "other");
+    }
+
+    #[test]
+    fn role_icon_url() {
+        assert_eq!(
+            role("mods", Some("abc")).icon_url(64).as_deref(),
+            Some("https://cdn.discordapp.com/role-icons/1/abc.png?size=64")
+        );
+        assert_eq!(role("mods", None).icon_url(64), None);
+    }
 }
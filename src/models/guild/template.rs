@@ -0,0 +1,64 @@
+use crate::models::user::User;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// A snapshot of a guild, used to create new guilds with the same channels, roles and settings.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild-template#guild-template-object)
+pub struct GuildTemplate {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub usage_count: u64,
+    pub creator_id: String,
+    pub creator: Option<User>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub source_guild_id: String,
+    /// The guild settings, roles and channels of the template, it's a partial guild
+    pub serialized_source_guild: Value,
+    /// True if the source guild was changed since the template was last synced
+    pub is_dirty: Option<bool>,
+}
+
+impl GuildTemplate {
+    /// Returns the link used to create a guild from this template in the client
+    pub fn url(&self) -> String {
+        format!("https://discord.new/{}", self.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_template() {
+        let template: GuildTemplate = serde_json::from_value(json!({
+            "code": "hgM48av5Q69A",
+            "name": "Friends & Family",
+            "description": null,
+            "usage_count": 49605,
+            "creator_id": "132837293881950208",
+            "creator": {
+                "id": "132837293881950208",
+                "username": "hoges",
+                "discriminator": "0001",
+                "avatar": null
+            },
+            "created_at": "2020-04-02T21:10:38+00:00",
+            "updated_at": "2020-05-01T17:57:38+00:00",
+            "source_guild_id": "678070694164299796",
+            "serialized_source_guild": {"name": "Friends & Family", "roles": [], "channels": []},
+            "is_dirty": null
+        }))
+        .unwrap();
+
+        assert_eq!(template.creator.as_ref().unwrap().username, "hoges");
+        assert_eq!(template.serialized_source_guild["name"], "Friends & Family");
+        assert!(template.is_dirty.is_none());
+        assert_eq!(template.url(), "https://discord.new/hgM48av5Q69A");
+    }
+}
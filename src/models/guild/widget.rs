@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// Settings of the guild widget.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#guild-widget-settings-object)
+pub struct GuildWidgetSettings {
+    pub enabled: bool,
+    /// The channel used for the widget invite, `None` to disable the invite
+    pub channel_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The public widget of a guild, only available if it's enabled.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/guild#guild-widget-object)
+pub struct GuildWidget {
+    pub id: String,
+    pub name: String,
+    pub instant_invite: Option<String>,
+    #[serde(default)]
+    pub channels: Vec<WidgetChannel>,
+    /// Online members, the ids are anonymized
+    #[serde(default)]
+    pub members: Vec<WidgetMember>,
+    pub presence_count: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// A voice channel shown in the widget
+pub struct WidgetChannel {
+    pub id: String,
+    pub name: String,
+    pub position: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// An online member shown in the widget
+pub struct WidgetMember {
+    pub id: String,
+    pub username: String,
+    pub discriminator: String,
+    pub avatar: Option<String>,
+    pub status: String,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The vanity invite of a guild, the code is `None` if it isn't set
+pub struct VanityUrl {
+    pub code: Option<String>,
+    pub uses: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_widget() {
+        let widget: GuildWidget = serde_json::from_value(json!({
+            "id": "290926798626357999",
+            "name": "panda",
+            "instant_invite": "https://discord.com/invite/abcdef",
+            "channels": [{"id": "705216630279993882", "name": "elephant", "position": 2}],
+            "members": [{
+                "id": "0",
+                "username": "1234",
+                "discriminator": "0000",
+                "avatar": null,
+                "status": "online",
+                "avatar_url": "https://cdn.discordapp.com/widget-avatars/FfvURgcr3Za92K3JtoCppqnYMppMDc5B-Rll74YrGCU/C-1DyBZPQ6t5q2RuATFuMFgq0_uEMZVzd_6LbwuRw8Ss"
+            }],
+            "presence_count": 1
+        }))
+        .unwrap();
+
+        assert_eq!(widget.channels[0].name, "elephant");
+        assert_eq!(widget.members[0].status, "online");
+    }
+
+    #[test]
+    fn deserialize_settings_and_vanity_url() {
+        let settings: GuildWidgetSettings = serde_json::from_value(json!({"enabled": true, "channel_id": null})).unwrap();
+        assert!(settings.enabled);
+        assert_eq!(settings.channel_id, None);
+
+        let vanity: VanityUrl = serde_json::from_value(json!({"code": null, "uses": 0})).unwrap();
+        assert_eq!(vanity.code, None);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::models::{
+    channel::{Channel, Message, PartialChannel},
+    guild::{Guild, PartialGuild, Role, UnavailableGuild},
+    user::User,
+};
+
+/// A snowflake id, or a model with an id. The [`HttpClient`] methods take the ids with this trait,
+/// to use the models directly instead of their id.
+///
+/// ```rust,ignore
+/// // Both are the same
+/// http.delete_message(&msg.channel_id, &msg.id).await?;
+/// http.delete_message(&msg.channel_id, &msg).await?;
+/// ```
+///
+/// [`HttpClient`]: ../../struct.HttpClient.html
+pub trait IntoId {
+    /// Returns the id
+    fn as_id(&self) -> &str;
+}
+
+impl<T: AsRef<str> + ?Sized> IntoId for T {
+    fn as_id(&self) -> &str {
+        self.as_ref()
+    }
+}
+
+macro_rules! impl_into_id {
+    ($($model: ty),+) => {
+        $(
+            impl IntoId for $model {
+                fn as_id(&self) -> &str {
+                    &self.id
+                }
+            }
+
+            impl IntoId for &$model {
+                fn as_id(&self) -> &str {
+                    &self.id
+                }
+            }
+        )+
+    };
+}
+
+impl_into_id!(Channel, Guild, Message, PartialChannel, PartialGuild, Role, UnavailableGuild, User);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn id_of(id: impl IntoId) -> String {
+        id.as_id().to_string()
+    }
+
+    #[test]
+    fn ids_and_models() {
+        let user: User = serde_json::from_value(json!({
+            "id": "80351110224678912",
+            "username": "Nelly",
+            "discriminator": "1337",
+            "avatar": null
+        }))
+        .unwrap();
+
+        assert_eq!(id_of("80351110224678912"), "80351110224678912");
+        assert_eq!(id_of(String::from("80351110224678912")), "80351110224678912");
+        assert_eq!(id_of(&user.id), "80351110224678912");
+        assert_eq!(id_of(&user), "80351110224678912");
+        assert_eq!(id_of(user), "80351110224678912");
+    }
+}
@@ -0,0 +1,49 @@
+use crate::models::{channel::PartialChannel, user::User};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_repr::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize)]
+/// An option of an application command, with the value given by the user.
+/// [Discord Documentation](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object-application-command-interaction-data-option-structure)
+pub struct CommandOption {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: CommandOptionKind,
+    /// The value of the option, for users, channels and roles it's the id, the object
+    /// is in the resolved data
+    pub value: Option<Value>,
+    /// The options of a sub command or a sub command group
+    #[serde(default)]
+    pub options: Vec<CommandOption>,
+    /// For autocomplete, true if the user is writing this option
+    #[serde(default)]
+    pub focused: bool,
+}
+
+#[derive(Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum CommandOptionKind {
+    SubCommand = 1,
+    SubCommandGroup = 2,
+    String = 3,
+    Integer = 4,
+    Boolean = 5,
+    User = 6,
+    Channel = 7,
+    Role = 8,
+    Mentionable = 9,
+    Number = 10,
+    Attachment = 11,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+/// The objects of the user and channel options, by id
+pub struct ResolvedData {
+    #[serde(default)]
+    pub users: HashMap<String, User>,
+    #[serde(default)]
+    pub channels: HashMap<String, PartialChannel>,
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// The permissions of an application command in a guild
+/// [Discord Documentation](https://discord.com/developers/docs/interactions/application-commands#application-command-permissions-object-guild-application-command-permissions-structure)
+pub struct GuildCommandPermissions {
+    /// ID of the command, or the application id for the permissions of all the commands
+    pub id: String,
+    pub application_id: String,
+    pub guild_id: String,
+    pub permissions: Vec<CommandPermission>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+/// Allows or denies a command to a role, user or channel
+pub struct CommandPermission {
+    /// ID of the role, user or channel. The guild id is used for @everyone, and the guild id
+    /// minus 1 for all the channels
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: CommandPermissionKind,
+    /// True to allow the command, false to deny it
+    pub permission: bool,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum CommandPermissionKind {
+    Role = 1,
+    User = 2,
+    Channel = 3,
+}
+
+impl CommandPermission {
+    pub fn role(role_id: impl Into<String>, permission: bool) -> Self {
+        Self::new(role_id, CommandPermissionKind::Role, permission)
+    }
+
+    pub fn user(user_id: impl Into<String>, permission: bool) -> Self {
+        Self::new(user_id, CommandPermissionKind::User, permission)
+    }
+
+    pub fn channel(channel_id: impl Into<String>, permission: bool) -> Self {
+        Self::new(channel_id, CommandPermissionKind::Channel, permission)
+    }
+
+    fn new(id: impl Into<String>, kind: CommandPermissionKind, permission: bool) -> Self {
+        CommandPermission {
+            id: id.into(),
+            kind,
+            permission,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_permissions() {
+        let permissions: GuildCommandPermissions = serde_json::from_value(json!({
+            "id": "1",
+            "application_id": "2",
+            "guild_id": "3",
+            "permissions": [
+                {"id": "3", "type": 1, "permission": false},
+                {"id": "4", "type": 2, "permission": true}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            permissions.permissions,
+            vec![CommandPermission::role("3", false), CommandPermission::user("4", true)]
+        );
+    }
+
+    #[test]
+    fn serialize_permission() {
+        assert_eq!(
+            serde_json::to_value(CommandPermission::channel("5", true)).unwrap(),
+            json!({"id": "5", "type": 3, "permission": true})
+        );
+    }
+}
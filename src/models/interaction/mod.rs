@@ -0,0 +1,358 @@
+//! Interaction related models
+
+mod command_option;
+mod command_permissions;
+mod response;
+
+pub use command_option::{CommandOption, CommandOptionKind, ResolvedData};
+pub use command_permissions::{CommandPermission, CommandPermissionKind, GuildCommandPermissions};
+pub use response::{InteractionResponse, InteractionResponseKind};
+
+use crate::{
+    error::{PandaError, Result},
+    http::HttpClient,
+    models::{
+        channel::{Component, ComponentKind, Message, PartialChannel},
+        guild::GuildMember,
+        user::User,
+    },
+    utils::builders::{MessageEdit, Modal},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Flag used to make an interaction response only visible to the user who invoked it
+const EPHEMERAL_FLAG: u64 = 1 << 6;
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Represents an interaction received from Discord, like a slash command or a button click.
+/// [Discord Documentation](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-object)
+pub struct Interaction {
+    /// ID of the interaction
+    pub id: String,
+
+    /// ID of the application this interaction is for
+    pub application_id: String,
+
+    /// Type of interaction
+    #[serde(rename = "type")]
+    pub kind: InteractionKind,
+
+    /// Interaction data payload
+    pub data: Option<InteractionData>,
+
+    /// ID of the guild it was sent from
+    pub guild_id: Option<String>,
+
+    /// ID of the channel it was sent from
+    pub channel_id: Option<String>,
+
+    /// Guild member data for the invoking user, in case it was sent in a guild
+    pub member: Option<GuildMember>,
+
+    /// User object for the invoking user, in case it was sent in a DM
+    pub user: Option<User>,
+
+    /// Continuation token for responding to the interaction
+    pub token: String,
+
+    /// Read-only property, always 1
+    pub version: u64,
+
+    /// For components, the message they were attached to
+    pub message: Option<Message>,
+
+    /// Whether a response was already sent for this interaction
+    #[serde(skip)]
+    responded: AtomicBool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InteractionData {
+    /// ID of the invoked command
+    pub id: Option<String>,
+
+    /// Name of the invoked command
+    pub name: Option<String>,
+
+    /// The custom_id of the component
+    pub custom_id: Option<String>,
+
+    /// The type of the component
+    pub component_type: Option<u64>,
+
+    /// The values the user selected, for select menus
+    #[serde(default)]
+    pub values: Vec<String>,
+
+    /// The action rows with the submitted text inputs, for modal submits
+    #[serde(default)]
+    pub components: Vec<Component>,
+
+    /// The options of the command given by the user, for application commands
+    #[serde(default)]
+    pub options: Vec<CommandOption>,
+
+    /// The users and channels of the options
+    pub resolved: Option<ResolvedData>,
+}
+
+impl InteractionData {
+    /// Returns the value written by the user in the text input with the given custom_id,
+    /// for modal submits
+    pub fn text_input_value(&self, custom_id: &str) -> Option<&str> {
+        self.components
+            .iter()
+            .flat_map(|row| &row.components)
+            .filter(|c| c.kind == ComponentKind::TextInput)
+            .find(|c| c.custom_id.as_deref() == Some(custom_id))
+            .and_then(|c| c.value.as_deref())
+    }
+
+    /// Returns the option with the given name, the options of the sub command are searched
+    /// if the command was invoked with one
+    pub fn option(&self, name: &str) -> Option<&CommandOption> {
+        find_option(&self.options, name)
+    }
+
+    pub fn get_string(&self, name: &str) -> Option<&str> {
+        self.option_value(name)?.as_str()
+    }
+
+    pub fn get_integer(&self, name: &str) -> Option<i64> {
+        self.option_value(name)?.as_i64()
+    }
+
+    pub fn get_number(&self, name: &str) -> Option<f64> {
+        self.option_value(name)?.as_f64()
+    }
+
+    pub fn get_boolean(&self, name: &str) -> Option<bool> {
+        self.option_value(name)?.as_bool()
+    }
+
+    /// Returns the user of a user option, from the resolved data
+    pub fn get_user(&self, name: &str) -> Option<&User> {
+        let id = self.get_string(name)?;
+
+        self.resolved.as_ref()?.users.get(id)
+    }
+
+    /// Returns the channel of a channel option, from the resolved data
+    pub fn get_channel(&self, name: &str) -> Option<&PartialChannel> {
+        let id = self.get_string(name)?;
+
+        self.resolved.as_ref()?.channels.get(id)
+    }
+
+    fn option_value(&self, name: &str) -> Option<&serde_json::Value> {
+        self.option(name)?.value.as_ref()
+    }
+}
+
+/// Search the option by name, and inside the sub commands and sub command groups
+fn find_option<'a>(options: &'a [CommandOption], name: &str) -> Option<&'a CommandOption> {
+    for option in options {
+        match option.kind {
+            CommandOptionKind::SubCommand | CommandOptionKind::SubCommandGroup => {
+                if let Some(option) = find_option(&option.options, name) {
+                    return Some(option);
+                }
+            }
+            _ if option.name == name => return Some(option),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum InteractionKind {
+    Ping = 1,
+    ApplicationCommand = 2,
+    MessageComponent = 3,
+    ApplicationCommandAutocomplete = 4,
+    ModalSubmit = 5,
+}
+
+impl Interaction {
+    /// Returns true if a response was already sent for this interaction
+    pub fn is_responded(&self) -> bool {
+        self.responded.load(Ordering::Relaxed)
+    }
+
+    /// Shortcut for [`HttpClient.create_interaction_response`], it will fail with
+    /// [`PandaError::InteractionAlreadyResponded`] if this interaction was already acknowledged
+    ///
+    /// [`HttpClient.create_interaction_response`]: ../../../struct.HttpClient.html#method.create_interaction_response
+    /// [`PandaError::InteractionAlreadyResponded`]: ../../../enum.PandaError.html#variant.InteractionAlreadyResponded
+    pub async fn respond(&self, http: &HttpClient, response: InteractionResponse) -> Result<()> {
+        // Mark it before sending, so two concurrent responses can't be sent
+        if self.responded.swap(true, Ordering::Relaxed) {
+            return Err(PandaError::InteractionAlreadyResponded);
+        }
+
+        let result = http.create_interaction_response(&self.id, &self.token, response).await;
+
+        // If the request failed, the interaction can still be acknowledged
+        if result.is_err() {
+            self.responded.store(false, Ordering::Relaxed);
+        }
+
+        result
+    }
+
+    /// Acknowledge a component interaction, the message can be edited later.
+    /// The user doesn't see a loading state
+    pub async fn defer(&self, http: &HttpClient) -> Result<()> {
+        self.respond(http, deferred_update_response()).await
+    }
+
+    /// Acknowledge a component interaction, the user sees an ephemeral loading state
+    /// that can be edited later
+    pub async fn defer_ephemeral(&self, http: &HttpClient) -> Result<()> {
+        self.respond(http, deferred_ephemeral_response()).await
+    }
+
+    /// Open a [`Modal`] as the interaction response, the values are received with a
+    /// `ModalSubmit` interaction. It can't be the response of another modal submit
+    ///
+    /// [`Modal`]: ../../utils/builders/struct.Modal.html
+    pub async fn open_modal(&self, http: &HttpClient, modal: Modal) -> Result<()> {
+        modal.validate()?;
+
+        let mut response = InteractionResponse::new(InteractionResponseKind::Modal);
+        response.data = Some(serde_json::to_value(modal)?);
+
+        self.respond(http, response).await
+    }
+
+    /// Edit the message the component was attached to, as the interaction response
+    pub async fn update(&self, http: &HttpClient, builder: MessageEdit) -> Result<()> {
+        let mut response = InteractionResponse::new(InteractionResponseKind::UpdateMessage);
+        response.data = Some(serde_json::to_value(builder)?);
+
+        self.respond(http, response).await
+    }
+}
+
+/// The response of [`Interaction::defer`]
+///
+/// [`Interaction::defer`]: struct.Interaction.html#method.defer
+fn deferred_update_response() -> InteractionResponse {
+    InteractionResponse::new(InteractionResponseKind::DeferredUpdateMessage)
+}
+
+/// The response of [`Interaction::defer_ephemeral`]
+///
+/// [`Interaction::defer_ephemeral`]: struct.Interaction.html#method.defer_ephemeral
+fn deferred_ephemeral_response() -> InteractionResponse {
+    let mut response = InteractionResponse::new(InteractionResponseKind::DeferredChannelMessageWithSource);
+    response.data = Some(serde_json::json!({ "flags": EPHEMERAL_FLAG }));
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::{json, Value};
+
+    #[test]
+    fn deferred_responses() {
+        assert_eq!(serde_json::to_value(deferred_update_response()).unwrap(), json!({"type": 6}));
+        assert_eq!(
+            serde_json::to_value(deferred_ephemeral_response()).unwrap(),
+            json!({"type": 5, "data": {"flags": 64}})
+        );
+    }
+
+    #[test]
+    fn interaction_is_not_responded() {
+        let interaction: Interaction = serde_json::from_value(json!({
+            "id": "1",
+            "application_id": "2",
+            "type": 3,
+            "data": {"custom_id": "button", "component_type": 2},
+            "token": "token",
+            "version": 1
+        }))
+        .unwrap();
+
+        assert_eq!(interaction.kind, InteractionKind::MessageComponent);
+        assert!(!interaction.is_responded());
+    }
+
+    fn command_data(options: Value) -> InteractionData {
+        serde_json::from_value(json!({
+            "id": "10",
+            "name": "settings",
+            "options": options,
+            "resolved": {
+                "users": {"20": {"id": "20", "username": "user", "discriminator": "0001", "avatar": null}},
+                "channels": {"30": {"id": "30", "name": "general", "type": 0}}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn typed_option_getters() {
+        let data = command_data(json!([
+            {"name": "name", "type": 3, "value": "panda"},
+            {"name": "count", "type": 4, "value": 5},
+            {"name": "ratio", "type": 10, "value": 0.5},
+            {"name": "enabled", "type": 5, "value": true},
+            {"name": "user", "type": 6, "value": "20"},
+            {"name": "channel", "type": 7, "value": "30"}
+        ]));
+
+        assert_eq!(data.get_string("name"), Some("panda"));
+        assert_eq!(data.get_integer("count"), Some(5));
+        assert_eq!(data.get_number("ratio"), Some(0.5));
+        assert_eq!(data.get_boolean("enabled"), Some(true));
+        assert_eq!(data.get_user("user").unwrap().username, "user");
+        assert_eq!(data.get_channel("channel").unwrap().name.as_deref(), Some("general"));
+
+        // Missing options, or with another type
+        assert_eq!(data.get_string("missing"), None);
+        assert_eq!(data.get_integer("name"), None);
+    }
+
+    #[test]
+    fn options_of_sub_commands() {
+        let data = command_data(json!([{
+            "name": "group",
+            "type": 2,
+            "options": [{
+                "name": "set",
+                "type": 1,
+                "options": [{"name": "count", "type": 4, "value": 7}]
+            }]
+        }]));
+
+        assert_eq!(data.get_integer("count"), Some(7));
+        assert!(data.option("set").is_none());
+    }
+
+    #[test]
+    fn modal_submit_values() {
+        let data: InteractionData = serde_json::from_value(json!({
+            "custom_id": "feedback",
+            "components": [
+                {"type": 1, "components": [{"type": 4, "custom_id": "title", "value": "Great"}]},
+                {"type": 1, "components": [{"type": 4, "custom_id": "body", "value": ""}]}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(data.text_input_value("title"), Some("Great"));
+        assert_eq!(data.text_input_value("body"), Some(""));
+        assert_eq!(data.text_input_value("missing"), None);
+    }
+}
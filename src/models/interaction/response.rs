@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_repr::*;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+/// The response sent to Discord to acknowledge an interaction
+/// [Discord Documentation](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object)
+pub struct InteractionResponse {
+    /// Type of the response
+    #[serde(rename = "type")]
+    pub kind: InteractionResponseKind,
+
+    /// Optional response message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+#[derive(Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum InteractionResponseKind {
+    Pong = 1,
+    ChannelMessageWithSource = 4,
+    DeferredChannelMessageWithSource = 5,
+    DeferredUpdateMessage = 6,
+    UpdateMessage = 7,
+    ApplicationCommandAutocompleteResult = 8,
+    Modal = 9,
+}
+
+impl InteractionResponse {
+    pub fn new(kind: InteractionResponseKind) -> Self {
+        InteractionResponse { kind, data: None }
+    }
+}
@@ -1,14 +1,79 @@
-use super::{Guild, Channel, User};
+use super::{PartialChannel, PartialGuild, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Invite {
     pub code: String,
-    pub guild: Option<Guild>,
-    pub channel: Channel,
+    pub guild: Option<PartialGuild>,
+    pub channel: PartialChannel,
     pub inviter: Option<User>,
     pub target_user: Option<User>,
     // target_user_type always 1
     pub approximate_presence_count: Option<u64>,
-    pub approximate_member_count: u64
-}
\ No newline at end of file
+    pub approximate_member_count: Option<u64>,
+
+    // Metadata, only sent when listing invites of a channel or guild
+    /// Number of times this invite has been used
+    pub uses: Option<u64>,
+    /// Max number of times this invite can be used
+    pub max_uses: Option<u64>,
+    /// Duration (in seconds) after which the invite expires
+    pub max_age: Option<u64>,
+    /// Whether this invite only grants temporary membership
+    pub temporary: Option<bool>,
+    /// When this invite was created, as string
+    pub created_at: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::channel::ChannelKind;
+
+    use serde_json::json;
+
+    #[test]
+    fn invite_with_partial_channel() {
+        let invite: Invite = serde_json::from_value(json!({
+            "code": "0vCdhLbwjZZTWZLD",
+            "channel": {"id": "165176875973476352", "name": "illuminati", "type": 0}
+        }))
+        .unwrap();
+
+        assert_eq!(invite.channel.name.as_deref(), Some("illuminati"));
+        assert_eq!(invite.channel.kind, ChannelKind::GuildText);
+        assert!(invite.guild.is_none());
+        assert!(invite.uses.is_none());
+    }
+
+    #[test]
+    fn invite_with_metadata() {
+        let invite: Invite = serde_json::from_value(json!({
+            "code": "0vCdhLbwjZZTWZLD",
+            "guild": {
+                "id": "165176875973476352",
+                "name": "CS:GO Fraggers Only",
+                "splash": null,
+                "banner": null,
+                "description": null,
+                "icon": null,
+                "features": [],
+                "verification_level": 2,
+                "vanity_url_code": null
+            },
+            "channel": {"id": "165176875973476352", "name": "illuminati", "type": 0},
+            "uses": 0,
+            "max_uses": 0,
+            "max_age": 0,
+            "temporary": false,
+            "created_at": "2016-03-31T19:15:39.954000+00:00"
+        }))
+        .unwrap();
+
+        let guild = invite.guild.unwrap();
+        assert_eq!(guild.verification_level, Some(2));
+        assert_eq!(guild.owner, None);
+        assert_eq!(invite.uses, Some(0));
+        assert_eq!(invite.temporary, Some(false));
+    }
+}
@@ -11,17 +11,30 @@ pub mod gateway;
 #[doc(inline)]
 pub mod guild;
 #[doc(inline)]
+pub mod interaction;
+#[doc(inline)]
+pub mod oauth2;
+#[doc(inline)]
+pub mod sticker;
+#[doc(inline)]
 pub mod user;
 #[doc(inline)]
 pub mod voice;
 #[doc(inline)]
 pub mod invite;
 
+mod id;
+
 // Re-export all models
 pub use channel::*;
 pub use emoji::*;
 pub use gateway::*;
 pub use guild::*;
+pub use interaction::*;
+pub use oauth2::*;
+pub use sticker::*;
 pub use user::*;
 pub use voice::*;
 pub use invite::*;
+
+pub use id::IntoId;
@@ -0,0 +1,45 @@
+//! OAuth2 related models
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// The token returned by an OAuth2 code exchange or refresh.
+/// [Discord Documentation](https://discord.com/developers/docs/topics/oauth2#authorization-code-grant-access-token-response)
+pub struct AccessToken {
+    pub access_token: String,
+    /// Usually "Bearer"
+    pub token_type: String,
+    /// Seconds until the access token expires
+    pub expires_in: u64,
+    pub refresh_token: String,
+    /// Granted scopes, separated by spaces
+    pub scope: String,
+}
+
+impl AccessToken {
+    /// Returns the granted scopes
+    pub fn scopes(&self) -> Vec<&str> {
+        self.scope.split_whitespace().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn access_token_scopes() {
+        let token: AccessToken = serde_json::from_value(json!({
+            "access_token": "6qrZcUqja7812RVdnEKjpzOL4CvHBFG",
+            "token_type": "Bearer",
+            "expires_in": 604800,
+            "refresh_token": "D43f5y0ahjqew82jZ4NViEr2YafMKhue",
+            "scope": "identify guilds"
+        }))
+        .unwrap();
+
+        assert_eq!(token.scopes(), vec!["identify", "guilds"]);
+    }
+}
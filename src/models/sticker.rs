@@ -0,0 +1,48 @@
+//! Sticker related models
+
+use crate::models::user::User;
+use serde::{Deserialize, Serialize};
+use serde_repr::*;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+/// [Discord Documentation](https://discord.com/developers/docs/resources/sticker#sticker-object)
+pub struct Sticker {
+    pub id: String,
+    pub pack_id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    /// Autocomplete/suggestion tags, separated by comma
+    pub tags: String,
+    #[serde(rename = "type")]
+    pub kind: Option<StickerKind>,
+    pub format_type: StickerFormat,
+    pub available: Option<bool>,
+    pub guild_id: Option<String>,
+    pub user: Option<User>,
+    pub sort_value: Option<u64>,
+}
+
+/// The smallest representation of a sticker, sent with messages
+/// [Discord Documentation](https://discord.com/developers/docs/resources/sticker#sticker-item-object)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StickerItem {
+    pub id: String,
+    pub name: String,
+    pub format_type: StickerFormat,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum StickerKind {
+    Standard = 1,
+    Guild = 2,
+}
+
+#[derive(Clone, Debug, Deserialize_repr, Serialize_repr, PartialEq)]
+#[repr(u8)]
+pub enum StickerFormat {
+    Png = 1,
+    Apng = 2,
+    Lottie = 3,
+    Gif = 4,
+}
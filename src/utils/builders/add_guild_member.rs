@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AddGuildMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deaf: Option<bool>,
+}
+
+impl AddGuildMember {
+    pub fn new() -> Self {
+        AddGuildMember::default()
+    }
+
+    pub fn nick(mut self, nick: impl Into<String>) -> Self {
+        self.nick = Some(nick.into());
+
+        self
+    }
+
+    pub fn role(mut self, role_id: impl Into<String>) -> Self {
+        if let Some(vec) = &mut self.roles {
+            vec.push(role_id.into());
+        } else {
+            self.roles = Some(vec![role_id.into()]);
+        }
+
+        self
+    }
+
+    pub fn mute(mut self, mute: bool) -> Self {
+        self.mute = Some(mute);
+
+        self
+    }
+
+    pub fn deaf(mut self, deaf: bool) -> Self {
+        self.deaf = Some(deaf);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_add_guild_member() {
+        assert_eq!(serde_json::to_value(AddGuildMember::new()).unwrap(), json!({}));
+
+        let builder = AddGuildMember::new().nick("panda").role("1").role("2").mute(true);
+        assert_eq!(
+            serde_json::to_value(builder).unwrap(),
+            json!({"nick": "panda", "roles": ["1", "2"], "mute": true})
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use crate::error::{PandaError, Result};
+
+use serde::{Deserialize, Serialize};
+
+/// Controls which mentions of a message will notify users.
+/// [Discord Documentation](https://discord.com/developers/docs/resources/channel#allowed-mentions-object)
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AllowedMentions {
+    parse: Vec<MentionKind>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    users: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+    #[serde(default)]
+    replied_user: bool,
+}
+
+/// Discord limit of ids in users and roles
+const MAX_IDS: usize = 100;
+
+/// The mention types that Discord parses from the message content
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionKind {
+    /// Role mentions
+    Roles,
+
+    /// User mentions
+    Users,
+
+    /// @everyone and @here mentions
+    Everyone,
+}
+
+impl Default for AllowedMentions {
+    /// Same behaviour as Discord when no allowed mentions are sent, but the author of the
+    /// replied message is not pinged
+    fn default() -> Self {
+        AllowedMentions {
+            parse: vec![MentionKind::Users, MentionKind::Roles, MentionKind::Everyone],
+            users: vec![],
+            roles: vec![],
+            replied_user: false,
+        }
+    }
+}
+
+impl AllowedMentions {
+    pub fn new() -> Self {
+        AllowedMentions::default()
+    }
+
+    /// Nothing will be mentioned
+    pub fn none() -> Self {
+        AllowedMentions {
+            parse: vec![],
+            users: vec![],
+            roles: vec![],
+            replied_user: false,
+        }
+    }
+
+    /// Users, roles, @everyone and the replied user will be mentioned
+    pub fn all() -> Self {
+        AllowedMentions::default().replied_user(true)
+    }
+
+    /// Only the given users will be mentioned
+    pub fn users<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut mentions = AllowedMentions::none();
+        mentions.users = ids.into_iter().map(Into::into).collect();
+
+        mentions
+    }
+
+    /// Only the given roles will be mentioned
+    pub fn roles<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut mentions = AllowedMentions::none();
+        mentions.roles = ids.into_iter().map(Into::into).collect();
+
+        mentions
+    }
+
+    /// Allow all mentions of this kind
+    pub fn parse(mut self, kind: MentionKind) -> Self {
+        if !self.parse.contains(&kind) {
+            self.parse.push(kind);
+        }
+
+        self
+    }
+
+    /// Allow the mention of this user, it can't be used with [`MentionKind::Users`] so
+    /// it's removed, and only the listed users will be mentioned
+    ///
+    /// [`MentionKind::Users`]: enum.MentionKind.html#variant.Users
+    pub fn user(mut self, user_id: impl Into<String>) -> Self {
+        self.parse.retain(|kind| *kind != MentionKind::Users);
+        self.users.push(user_id.into());
+
+        self
+    }
+
+    /// Allow the mention of this role, it can't be used with [`MentionKind::Roles`] so
+    /// it's removed, and only the listed roles will be mentioned
+    ///
+    /// [`MentionKind::Roles`]: enum.MentionKind.html#variant.Roles
+    pub fn role(mut self, role_id: impl Into<String>) -> Self {
+        self.parse.retain(|kind| *kind != MentionKind::Roles);
+        self.roles.push(role_id.into());
+
+        self
+    }
+
+    /// Whether to mention the author of the message being replied to. Default false
+    pub fn replied_user(mut self, replied_user: bool) -> Self {
+        self.replied_user = replied_user;
+
+        self
+    }
+
+    /// Check Discord rules, users and roles can't be used with their parse kind,
+    /// and they can have up to 100 ids
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.parse.contains(&MentionKind::Users) && !self.users.is_empty() {
+            return Err(PandaError::Validation {
+                field: "allowed_mentions.users",
+                reason: "can't be used with parse users".into(),
+            });
+        }
+
+        if self.parse.contains(&MentionKind::Roles) && !self.roles.is_empty() {
+            return Err(PandaError::Validation {
+                field: "allowed_mentions.roles",
+                reason: "can't be used with parse roles".into(),
+            });
+        }
+
+        if self.users.len() > MAX_IDS {
+            return Err(PandaError::Validation {
+                field: "allowed_mentions.users",
+                reason: format!("it can have up to {} ids", MAX_IDS),
+            });
+        }
+
+        if self.roles.len() > MAX_IDS {
+            return Err(PandaError::Validation {
+                field: "allowed_mentions.roles",
+                reason: format!("it can have up to {} ids", MAX_IDS),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_constructors() {
+        assert_eq!(
+            serde_json::to_value(AllowedMentions::none()).unwrap(),
+            json!({"parse": [], "replied_user": false})
+        );
+        assert_eq!(
+            serde_json::to_value(AllowedMentions::all()).unwrap(),
+            json!({"parse": ["users", "roles", "everyone"], "replied_user": true})
+        );
+        assert_eq!(
+            serde_json::to_value(AllowedMentions::users(vec!["1", "2"])).unwrap(),
+            json!({"parse": [], "users": ["1", "2"], "replied_user": false})
+        );
+        assert_eq!(
+            serde_json::to_value(AllowedMentions::roles(vec!["3"])).unwrap(),
+            json!({"parse": [], "roles": ["3"], "replied_user": false})
+        );
+    }
+
+    #[test]
+    fn user_and_role_remove_their_parse_kind() {
+        let mentions = AllowedMentions::new().user("1").role("2");
+
+        assert_eq!(mentions.parse, vec![MentionKind::Everyone]);
+        assert!(mentions.validate().is_ok());
+    }
+
+    #[test]
+    fn parse_is_not_repeated() {
+        let mentions = AllowedMentions::none().parse(MentionKind::Everyone).parse(MentionKind::Everyone);
+        assert_eq!(mentions.parse, vec![MentionKind::Everyone]);
+    }
+
+    #[test]
+    fn reject_ids_with_their_parse_kind() {
+        let mentions = AllowedMentions::users(vec!["1"]).parse(MentionKind::Users);
+        assert!(matches!(
+            mentions.validate(),
+            Err(PandaError::Validation { field: "allowed_mentions.users", .. })
+        ));
+
+        let mentions = AllowedMentions::roles(vec!["1"]).parse(MentionKind::Roles);
+        assert!(matches!(
+            mentions.validate(),
+            Err(PandaError::Validation { field: "allowed_mentions.roles", .. })
+        ));
+    }
+
+    #[test]
+    fn reject_too_many_ids() {
+        let ids: Vec<String> = (0..=MAX_IDS).map(|id| id.to_string()).collect();
+        assert!(AllowedMentions::users(ids.clone()).validate().is_err());
+        assert!(AllowedMentions::roles(ids).validate().is_err());
+
+        let ids: Vec<String> = (0..MAX_IDS).map(|id| id.to_string()).collect();
+        assert!(AllowedMentions::users(ids).validate().is_ok());
+    }
+}
@@ -0,0 +1,15 @@
+/// A file to upload with a message
+#[derive(Clone, Debug)]
+pub struct AttachmentFile {
+    pub(crate) filename: String,
+    pub(crate) data: Vec<u8>,
+}
+
+impl AttachmentFile {
+    pub fn new(filename: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        AttachmentFile {
+            filename: filename.into(),
+            data: data.into(),
+        }
+    }
+}
@@ -1,17 +1,28 @@
 use crate::models::channel::Overwrite;
 use serde::{Deserialize, Serialize};
 
+/// Unset fields are not sent, so Discord keeps their current value. Fields that can be
+/// cleared are `Option<Option<T>>`, where `Some(None)` is sent as null
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct ChannelEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
     name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     position: Option<u64>,
-    topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     nsfw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     rate_limit_per_user: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     user_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     permission_overwrites: Option<Vec<Overwrite>>,
-    parent_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Option<u64>>,
 }
 
 impl ChannelEdit {
@@ -32,7 +43,14 @@ impl ChannelEdit {
     }
 
     pub fn topic(mut self, topic: impl Into<String>) -> Self {
-        self.topic = Some(topic.into());
+        self.topic = Some(Some(topic.into()));
+
+        self
+    }
+
+    /// Remove the topic of the channel
+    pub fn clear_topic(mut self) -> Self {
+        self.topic = Some(None);
 
         self
     }
@@ -72,8 +90,45 @@ impl ChannelEdit {
     }
 
     pub fn parent_id(mut self, parent_id: u64) -> Self {
-        self.parent_id = Some(parent_id);
+        self.parent_id = Some(Some(parent_id));
+
+        self
+    }
+
+    /// Move the channel out of its category
+    pub fn clear_parent_id(mut self) -> Self {
+        self.parent_id = Some(None);
 
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn unset_fields_are_omitted() {
+        assert_eq!(serde_json::to_value(ChannelEdit::new()).unwrap(), json!({}));
+
+        let edit = ChannelEdit::new().name("general").position(2);
+        assert_eq!(serde_json::to_value(edit).unwrap(), json!({"name": "general", "position": 2}));
+    }
+
+    #[test]
+    fn cleared_fields_are_null() {
+        let edit = ChannelEdit::new().clear_topic().clear_parent_id();
+        assert_eq!(
+            serde_json::to_value(edit).unwrap(),
+            json!({"topic": null, "parent_id": null})
+        );
+
+        let edit = ChannelEdit::new().topic("news").parent_id(10);
+        assert_eq!(
+            serde_json::to_value(edit).unwrap(),
+            json!({"topic": "news", "parent_id": 10})
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+/// The new position of a channel, used to reorder the channels of a guild
+#[derive(Debug, Serialize)]
+pub struct ChannelPosition {
+    pub(crate) id: String,
+    position: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_permissions: Option<bool>,
+}
+
+impl ChannelPosition {
+    pub fn new(channel_id: impl Into<String>, position: u64) -> Self {
+        ChannelPosition {
+            id: channel_id.into(),
+            position,
+            parent_id: None,
+            lock_permissions: None,
+        }
+    }
+
+    /// Move the channel to the given category
+    pub fn parent_id(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+
+        self
+    }
+
+    /// Sync the permission overwrites with the new category
+    pub fn lock_permissions(mut self, lock_permissions: bool) -> Self {
+        self.lock_permissions = Some(lock_permissions);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_channel_positions() {
+        let positions = vec![
+            ChannelPosition::new("1", 0),
+            ChannelPosition::new("2", 1).parent_id("3").lock_permissions(true),
+        ];
+
+        assert_eq!(
+            serde_json::to_value(positions).unwrap(),
+            json!([
+                {"id": "1", "position": 0},
+                {"id": "2", "position": 1, "parent_id": "3", "lock_permissions": true}
+            ])
+        );
+    }
+}
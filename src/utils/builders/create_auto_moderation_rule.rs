@@ -0,0 +1,158 @@
+use crate::{
+    error::{PandaError, Result},
+    models::guild::{AutoModerationAction, AutoModerationEventType, AutoModerationTriggerMetadata, AutoModerationTriggerType},
+};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct CreateAutoModerationRule {
+    name: String,
+    event_type: AutoModerationEventType,
+    trigger_type: AutoModerationTriggerType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trigger_metadata: Option<AutoModerationTriggerMetadata>,
+    actions: Vec<AutoModerationAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exempt_roles: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exempt_channels: Vec<String>,
+}
+
+impl CreateAutoModerationRule {
+    /// Create a new rule builder, the event type is `MessageSend`. At least one action is needed
+    pub fn new(name: impl Into<String>, trigger_type: AutoModerationTriggerType) -> Self {
+        CreateAutoModerationRule {
+            name: name.into(),
+            event_type: AutoModerationEventType::MessageSend,
+            trigger_type,
+            trigger_metadata: None,
+            actions: Vec::new(),
+            enabled: None,
+            exempt_roles: Vec::new(),
+            exempt_channels: Vec::new(),
+        }
+    }
+
+    pub fn trigger_metadata(mut self, metadata: AutoModerationTriggerMetadata) -> Self {
+        self.trigger_metadata = Some(metadata);
+
+        self
+    }
+
+    pub fn action(mut self, action: AutoModerationAction) -> Self {
+        self.actions.push(action);
+
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+
+        self
+    }
+
+    pub fn exempt_role(mut self, role_id: impl Into<String>) -> Self {
+        self.exempt_roles.push(role_id.into());
+
+        self
+    }
+
+    pub fn exempt_channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.exempt_channels.push(channel_id.into());
+
+        self
+    }
+
+    /// Check Discord limits before creating the rule
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.name.is_empty() {
+            return Err(PandaError::Validation {
+                field: "name",
+                reason: "it can't be empty".into(),
+            });
+        }
+
+        if self.actions.is_empty() {
+            return Err(PandaError::Validation {
+                field: "actions",
+                reason: "at least one action is needed".into(),
+            });
+        }
+
+        if self.exempt_roles.len() > 20 {
+            return Err(PandaError::Validation {
+                field: "exempt_roles",
+                reason: "it can't have more than 20 roles".into(),
+            });
+        }
+
+        if self.exempt_channels.len() > 50 {
+            return Err(PandaError::Validation {
+                field: "exempt_channels",
+                reason: "it can't have more than 50 channels".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_rule() {
+        let rule = CreateAutoModerationRule::new("no spam", AutoModerationTriggerType::MentionSpam)
+            .trigger_metadata(AutoModerationTriggerMetadata {
+                mention_total_limit: Some(5),
+                ..Default::default()
+            })
+            .action(AutoModerationAction::block_message(None))
+            .exempt_role("323456789123456789");
+
+        assert_eq!(
+            serde_json::to_value(rule).unwrap(),
+            json!({
+                "name": "no spam",
+                "event_type": 1,
+                "trigger_type": 5,
+                "trigger_metadata": {"mention_total_limit": 5},
+                "actions": [{"type": 1}],
+                "exempt_roles": ["323456789123456789"]
+            })
+        );
+    }
+
+    #[test]
+    fn validate_rule() {
+        let rule = || CreateAutoModerationRule::new("no spam", AutoModerationTriggerType::Spam);
+
+        assert!(rule().action(AutoModerationAction::timeout(60)).validate().is_ok());
+        assert!(matches!(
+            rule().validate(),
+            Err(PandaError::Validation { field: "actions", .. })
+        ));
+        assert!(matches!(
+            CreateAutoModerationRule::new("", AutoModerationTriggerType::Spam)
+                .action(AutoModerationAction::timeout(60))
+                .validate(),
+            Err(PandaError::Validation { field: "name", .. })
+        ));
+
+        let too_many_roles = (0..21).fold(rule().action(AutoModerationAction::timeout(60)), |rule, id| {
+            rule.exempt_role(id.to_string())
+        });
+        assert!(matches!(
+            too_many_roles.validate(),
+            Err(PandaError::Validation {
+                field: "exempt_roles",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{
+    error::{PandaError, Result},
+    models::channel::{Component, ComponentKind, Embed, MessageReference, EMBED_TOTAL_LIMIT},
+};
+
+use super::{AllowedMentions, CreatePoll};
+
+use serde::Serialize;
+
+/// Max number of characters of the message content
+const CONTENT_LIMIT: usize = 2000;
+
+/// Max number of embeds of a message
+const EMBEDS_LIMIT: usize = 10;
+
+#[derive(Debug, Default, Serialize)]
+pub struct CreateMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embed: Option<Embed>,
+    tts: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_reference: Option<MessageReference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    components: Vec<Component>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll: Option<CreatePoll>,
+}
+
+impl CreateMessage {
+    pub fn new() -> Self {
+        CreateMessage::default()
+    }
+
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+
+        self
+    }
+
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embed = Some(embed);
+
+        self
+    }
+
+    /// Send the message as text to speech, it's read aloud to the users viewing the channel.
+    /// Requires the **SEND_TTS_MESSAGES** permission
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.tts = tts;
+
+        self
+    }
+
+    /// Reply to the given message. The author of the message will not be pinged, unless
+    /// [`AllowedMentions::replied_user`] is set to true
+    ///
+    /// [`AllowedMentions::replied_user`]: struct.AllowedMentions.html#method.replied_user
+    pub fn reply_to(mut self, message_id: impl Into<String>) -> Self {
+        self.message_reference = Some(MessageReference {
+            message_id: Some(message_id.into()),
+            ..Default::default()
+        });
+
+        if self.allowed_mentions.is_none() {
+            self.allowed_mentions = Some(AllowedMentions::default());
+        }
+
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+
+        self
+    }
+
+    /// Set the action rows of the message, see [`Component::action_row`]
+    ///
+    /// [`Component::action_row`]: ../../models/channel/struct.Component.html#method.action_row
+    pub fn components(mut self, rows: Vec<Component>) -> Self {
+        self.components = rows;
+
+        self
+    }
+
+    pub(crate) fn embeds_mut(&mut self) -> impl Iterator<Item = &mut Embed> {
+        self.embed.iter_mut()
+    }
+
+    /// Attach a poll to the message
+    pub fn poll(mut self, poll: CreatePoll) -> Self {
+        self.poll = Some(poll);
+
+        self
+    }
+
+    /// Check the builder values before sending it
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            allowed_mentions.validate()?;
+        }
+
+        if let Some(poll) = &self.poll {
+            poll.validate()?;
+        }
+
+        validate_components(&self.components)
+    }
+
+    /// Check the required fields and the Discord limits, when the strict validation is enabled
+    pub(crate) fn validate_strict(&self) -> Result<()> {
+        if self.content.is_none() && self.embed.is_none() && self.poll.is_none() && self.components.is_empty() {
+            return Err(PandaError::Validation {
+                field: "content",
+                reason: "a message needs a content, an embed, a poll or components".into(),
+            });
+        }
+
+        validate_message(self.content.as_deref(), self.embed.iter())
+    }
+}
+
+/// Check the length of the content, and the embeds limits of a message
+pub(crate) fn validate_message<'a>(content: Option<&str>, embeds: impl Iterator<Item = &'a Embed>) -> Result<()> {
+    if let Some(content) = content {
+        let len = content.chars().count();
+        if len > CONTENT_LIMIT {
+            return Err(PandaError::Validation {
+                field: "content",
+                reason: format!("it has {} characters, the limit is {}", len, CONTENT_LIMIT),
+            });
+        }
+    }
+
+    let mut count = 0;
+    let mut total_length = 0;
+    for embed in embeds {
+        embed.validate()?;
+        count += 1;
+        total_length += embed.text_length();
+    }
+
+    if count > EMBEDS_LIMIT {
+        return Err(PandaError::Validation {
+            field: "embeds",
+            reason: format!("a message can't have more than {} embeds", EMBEDS_LIMIT),
+        });
+    }
+
+    if total_length > EMBED_TOTAL_LIMIT {
+        return Err(PandaError::Validation {
+            field: "embeds",
+            reason: format!(
+                "the text of all the embeds can't be longer than {} characters",
+                EMBED_TOTAL_LIMIT
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check the layout of the action rows, there can be 5 rows, each one with 5 buttons
+/// or a single select menu
+fn validate_components(rows: &[Component]) -> Result<()> {
+    if rows.len() > 5 {
+        return Err(PandaError::Validation {
+            field: "components",
+            reason: "a message can't have more than 5 action rows".into(),
+        });
+    }
+
+    for row in rows {
+        if row.kind != ComponentKind::ActionRow {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "components must be inside an action row".into(),
+            });
+        }
+
+        let buttons = row.components.iter().filter(|c| c.kind == ComponentKind::Button).count();
+        let select_menus = row.components.iter().filter(|c| c.kind == ComponentKind::SelectMenu).count();
+
+        if buttons + select_menus != row.components.len() {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "an action row can only contain buttons and select menus".into(),
+            });
+        }
+
+        if row.components.is_empty() {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "an action row can't be empty".into(),
+            });
+        }
+
+        if buttons > 5 {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "an action row can't have more than 5 buttons".into(),
+            });
+        }
+
+        if select_menus > 0 && row.components.len() > 1 {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "a select menu must be alone in its action row".into(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::channel::{ButtonStyle, SelectOption};
+
+    use serde_json::json;
+
+    #[test]
+    fn reply_doesnt_ping_the_author() {
+        let message = CreateMessage::new().content("pong").reply_to("10");
+
+        assert_eq!(
+            serde_json::to_value(message).unwrap(),
+            json!({
+                "content": "pong",
+                "tts": false,
+                "message_reference": {"message_id": "10"},
+                "allowed_mentions": {"parse": ["users", "roles", "everyone"], "replied_user": false}
+            })
+        );
+    }
+
+    #[test]
+    fn reply_keeps_the_allowed_mentions() {
+        let message = CreateMessage::new()
+            .allowed_mentions(AllowedMentions::none().replied_user(true))
+            .reply_to("10");
+
+        assert_eq!(message.allowed_mentions, Some(AllowedMentions::none().replied_user(true)));
+    }
+
+    #[test]
+    fn serialize_tts() {
+        let message = CreateMessage::new().content("hello").tts(true);
+        assert_eq!(serde_json::to_value(message).unwrap(), json!({"content": "hello", "tts": true}));
+    }
+
+    fn buttons(n: usize) -> Vec<Component> {
+        (0..n)
+            .map(|i| Component::button(ButtonStyle::Primary, format!("button_{}", i), "Click"))
+            .collect()
+    }
+
+    fn select_menu() -> Component {
+        Component::select_menu("menu", vec![SelectOption::new("One", "1")])
+    }
+
+    fn is_components_error(result: Result<()>) -> bool {
+        matches!(result, Err(PandaError::Validation { field: "components", .. }))
+    }
+
+    #[test]
+    fn valid_components() {
+        let rows = vec![
+            Component::action_row(buttons(5)),
+            Component::action_row(vec![select_menu()]),
+            Component::action_row(vec![Component::link_button("https://example.com", "Open")]),
+        ];
+
+        assert!(validate_components(&rows).is_ok());
+        assert!(validate_components(&[]).is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_layouts() {
+        // Too many rows or buttons
+        assert!(is_components_error(validate_components(&vec![Component::action_row(buttons(1)); 6])));
+        assert!(is_components_error(validate_components(&[Component::action_row(buttons(6))])));
+
+        // Components outside of a row, and empty rows
+        assert!(is_components_error(validate_components(&buttons(1))));
+        assert!(is_components_error(validate_components(&[Component::action_row(vec![])])));
+
+        // A select menu must be alone
+        let mut row = buttons(1);
+        row.push(select_menu());
+        assert!(is_components_error(validate_components(&[Component::action_row(row)])));
+
+        // Rows can't be nested
+        let nested = Component::action_row(vec![Component::action_row(buttons(1))]);
+        assert!(is_components_error(validate_components(&[nested])));
+    }
+
+    #[test]
+    fn serialize_components() {
+        let message = CreateMessage::new().components(vec![Component::action_row(vec![
+            Component::button(ButtonStyle::Danger, "delete", "Delete").disabled(true),
+        ])]);
+
+        assert_eq!(
+            serde_json::to_value(message).unwrap(),
+            json!({
+                "tts": false,
+                "components": [{
+                    "type": 1,
+                    "components": [{"type": 2, "style": 4, "custom_id": "delete", "label": "Delete", "disabled": true}]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn strict_validation_needs_a_body() {
+        let result = CreateMessage::new().tts(true).validate_strict();
+        assert!(matches!(result, Err(PandaError::Validation { field: "content", .. })));
+
+        assert!(CreateMessage::new().content("hi").validate_strict().is_ok());
+        assert!(CreateMessage::new().embed(Embed::new()).validate_strict().is_ok());
+    }
+
+    #[test]
+    fn strict_validation_checks_the_limits() {
+        let content = "a".repeat(CONTENT_LIMIT + 1);
+        let result = CreateMessage::new().content(content).validate_strict();
+        assert!(matches!(result, Err(PandaError::Validation { field: "content", .. })));
+
+        let mut embed = Embed::new();
+        embed.set_title("a".repeat(300));
+        let result = CreateMessage::new().embed(embed).validate_strict();
+        assert!(matches!(result, Err(PandaError::Validation { field: "embed.title", .. })));
+    }
+
+    #[test]
+    fn validate_message_limits() {
+        let embeds = vec![Embed::new(); EMBEDS_LIMIT + 1];
+        let result = validate_message(None, embeds.iter());
+        assert!(matches!(result, Err(PandaError::Validation { field: "embeds", .. })));
+
+        // Each embed is valid, but the total text isn't
+        let mut embed = Embed::new();
+        embed.set_description("a".repeat(4000));
+        let embeds = vec![embed; 2];
+        let result = validate_message(None, embeds.iter());
+        assert!(matches!(result, Err(PandaError::Validation { field: "embeds", .. })));
+    }
+
+    #[test]
+    fn validate_embed_fields() {
+        let mut embed = Embed::new();
+        embed.add_field("", "value", false);
+        assert!(matches!(
+            embed.validate(),
+            Err(PandaError::Validation { field: "embed.fields", .. })
+        ));
+
+        let mut embed = Embed::new();
+        for _ in 0..26 {
+            embed.add_field("name", "value", true);
+        }
+        assert!(embed.validate().is_err());
+    }
+
+    #[test]
+    fn validate_checks_the_poll() {
+        assert!(CreateMessage::new().poll(CreatePoll::new("?")).validate().is_err());
+        assert!(CreateMessage::new().poll(CreatePoll::new("?").answer("yes")).validate_strict().is_ok());
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    error::{PandaError, Result},
+    models::channel::PollLayoutType,
+};
+
+use serde::Serialize;
+
+/// Max number of hours a poll can be open, 32 days
+const MAX_POLL_DURATION: u64 = 32 * 24;
+
+/// A poll sent with [`CreateMessage::poll`], a poll can't be edited after it was sent
+///
+/// [`CreateMessage::poll`]: struct.CreateMessage.html#method.poll
+#[derive(Debug, Serialize)]
+pub struct CreatePoll {
+    question: PollMediaBody,
+    answers: Vec<PollAnswerBody>,
+    duration: u64,
+    allow_multiselect: bool,
+    layout_type: PollLayoutType,
+}
+
+#[derive(Debug, Serialize)]
+struct PollMediaBody {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emoji: Option<PollEmojiBody>,
+}
+
+#[derive(Debug, Serialize)]
+struct PollAnswerBody {
+    poll_media: PollMediaBody,
+}
+
+/// Custom emojis are sent with the id, and unicode emojis with the name
+#[derive(Debug, Serialize)]
+struct PollEmojiBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl CreatePoll {
+    /// Create a poll with the given question, it's open for 24 hours
+    pub fn new(question: impl Into<String>) -> Self {
+        CreatePoll {
+            question: PollMediaBody {
+                text: question.into(),
+                emoji: None,
+            },
+            answers: vec![],
+            duration: 24,
+            allow_multiselect: false,
+            layout_type: PollLayoutType::Default,
+        }
+    }
+
+    pub fn answer(mut self, text: impl Into<String>) -> Self {
+        self.answers.push(PollAnswerBody {
+            poll_media: PollMediaBody {
+                text: text.into(),
+                emoji: None,
+            },
+        });
+
+        self
+    }
+
+    /// Add an answer with an emoji, it's the unicode emoji, or the id for custom emojis
+    pub fn answer_with_emoji(mut self, text: impl Into<String>, emoji: impl Into<String>) -> Self {
+        let emoji = emoji.into();
+        let emoji = if !emoji.is_empty() && emoji.chars().all(|c| c.is_ascii_digit()) {
+            PollEmojiBody {
+                id: Some(emoji),
+                name: None,
+            }
+        } else {
+            PollEmojiBody {
+                id: None,
+                name: Some(emoji),
+            }
+        };
+
+        self.answers.push(PollAnswerBody {
+            poll_media: PollMediaBody {
+                text: text.into(),
+                emoji: Some(emoji),
+            },
+        });
+
+        self
+    }
+
+    /// Set the number of hours the poll is open, up to 32 days. Default 24 hours
+    pub fn duration(mut self, hours: u64) -> Self {
+        self.duration = hours;
+
+        self
+    }
+
+    pub fn allow_multiselect(mut self, allow: bool) -> Self {
+        self.allow_multiselect = allow;
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.question.text.chars().count() > 300 {
+            return Err(PandaError::Validation {
+                field: "poll",
+                reason: "the question can't be longer than 300 characters".into(),
+            });
+        }
+
+        if self.answers.is_empty() || self.answers.len() > 10 {
+            return Err(PandaError::Validation {
+                field: "poll",
+                reason: "a poll must have between 1 and 10 answers".into(),
+            });
+        }
+
+        if self.answers.iter().any(|a| a.poll_media.text.chars().count() > 55) {
+            return Err(PandaError::Validation {
+                field: "poll",
+                reason: "an answer can't be longer than 55 characters".into(),
+            });
+        }
+
+        if self.duration == 0 || self.duration > MAX_POLL_DURATION {
+            return Err(PandaError::Validation {
+                field: "poll",
+                reason: "the duration must be between 1 and 768 hours".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_poll() {
+        let poll = CreatePoll::new("Best pet?")
+            .answer("Cat")
+            .answer_with_emoji("Dog", "🐶")
+            .answer_with_emoji("Panda", "41771983429993937")
+            .duration(48)
+            .allow_multiselect(true);
+
+        assert_eq!(
+            serde_json::to_value(poll).unwrap(),
+            json!({
+                "question": {"text": "Best pet?"},
+                "answers": [
+                    {"poll_media": {"text": "Cat"}},
+                    {"poll_media": {"text": "Dog", "emoji": {"name": "🐶"}}},
+                    {"poll_media": {"text": "Panda", "emoji": {"id": "41771983429993937"}}}
+                ],
+                "duration": 48,
+                "allow_multiselect": true,
+                "layout_type": 1
+            })
+        );
+    }
+
+    #[test]
+    fn validate_poll() {
+        assert!(CreatePoll::new("?").answer("yes").validate().is_ok());
+
+        // Without answers, or too many
+        assert!(CreatePoll::new("?").validate().is_err());
+        let poll = (0..11).fold(CreatePoll::new("?"), |poll, i| poll.answer(i.to_string()));
+        assert!(poll.validate().is_err());
+
+        // Long texts
+        assert!(CreatePoll::new("a".repeat(301)).answer("yes").validate().is_err());
+        assert!(CreatePoll::new("?").answer("a".repeat(56)).validate().is_err());
+
+        // Duration between 1 hour and 32 days
+        assert!(CreatePoll::new("?").answer("yes").duration(0).validate().is_err());
+        assert!(CreatePoll::new("?").answer("yes").duration(768).validate().is_ok());
+        assert!(CreatePoll::new("?").answer("yes").duration(769).validate().is_err());
+    }
+}
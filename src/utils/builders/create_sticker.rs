@@ -0,0 +1,103 @@
+use crate::error::{PandaError, Result};
+
+use super::AttachmentFile;
+
+/// Sticker files can't be bigger than 512 KB
+const MAX_STICKER_FILE_SIZE: usize = 512 * 1024;
+
+#[derive(Debug)]
+pub struct CreateSticker {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) tags: String,
+    pub(crate) file: AttachmentFile,
+}
+
+impl CreateSticker {
+    /// Create a new sticker builder, the file must be a PNG, APNG, GIF or Lottie JSON file
+    /// of max 512 KB. Tags are the autocomplete/suggestion tags, like the name of an emoji
+    pub fn new(name: impl Into<String>, tags: impl Into<String>, file: AttachmentFile) -> Self {
+        CreateSticker {
+            name: name.into(),
+            description: String::new(),
+            tags: tags.into(),
+            file,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+
+        self
+    }
+
+    /// Check Discord limits before uploading the sticker
+    pub(crate) fn validate(&self) -> Result<()> {
+        let name_len = self.name.chars().count();
+        if !(2..=30).contains(&name_len) {
+            return Err(PandaError::Validation {
+                field: "name",
+                reason: "it must be between 2 and 30 characters".into(),
+            });
+        }
+
+        let description_len = self.description.chars().count();
+        if description_len != 0 && !(2..=100).contains(&description_len) {
+            return Err(PandaError::Validation {
+                field: "description",
+                reason: "it must be empty or between 2 and 100 characters".into(),
+            });
+        }
+
+        let tags_len = self.tags.chars().count();
+        if tags_len == 0 || tags_len > 200 {
+            return Err(PandaError::Validation {
+                field: "tags",
+                reason: "it must be between 1 and 200 characters".into(),
+            });
+        }
+
+        if self.file.data.len() > MAX_STICKER_FILE_SIZE {
+            return Err(PandaError::Validation {
+                field: "file",
+                reason: "it can't be bigger than 512 KB".into(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sticker(name: &str, tags: &str, size: usize) -> CreateSticker {
+        CreateSticker::new(name, tags, AttachmentFile::new("panda.png", vec![0; size]))
+    }
+
+    #[test]
+    fn validate_sticker() {
+        assert!(sticker("panda", "🐼", 1024).validate().is_ok());
+        assert!(sticker("panda", "🐼", MAX_STICKER_FILE_SIZE).validate().is_ok());
+        assert!(matches!(
+            sticker("p", "🐼", 1024).validate(),
+            Err(PandaError::Validation { field: "name", .. })
+        ));
+        assert!(matches!(
+            sticker("panda", "", 1024).validate(),
+            Err(PandaError::Validation { field: "tags", .. })
+        ));
+        assert!(matches!(
+            sticker("panda", "🐼", 1024).description("p").validate(),
+            Err(PandaError::Validation {
+                field: "description",
+                ..
+            })
+        ));
+        assert!(matches!(
+            sticker("panda", "🐼", MAX_STICKER_FILE_SIZE + 1).validate(),
+            Err(PandaError::Validation { field: "file", .. })
+        ));
+    }
+}
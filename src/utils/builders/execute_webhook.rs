@@ -0,0 +1,152 @@
+use crate::{
+    error::{PandaError, Result},
+    models::channel::Embed,
+};
+
+use super::{create_message::validate_message, AllowedMentions};
+
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct ExecuteWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<String>,
+    tts: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<Embed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    // Sent in the query string
+    #[serde(skip)]
+    thread_id: Option<String>,
+}
+
+impl ExecuteWebhook {
+    pub fn new() -> Self {
+        ExecuteWebhook::default()
+    }
+
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+
+        self
+    }
+
+    /// Override the default username of the webhook
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+
+        self
+    }
+
+    /// Override the default avatar of the webhook
+    pub fn avatar_url(mut self, avatar_url: impl Into<String>) -> Self {
+        self.avatar_url = Some(avatar_url.into());
+
+        self
+    }
+
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.tts = tts;
+
+        self
+    }
+
+    /// Add an embed, up to 10 embeds
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embeds.push(embed);
+
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+
+        self
+    }
+
+    /// Send the message to a thread of the webhook channel
+    pub fn thread_id(mut self, thread_id: impl Into<String>) -> Self {
+        self.thread_id = Some(thread_id.into());
+
+        self
+    }
+
+    pub(crate) fn get_thread_id(&self) -> Option<&str> {
+        self.thread_id.as_deref()
+    }
+
+    pub(crate) fn embeds_mut(&mut self) -> impl Iterator<Item = &mut Embed> {
+        self.embeds.iter_mut()
+    }
+
+    /// Check the builder values before sending it
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            allowed_mentions.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Check the required fields and the Discord limits, when the strict validation is enabled
+    pub(crate) fn validate_strict(&self) -> Result<()> {
+        if self.content.is_none() && self.embeds.is_empty() {
+            return Err(PandaError::Validation {
+                field: "content",
+                reason: "a webhook message needs a content or an embed".into(),
+            });
+        }
+
+        if let Some(username) = &self.username {
+            let len = username.chars().count();
+            if !(1..=80).contains(&len) {
+                return Err(PandaError::Validation {
+                    field: "username",
+                    reason: "the username must be between 1 and 80 characters".into(),
+                });
+            }
+        }
+
+        validate_message(self.content.as_deref(), self.embeds.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_without_thread_id() {
+        let webhook = ExecuteWebhook::new().content("hi").username("panda").thread_id("1234");
+
+        assert_eq!(webhook.get_thread_id(), Some("1234"));
+        assert_eq!(
+            serde_json::to_value(webhook).unwrap(),
+            json!({"content": "hi", "username": "panda", "tts": false})
+        );
+    }
+
+    #[test]
+    fn validate_strict() {
+        assert!(ExecuteWebhook::new().content("hi").validate_strict().is_ok());
+        assert!(matches!(
+            ExecuteWebhook::new().username("panda").validate_strict(),
+            Err(PandaError::Validation { field: "content", .. })
+        ));
+        assert!(matches!(
+            ExecuteWebhook::new().content("hi").username("").validate_strict(),
+            Err(PandaError::Validation { field: "username", .. })
+        ));
+        assert!(matches!(
+            ExecuteWebhook::new().content("a".repeat(2001)).validate_strict(),
+            Err(PandaError::Validation { field: "content", .. })
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::error::{PandaError, Result};
+
+use serde::Serialize;
+
+/// The AFK timeouts accepted by Discord, in seconds
+const AFK_TIMEOUTS: [u64; 5] = [60, 300, 900, 1800, 3600];
+
+/// Used to modify a guild, unset fields are not sent so Discord keeps the current value
+#[derive(Debug, Default, Serialize)]
+pub struct GuildEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification_level: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_message_notifications: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explicit_content_filter: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    afk_channel_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    afk_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_channel_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_id: Option<String>,
+}
+
+impl GuildEdit {
+    pub fn new() -> Self {
+        GuildEdit::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+
+        self
+    }
+
+    /// Set the guild icon, it must be a base64 data uri like `data:image/png;base64,BASE64_DATA`
+    pub fn icon(mut self, data_uri: impl Into<String>) -> Self {
+        self.icon = Some(Some(data_uri.into()));
+
+        self
+    }
+
+    pub fn remove_icon(mut self) -> Self {
+        self.icon = Some(None);
+
+        self
+    }
+
+    /// From 0 (none) to 4 (very high), `None` resets it to the default
+    pub fn verification_level(mut self, level: Option<u64>) -> Self {
+        self.verification_level = Some(level);
+
+        self
+    }
+
+    /// 0 to notify all messages, 1 to notify only mentions, `None` resets it to the default
+    pub fn default_message_notifications(mut self, level: Option<u64>) -> Self {
+        self.default_message_notifications = Some(level);
+
+        self
+    }
+
+    /// From 0 (disabled) to 2 (all members), `None` resets it to the default
+    pub fn explicit_content_filter(mut self, level: Option<u64>) -> Self {
+        self.explicit_content_filter = Some(level);
+
+        self
+    }
+
+    /// Set the AFK voice channel, `None` disables it
+    pub fn afk_channel_id(mut self, channel_id: Option<String>) -> Self {
+        self.afk_channel_id = Some(channel_id);
+
+        self
+    }
+
+    /// Set the AFK timeout in seconds, it can be 60, 300, 900, 1800 or 3600
+    pub fn afk_timeout(mut self, seconds: u64) -> Self {
+        self.afk_timeout = Some(seconds);
+
+        self
+    }
+
+    /// Set the channel of the system messages, like member joins, `None` disables them
+    pub fn system_channel_id(mut self, channel_id: Option<String>) -> Self {
+        self.system_channel_id = Some(channel_id);
+
+        self
+    }
+
+    /// Transfer the ownership of the guild to the user, the bot must be the owner
+    pub fn owner_id(mut self, user_id: impl Into<String>) -> Self {
+        self.owner_id = Some(user_id.into());
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(Some(icon)) = &self.icon {
+            if !icon.starts_with("data:image/") || !icon.contains(";base64,") {
+                return Err(PandaError::Validation {
+                    field: "icon",
+                    reason: "it must be a base64 data uri, like data:image/png;base64,BASE64_DATA".into(),
+                });
+            }
+        }
+
+        if let Some(name) = &self.name {
+            let len = name.chars().count();
+            if !(2..=100).contains(&len) {
+                return Err(PandaError::Validation {
+                    field: "name",
+                    reason: format!("it must have between 2 and 100 characters, it has {}", len),
+                });
+            }
+        }
+
+        if let Some(timeout) = self.afk_timeout {
+            if !AFK_TIMEOUTS.contains(&timeout) {
+                return Err(PandaError::Validation {
+                    field: "afk_timeout",
+                    reason: format!("it must be one of {:?}, it's {}", AFK_TIMEOUTS, timeout),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_guild_edit() {
+        assert_eq!(serde_json::to_value(GuildEdit::new()).unwrap(), json!({}));
+
+        let edit = GuildEdit::new()
+            .name("pandas")
+            .remove_icon()
+            .verification_level(None)
+            .afk_channel_id(Some("81384788765712384".into()))
+            .afk_timeout(300);
+        assert_eq!(
+            serde_json::to_value(edit).unwrap(),
+            json!({
+                "name": "pandas",
+                "icon": null,
+                "verification_level": null,
+                "afk_channel_id": "81384788765712384",
+                "afk_timeout": 300
+            })
+        );
+    }
+
+    #[test]
+    fn validate_guild_edit() {
+        assert!(GuildEdit::new().name("pandas").afk_timeout(3600).validate().is_ok());
+        assert!(matches!(
+            GuildEdit::new().name("p").validate(),
+            Err(PandaError::Validation { field: "name", .. })
+        ));
+        assert!(matches!(
+            GuildEdit::new().afk_timeout(120).validate(),
+            Err(PandaError::Validation { field: "afk_timeout", .. })
+        ));
+        assert!(matches!(
+            GuildEdit::new().icon("icon.png").validate(),
+            Err(PandaError::Validation { field: "icon", .. })
+        ));
+    }
+}
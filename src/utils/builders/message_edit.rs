@@ -1,9 +1,33 @@
+use crate::{error::Result, models::channel::Embed};
+
+use super::{create_message::validate_message, AllowedMentions, AttachmentFile};
+
 use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct MessageEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
     content: Option<String>,
-    embed: Option<()>,
-    flags: u64, // TODO: SEE FLAGS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    embeds: Option<Vec<Embed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allowed_mentions: Option<AllowedMentions>,
+    /// The attachments to keep, if it's not set all attachments are kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attachments: Option<Vec<PartialAttachment>>,
+    #[serde(skip)]
+    files: Vec<AttachmentFile>,
+}
+
+/// An attachment sent in the attachments array, for a kept attachment it's the attachment id,
+/// and for a new file it's the index of the file
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) struct PartialAttachment {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
 }
 
 impl MessageEdit {
@@ -17,11 +41,135 @@ impl MessageEdit {
         self
     }
 
-    pub fn embed(self) {
-        unimplemented!()
+    /// Add an embed, the embeds of the message are replaced by the embeds of the builder
+    pub fn embed(mut self, embed: Embed) -> Self {
+        self.embeds.get_or_insert_with(Vec::new).push(embed);
+
+        self
+    }
+
+    /// Remove all embeds of the message
+    pub fn remove_embeds(mut self) -> Self {
+        self.embeds = Some(vec![]);
+
+        self
+    }
+
+    /// Set the message flags, like SUPPRESS_EMBEDS (1 << 2)
+    pub fn flags(mut self, flags: u64) -> Self {
+        self.flags = Some(flags);
+
+        self
+    }
+
+    pub fn allowed_mentions(mut self, allowed_mentions: AllowedMentions) -> Self {
+        self.allowed_mentions = Some(allowed_mentions);
+
+        self
+    }
+
+    /// Keep this attachment. Once an attachment is kept, all the attachments
+    /// that are not kept will be removed
+    pub fn keep_attachment(mut self, attachment_id: impl Into<String>) -> Self {
+        let attachment = PartialAttachment {
+            id: attachment_id.into(),
+            filename: None,
+        };
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+
+        self
+    }
+
+    /// Remove all the attachments of the message, new files are still uploaded
+    pub fn remove_attachments(mut self) -> Self {
+        self.attachments = Some(vec![]);
+
+        self
+    }
+
+    /// Upload a new file with the message
+    pub fn file(mut self, file: AttachmentFile) -> Self {
+        self.files.push(file);
+
+        self
+    }
+
+    pub(crate) fn embeds_mut(&mut self) -> impl Iterator<Item = &mut Embed> {
+        self.embeds.iter_mut().flatten()
+    }
+
+    /// Check the Discord limits of the content and embeds, when the strict validation is enabled
+    pub(crate) fn validate_strict(&self) -> Result<()> {
+        validate_message(self.content.as_deref(), self.embeds.iter().flatten())
+    }
+
+    /// Returns the files to upload, and the builder ready to be sent as payload.
+    /// When attachments are set, the new files need to be in the attachments array too
+    pub(crate) fn into_payload(mut self) -> Result<(Self, Vec<AttachmentFile>)> {
+        if let Some(allowed_mentions) = &self.allowed_mentions {
+            allowed_mentions.validate()?;
+        }
+
+        let files = std::mem::take(&mut self.files);
+
+        if let Some(attachments) = &mut self.attachments {
+            for (index, file) in files.iter().enumerate() {
+                attachments.push(PartialAttachment {
+                    id: index.to_string(),
+                    filename: Some(file.filename.clone()),
+                });
+            }
+        }
+
+        Ok((self, files))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn unset_fields_are_omitted() {
+        assert_eq!(serde_json::to_value(MessageEdit::new()).unwrap(), json!({}));
+
+        let edit = MessageEdit::new().content("edited");
+        assert_eq!(serde_json::to_value(edit).unwrap(), json!({"content": "edited"}));
     }
 
-    pub fn flags(self) {
-        unimplemented!()
+    #[test]
+    fn remove_embeds_and_attachments() {
+        let edit = MessageEdit::new().remove_embeds().remove_attachments();
+        assert_eq!(serde_json::to_value(edit).unwrap(), json!({"embeds": [], "attachments": []}));
+    }
+
+    #[test]
+    fn new_files_are_added_to_kept_attachments() {
+        let edit = MessageEdit::new()
+            .keep_attachment("100")
+            .file(AttachmentFile::new("a.png", vec![1, 2]))
+            .file(AttachmentFile::new("b.txt", "text"));
+        let (payload, files) = edit.into_payload().unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            json!({"attachments": [
+                {"id": "100"},
+                {"id": "0", "filename": "a.png"},
+                {"id": "1", "filename": "b.txt"}
+            ]})
+        );
+    }
+
+    #[test]
+    fn files_without_attachments_keep_all() {
+        let edit = MessageEdit::new().file(AttachmentFile::new("a.png", vec![1]));
+        let (payload, files) = edit.into_payload().unwrap();
+
+        assert_eq!(files[0].filename, "a.png");
+        assert_eq!(serde_json::to_value(payload).unwrap(), json!({}));
     }
 }
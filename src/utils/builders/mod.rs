@@ -1,5 +1,32 @@
+mod add_guild_member;
+mod allowed_mentions;
+mod attachment_file;
 mod channel_edit;
+mod channel_position;
+mod create_auto_moderation_rule;
+mod create_message;
+mod create_poll;
+mod create_sticker;
+mod execute_webhook;
+mod guild_edit;
 mod message_edit;
+mod modal;
+mod modify_onboarding;
+mod role_edit;
 
+pub use add_guild_member::AddGuildMember;
+pub use allowed_mentions::{AllowedMentions, MentionKind};
+pub use attachment_file::AttachmentFile;
 pub use channel_edit::ChannelEdit;
+pub use channel_position::ChannelPosition;
+pub use create_auto_moderation_rule::CreateAutoModerationRule;
+pub use create_message::CreateMessage;
+pub(crate) use create_message::validate_message;
+pub use create_poll::CreatePoll;
+pub use create_sticker::CreateSticker;
+pub use execute_webhook::ExecuteWebhook;
+pub use guild_edit::GuildEdit;
 pub use message_edit::MessageEdit;
+pub use modal::{Modal, TextInput};
+pub use modify_onboarding::ModifyOnboarding;
+pub use role_edit::RoleEdit;
@@ -0,0 +1,229 @@
+use crate::{
+    error::{PandaError, Result},
+    models::channel::{ComponentKind, TextInputStyle},
+};
+use serde::Serialize;
+
+/// A popup with text inputs, sent as the response of an interaction
+#[derive(Debug, Serialize)]
+pub struct Modal {
+    custom_id: String,
+    title: String,
+    components: Vec<ModalRow>,
+}
+
+// Each text input goes in its own action row
+#[derive(Debug, Serialize)]
+struct ModalRow {
+    #[serde(rename = "type")]
+    kind: ComponentKind,
+    components: Vec<TextInput>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TextInput {
+    #[serde(rename = "type")]
+    kind: ComponentKind,
+    custom_id: String,
+    style: TextInputStyle,
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    required: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    placeholder: Option<String>,
+}
+
+impl Modal {
+    /// Create a new modal, at least one text input must be added
+    pub fn new(custom_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Modal {
+            custom_id: custom_id.into(),
+            title: title.into(),
+            components: Vec::new(),
+        }
+    }
+
+    pub fn text_input(mut self, text_input: TextInput) -> Self {
+        self.components.push(ModalRow {
+            kind: ComponentKind::ActionRow,
+            components: vec![text_input],
+        });
+
+        self
+    }
+
+    /// Check Discord limits before sending the modal
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.custom_id.is_empty() || self.custom_id.chars().count() > 100 {
+            return Err(PandaError::Validation {
+                field: "custom_id",
+                reason: "it must be between 1 and 100 characters".into(),
+            });
+        }
+
+        if self.title.is_empty() || self.title.chars().count() > 45 {
+            return Err(PandaError::Validation {
+                field: "title",
+                reason: "it must be between 1 and 45 characters".into(),
+            });
+        }
+
+        if self.components.is_empty() || self.components.len() > 5 {
+            return Err(PandaError::Validation {
+                field: "components",
+                reason: "a modal must have between 1 and 5 text inputs".into(),
+            });
+        }
+
+        for text_input in self.components.iter().flat_map(|row| &row.components) {
+            text_input.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TextInput {
+    pub fn new(custom_id: impl Into<String>, style: TextInputStyle, label: impl Into<String>) -> Self {
+        TextInput {
+            kind: ComponentKind::TextInput,
+            custom_id: custom_id.into(),
+            style,
+            label: label.into(),
+            min_length: None,
+            max_length: None,
+            required: None,
+            value: None,
+            placeholder: None,
+        }
+    }
+
+    pub fn min_length(mut self, min_length: u64) -> Self {
+        self.min_length = Some(min_length);
+
+        self
+    }
+
+    pub fn max_length(mut self, max_length: u64) -> Self {
+        self.max_length = Some(max_length);
+
+        self
+    }
+
+    /// Text inputs are required by default
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+
+        self
+    }
+
+    /// Pre-filled value of the input
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.label.is_empty() || self.label.chars().count() > 45 {
+            return Err(PandaError::Validation {
+                field: "label",
+                reason: "it must be between 1 and 45 characters".into(),
+            });
+        }
+
+        if self.min_length.map_or(false, |min| min > 4000) {
+            return Err(PandaError::Validation {
+                field: "min_length",
+                reason: "it can't be bigger than 4000".into(),
+            });
+        }
+
+        if self.max_length.map_or(false, |max| max == 0 || max > 4000) {
+            return Err(PandaError::Validation {
+                field: "max_length",
+                reason: "it must be between 1 and 4000".into(),
+            });
+        }
+
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err(PandaError::Validation {
+                    field: "min_length",
+                    reason: "it can't be bigger than max_length".into(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn text_input() -> TextInput {
+        TextInput::new("body", TextInputStyle::Paragraph, "Feedback")
+    }
+
+    #[test]
+    fn serialize_modal() {
+        let modal = Modal::new("feedback", "Send feedback").text_input(text_input().max_length(500).required(false));
+
+        assert_eq!(
+            serde_json::to_value(modal).unwrap(),
+            json!({
+                "custom_id": "feedback",
+                "title": "Send feedback",
+                "components": [{
+                    "type": 1,
+                    "components": [{
+                        "type": 4,
+                        "custom_id": "body",
+                        "style": 2,
+                        "label": "Feedback",
+                        "max_length": 500,
+                        "required": false
+                    }]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn validate_modal() {
+        assert!(Modal::new("feedback", "Feedback").text_input(text_input()).validate().is_ok());
+
+        // Without text inputs, or with too many
+        assert!(Modal::new("feedback", "Feedback").validate().is_err());
+        let modal = (0..6).fold(Modal::new("feedback", "Feedback"), |modal, _| modal.text_input(text_input()));
+        assert!(modal.validate().is_err());
+
+        assert!(Modal::new("", "Feedback").text_input(text_input()).validate().is_err());
+        assert!(Modal::new("feedback", "a".repeat(46)).text_input(text_input()).validate().is_err());
+    }
+
+    #[test]
+    fn validate_text_input_lengths() {
+        assert!(text_input().min_length(10).max_length(100).validate().is_ok());
+        assert!(text_input().min_length(4001).validate().is_err());
+        assert!(text_input().max_length(0).validate().is_err());
+        assert!(text_input().min_length(100).max_length(10).validate().is_err());
+        assert!(TextInput::new("body", TextInputStyle::Short, "").validate().is_err());
+    }
+}
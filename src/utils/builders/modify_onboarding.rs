@@ -0,0 +1,74 @@
+use crate::models::guild::{OnboardingMode, OnboardingPrompt};
+
+use serde::Serialize;
+
+/// Unset fields are not sent, so Discord keeps their current value. The prompts replace
+/// all the prompts of the onboarding
+#[derive(Debug, Default, Serialize)]
+pub struct ModifyOnboarding {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompts: Option<Vec<OnboardingPrompt>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_channel_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<OnboardingMode>,
+}
+
+impl ModifyOnboarding {
+    pub fn new() -> Self {
+        ModifyOnboarding::default()
+    }
+
+    /// Add a prompt, the prompts of the onboarding are replaced by the prompts of the builder
+    pub fn prompt(mut self, prompt: OnboardingPrompt) -> Self {
+        self.prompts.get_or_insert_with(Vec::new).push(prompt);
+
+        self
+    }
+
+    /// Set the channels that members get opted into automatically
+    pub fn default_channels<I, S>(mut self, channel_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.default_channel_ids = Some(channel_ids.into_iter().map(Into::into).collect());
+
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+
+        self
+    }
+
+    pub fn mode(mut self, mode: OnboardingMode) -> Self {
+        self.mode = Some(mode);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_modify_onboarding() {
+        assert_eq!(serde_json::to_value(ModifyOnboarding::new()).unwrap(), json!({}));
+
+        let modify = ModifyOnboarding::new()
+            .default_channels(vec!["290926798999357250"])
+            .enabled(true)
+            .mode(OnboardingMode::Default);
+        assert_eq!(
+            serde_json::to_value(modify).unwrap(),
+            json!({"default_channel_ids": ["290926798999357250"], "enabled": true, "mode": 0})
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::{PandaError, Result};
+
+use serde::Serialize;
+
+/// Used to create and modify roles. Unset fields are not sent, so Discord uses the default
+/// value for a new role, and keeps the current value when modifying a role
+#[derive(Debug, Default, Serialize)]
+pub struct RoleEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hoist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unicode_emoji: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionable: Option<bool>,
+}
+
+impl RoleEdit {
+    pub fn new() -> Self {
+        RoleEdit::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+
+        self
+    }
+
+    pub fn permissions(mut self, permissions: u64) -> Self {
+        self.permissions = Some(permissions);
+
+        self
+    }
+
+    pub fn color(mut self, color: u64) -> Self {
+        self.color = Some(color);
+
+        self
+    }
+
+    /// Display the role members separately from online members
+    pub fn hoist(mut self, hoist: bool) -> Self {
+        self.hoist = Some(hoist);
+
+        self
+    }
+
+    /// Set the role icon, it must be a base64 data uri like `data:image/png;base64,BASE64_DATA`.
+    /// The guild needs the ROLE_ICONS feature
+    pub fn icon(mut self, data_uri: impl Into<String>) -> Self {
+        self.icon = Some(Some(data_uri.into()));
+
+        self
+    }
+
+    pub fn remove_icon(mut self) -> Self {
+        self.icon = Some(None);
+
+        self
+    }
+
+    /// Set an unicode emoji as the role icon
+    pub fn unicode_emoji(mut self, emoji: impl Into<String>) -> Self {
+        self.unicode_emoji = Some(Some(emoji.into()));
+
+        self
+    }
+
+    pub fn remove_unicode_emoji(mut self) -> Self {
+        self.unicode_emoji = Some(None);
+
+        self
+    }
+
+    pub fn mentionable(mut self, mentionable: bool) -> Self {
+        self.mentionable = Some(mentionable);
+
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(Some(icon)) = &self.icon {
+            if !icon.starts_with("data:image/") || !icon.contains(";base64,") {
+                return Err(PandaError::Validation {
+                    field: "icon",
+                    reason: "it must be a base64 data uri, like data:image/png;base64,BASE64_DATA".into(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn serialize_role_edit() {
+        assert_eq!(serde_json::to_value(RoleEdit::new()).unwrap(), json!({}));
+
+        let edit = RoleEdit::new().name("mods").color(0xff0000).unicode_emoji("🐼").remove_icon();
+        assert_eq!(
+            serde_json::to_value(edit).unwrap(),
+            json!({"name": "mods", "color": 0xff0000, "icon": null, "unicode_emoji": "🐼"})
+        );
+    }
+
+    #[test]
+    fn validate_icon() {
+        assert!(RoleEdit::new().icon("data:image/png;base64,iVBORw0KGgo=").validate().is_ok());
+        assert!(RoleEdit::new().remove_icon().validate().is_ok());
+        assert!(matches!(
+            RoleEdit::new().icon("https://example.com/icon.png").validate(),
+            Err(PandaError::Validation { field: "icon", .. })
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    error::{PandaError, Result},
+    http::HttpClient,
+    models::{
+        channel::{Channel, Message},
+        guild::Role,
+        user::User,
+    },
+};
+
+use futures::future::{BoxFuture, FutureExt};
+
+/// Converts a command argument to a type, the message is used to get the guild of the command.
+/// When the argument can't be converted it returns [`PandaError::InvalidArgument`], its message
+/// can be sent to the user.
+///
+/// ```rust,ignore
+/// Command::new("ban", |s, msg, args| async move {
+///     let user = User::from_arg(&s.http, &msg, &args[0]).await?;
+///     let days = i64::from_arg(&s.http, &msg, &args[1]).await?;
+///     Ok(())
+/// })
+/// ```
+///
+/// [`PandaError::InvalidArgument`]: ../../../enum.PandaError.html#variant.InvalidArgument
+pub trait FromArg: Sized {
+    fn from_arg<'a>(http: &'a HttpClient, msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>>;
+}
+
+impl FromArg for String {
+    fn from_arg<'a>(_http: &'a HttpClient, _msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        futures::future::ready(Ok(arg.to_string())).boxed()
+    }
+}
+
+impl FromArg for i64 {
+    fn from_arg<'a>(_http: &'a HttpClient, _msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        let res = arg.parse().map_err(|_| invalid(arg, "an integer"));
+        futures::future::ready(res).boxed()
+    }
+}
+
+impl FromArg for f64 {
+    fn from_arg<'a>(_http: &'a HttpClient, _msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        let res = arg.parse().map_err(|_| invalid(arg, "a number"));
+        futures::future::ready(res).boxed()
+    }
+}
+
+impl FromArg for User {
+    /// Accepts a mention (`<@id>` or `<@!id>`) or an id, the user is fetched
+    fn from_arg<'a>(http: &'a HttpClient, _msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        async move {
+            let id = parse_mention(arg, "<@!").or_else(|| parse_mention(arg, "<@"));
+            let id = id.ok_or_else(|| invalid(arg, "a user mention or id"))?;
+
+            http.get_user(id).await.map_err(|e| not_found(e, arg, "a user mention or id"))
+        }
+        .boxed()
+    }
+}
+
+impl FromArg for Channel {
+    /// Accepts a mention (`<#id>`) or an id, the channel is fetched
+    fn from_arg<'a>(http: &'a HttpClient, _msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        async move {
+            let id = parse_mention(arg, "<#").ok_or_else(|| invalid(arg, "a channel mention or id"))?;
+
+            http.get_channel(id)
+                .await
+                .map_err(|e| not_found(e, arg, "a channel mention or id"))
+        }
+        .boxed()
+    }
+}
+
+impl FromArg for Role {
+    /// Accepts a mention (`<@&id>`), an id or the role name, it's searched in the guild of the message
+    fn from_arg<'a>(http: &'a HttpClient, msg: &'a Message, arg: &'a str) -> BoxFuture<'a, Result<Self>> {
+        async move {
+            let guild_id = msg.guild_id.as_ref().ok_or_else(|| invalid(arg, "a role of a guild"))?;
+            let id = parse_mention(arg, "<@&");
+
+            let roles = http.get_guild_roles(guild_id).await?;
+
+            roles
+                .into_iter()
+                .find(|role| match id {
+                    Some(id) => role.id == id,
+                    None => role.name.eq_ignore_ascii_case(arg),
+                })
+                .ok_or_else(|| invalid(arg, "a role mention, id or name"))
+        }
+        .boxed()
+    }
+}
+
+/// Returns the id of a mention with the given start, like `<@`, or the argument if it is an id
+fn parse_mention<'a>(arg: &'a str, start: &str) -> Option<&'a str> {
+    let id = match arg.strip_prefix(start) {
+        Some(rest) => rest.strip_suffix('>')?,
+        None => arg,
+    };
+
+    if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+fn invalid(arg: &str, expected: &'static str) -> PandaError {
+    PandaError::InvalidArgument {
+        arg: arg.to_string(),
+        expected,
+    }
+}
+
+/// A 404 means that the argument was a valid id, but the object doesn't exist
+fn not_found(error: PandaError, arg: &str, expected: &'static str) -> PandaError {
+    match error {
+        PandaError::HttpInvalidParameters => invalid(arg, expected),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    fn message() -> Message {
+        serde_json::from_value(json!({
+            "id": "334385199974967042",
+            "channel_id": "290926798999357250",
+            "author": {
+                "id": "53908099506183680",
+                "username": "Mason",
+                "discriminator": "9999",
+                "avatar": null
+            },
+            "content": "!ban",
+            "timestamp": "2017-07-11T17:27:07.299000+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "pinned": false,
+            "type": 0
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_mentions() {
+        assert_eq!(parse_mention("<@!80351110224678912>", "<@!"), Some("80351110224678912"));
+        assert_eq!(parse_mention("<#41771983423143937>", "<#"), Some("41771983423143937"));
+        assert_eq!(parse_mention("80351110224678912", "<@"), Some("80351110224678912"));
+        assert_eq!(parse_mention("<#41771983423143937>", "<@"), None);
+        assert_eq!(parse_mention("<@80351110224678912", "<@"), None);
+        assert_eq!(parse_mention("<@>", "<@"), None);
+        assert_eq!(parse_mention("panda", "<@"), None);
+    }
+
+    #[test]
+    fn convert_numbers() {
+        let http = HttpClient::new("token");
+        let msg = message();
+
+        assert_eq!(block_on(i64::from_arg(&http, &msg, "-7")).unwrap(), -7);
+        assert_eq!(block_on(f64::from_arg(&http, &msg, "1.5")).unwrap(), 1.5);
+        assert_eq!(block_on(String::from_arg(&http, &msg, "panda")).unwrap(), "panda");
+        assert!(matches!(
+            block_on(i64::from_arg(&http, &msg, "1.5")),
+            Err(PandaError::InvalidArgument {
+                expected: "an integer",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_arguments_are_not_fetched() {
+        let http = HttpClient::new("token");
+        let msg = message();
+
+        assert!(matches!(
+            block_on(User::from_arg(&http, &msg, "panda")),
+            Err(PandaError::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            block_on(Channel::from_arg(&http, &msg, "<@80351110224678912>")),
+            Err(PandaError::InvalidArgument { .. })
+        ));
+        // Without guild the role can't be searched
+        assert!(matches!(
+            block_on(Role::from_arg(&http, &msg, "mods")),
+            Err(PandaError::InvalidArgument {
+                expected: "a role of a guild",
+                ..
+            })
+        ));
+    }
+}
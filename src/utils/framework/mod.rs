@@ -0,0 +1,276 @@
+//! Command framework, for bots that use prefixed commands like `!ping`
+
+mod args;
+
+pub use args::FromArg;
+
+use crate::{client::SessionData, models::channel::Message};
+
+use futures::future::{BoxFuture, FutureExt};
+use std::{error::Error, future::Future, sync::Arc};
+
+type CommandResult = Result<(), Box<dyn Error>>;
+
+type CommandFn<S> = dyn Fn(Arc<SessionData<S>>, Message, Vec<String>) -> BoxFuture<'static, CommandResult> + Send + Sync;
+
+/// Parses the messages that start with a prefix, and runs the command with the given name or alias.
+/// Call [`handle`] from the message create handler.
+///
+/// ```rust,ignore
+/// let framework = Arc::new(
+///     Framework::new()
+///         .prefix("!")
+///         .command(
+///             Command::new("ping", |s, msg, _args| async move {
+///                 msg.reply(&s.http, "pong").await?;
+///                 Ok(())
+///             })
+///             .alias("p")
+///             .description("Answers with pong"),
+///         ),
+/// );
+///
+/// client.on_message_create(move |s, msg| {
+///     let framework = framework.clone();
+///     async move {
+///         framework.handle(s, &msg).await?;
+///         Ok(())
+///     }
+/// });
+/// ```
+///
+/// [`handle`]: #method.handle
+pub struct Framework<S> {
+    prefixes: Vec<String>,
+    commands: Vec<Command<S>>,
+    help: bool,
+}
+
+/// A command of the [`Framework`], the handler receives the session, the message and the
+/// arguments after the command name
+///
+/// [`Framework`]: struct.Framework.html
+pub struct Command<S> {
+    name: String,
+    aliases: Vec<String>,
+    description: Option<String>,
+    handler: Box<CommandFn<S>>,
+}
+
+impl<S> Command<S> {
+    pub fn new<F, Fut>(name: impl Into<String>, func: F) -> Self
+    where
+        F: Fn(Arc<SessionData<S>>, Message, Vec<String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CommandResult> + Send + 'static,
+    {
+        Command {
+            name: name.into(),
+            aliases: vec![],
+            description: None,
+            handler: Box::new(move |s, msg, args| func(s, msg, args).boxed()),
+        }
+    }
+
+    /// Add another name for this command
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+
+        self
+    }
+
+    /// Set the description shown by the help command
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+
+        self
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    }
+}
+
+impl<S: Send + Sync + 'static> Framework<S> {
+    /// Create a framework without prefixes and with the help command enabled
+    pub fn new() -> Self {
+        Framework {
+            prefixes: vec![],
+            commands: vec![],
+            help: true,
+        }
+    }
+
+    /// Add a prefix, when many prefixes match the message the longest one is used
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefixes.push(prefix.into());
+
+        self
+    }
+
+    /// Add a command
+    pub fn command(mut self, command: Command<S>) -> Self {
+        self.commands.push(command);
+
+        self
+    }
+
+    /// Enable or disable the built-in `help` command, it lists the commands and their
+    /// descriptions. A command named `help` replaces it. Default true
+    pub fn help(mut self, enabled: bool) -> Self {
+        self.help = enabled;
+
+        self
+    }
+
+    /// Runs the command of the message, returns true if a command was found.
+    /// Messages sent by bots are ignored
+    pub async fn handle(&self, session: Arc<SessionData<S>>, msg: &Message) -> Result<bool, Box<dyn Error>> {
+        if msg.author.bot {
+            return Ok(false);
+        }
+
+        let content = match self.strip_prefix(&msg.content) {
+            Some(content) => content,
+            None => return Ok(false),
+        };
+
+        let mut args = parse_args(content);
+        if args.is_empty() {
+            return Ok(false);
+        }
+        let name = args.remove(0);
+
+        if let Some(command) = self.commands.iter().find(|c| c.matches(&name)) {
+            (command.handler)(session, msg.clone(), args).await?;
+            return Ok(true);
+        }
+
+        if self.help && name.eq_ignore_ascii_case("help") {
+            session.http.send_message(&msg.channel_id, self.help_message()).await?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the content after the longest prefix that matches
+    fn strip_prefix<'a>(&self, content: &'a str) -> Option<&'a str> {
+        self.prefixes
+            .iter()
+            .filter(|p| !p.is_empty() && content.starts_with(p.as_str()))
+            .max_by_key(|p| p.len())
+            .map(|p| &content[p.len()..])
+    }
+
+    fn help_message(&self) -> String {
+        let prefix = self.prefixes.first().map(String::as_str).unwrap_or("");
+        let mut help = String::from("**Commands**\n");
+
+        for command in &self.commands {
+            help.push_str(&format!("`{}{}`", prefix, command.name));
+
+            if !command.aliases.is_empty() {
+                help.push_str(&format!(" ({})", command.aliases.join(", ")));
+            }
+
+            if let Some(description) = &command.description {
+                help.push_str(&format!(": {}", description));
+            }
+
+            help.push('\n');
+        }
+
+        help
+    }
+}
+
+impl<S: Send + Sync + 'static> Default for Framework<S> {
+    fn default() -> Self {
+        Framework::new()
+    }
+}
+
+/// Split the arguments by whitespace, the text between double quotes is a single argument.
+/// A quote without closing quote takes the rest of the message
+pub fn parse_args(input: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    // Used to keep empty quoted arguments, like ""
+    let mut has_arg = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+
+    if has_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(name: &str) -> Command<()> {
+        Command::new(name, |_s, _msg, _args| async { Ok(()) })
+    }
+
+    #[test]
+    fn parse_quoted_args() {
+        assert_eq!(parse_args("ban  @panda 7"), vec!["ban", "@panda", "7"]);
+        assert_eq!(parse_args(r#"say "hello world" !"#), vec!["say", "hello world", "!"]);
+        assert_eq!(parse_args(r#"say "" x"#), vec!["say", "", "x"]);
+        assert_eq!(parse_args(r#"say "not closed"#), vec!["say", "not closed"]);
+        assert!(parse_args("   ").is_empty());
+    }
+
+    #[test]
+    fn strip_longest_prefix() {
+        let framework = Framework::<()>::new().prefix("!").prefix("!!").prefix("");
+
+        assert_eq!(framework.strip_prefix("!!ping"), Some("ping"));
+        assert_eq!(framework.strip_prefix("!ping"), Some("ping"));
+        assert_eq!(framework.strip_prefix("ping"), None);
+    }
+
+    #[test]
+    fn match_name_and_aliases() {
+        let ping = command("ping").alias("p");
+
+        assert!(ping.matches("ping"));
+        assert!(ping.matches("PING"));
+        assert!(ping.matches("P"));
+        assert!(!ping.matches("pong"));
+    }
+
+    #[test]
+    fn help_lists_commands() {
+        let framework = Framework::new()
+            .prefix("!")
+            .command(command("ping").alias("p").description("Answers with pong"))
+            .command(command("ban"));
+
+        assert_eq!(
+            framework.help_message(),
+            "**Commands**\n`!ping` (p): Answers with pong\n`!ban`\n"
+        );
+    }
+}
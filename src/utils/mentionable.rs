@@ -0,0 +1,125 @@
+//! Mentionable trait, to build the mention string of discord objects
+
+use crate::models::{
+    channel::{Channel, MentionChannel},
+    emoji::Emoji,
+    guild::{GuildMember, Role},
+    user::User,
+};
+
+/// Discord objects that can be mentioned in a message
+pub trait Mentionable {
+    /// Returns the mention string of the object, like `<@id>` for users
+    fn mention(&self) -> String;
+}
+
+impl Mentionable for User {
+    fn mention(&self) -> String {
+        format!("<@{}>", self.id)
+    }
+}
+
+impl Mentionable for GuildMember {
+    /// Members with nickname are mentioned with `<@!id>`. It returns an empty
+    /// string if the member doesn't have the user field
+    fn mention(&self) -> String {
+        match (&self.user, &self.nick) {
+            (Some(user), Some(_)) => format!("<@!{}>", user.id),
+            (Some(user), None) => user.mention(),
+            (None, _) => String::new(),
+        }
+    }
+}
+
+impl Mentionable for Role {
+    fn mention(&self) -> String {
+        format!("<@&{}>", self.id)
+    }
+}
+
+impl Mentionable for Channel {
+    fn mention(&self) -> String {
+        format!("<#{}>", self.id)
+    }
+}
+
+impl Mentionable for MentionChannel {
+    fn mention(&self) -> String {
+        format!("<#{}>", self.id)
+    }
+}
+
+impl Mentionable for Emoji {
+    /// Custom emojis are mentioned with `<:name:id>` (or `<a:name:id>` if animated),
+    /// unicode emojis just return its name
+    fn mention(&self) -> String {
+        let name = self.name.as_deref().unwrap_or_default();
+
+        match &self.id {
+            Some(id) if self.animated.unwrap_or(false) => format!("<a:{}:{}>", name, id),
+            Some(id) => format!("<:{}:{}>", name, id),
+            None => name.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn user() -> User {
+        serde_json::from_value(json!({"id": "1", "username": "user", "discriminator": "0001", "avatar": null})).unwrap()
+    }
+
+    #[test]
+    fn mention_users_and_members() {
+        assert_eq!(user().mention(), "<@1>");
+
+        let member = |nick: Option<&str>, user: Option<User>| -> GuildMember {
+            serde_json::from_value(json!({
+                "user": user,
+                "nick": nick,
+                "roles": [],
+                "joined_at": null,
+                "premium_since": null,
+                "deaf": false,
+                "mute": false
+            }))
+            .unwrap()
+        };
+        assert_eq!(member(None, Some(user())).mention(), "<@1>");
+        assert_eq!(member(Some("nick"), Some(user())).mention(), "<@!1>");
+        assert_eq!(member(Some("nick"), None).mention(), "");
+    }
+
+    #[test]
+    fn mention_emojis() {
+        let emoji = |value| -> Emoji { serde_json::from_value(value).unwrap() };
+
+        assert_eq!(emoji(json!({"id": "2", "name": "panda"})).mention(), "<:panda:2>");
+        assert_eq!(emoji(json!({"id": "2", "name": "panda", "animated": true})).mention(), "<a:panda:2>");
+        assert_eq!(emoji(json!({"id": null, "name": "🐼"})).mention(), "🐼");
+    }
+
+    #[test]
+    fn mention_roles_and_channels() {
+        let role: Role = serde_json::from_value(json!({
+            "id": "5",
+            "name": "mods",
+            "color": 0,
+            "hoist": false,
+            "position": 1,
+            "permissions": 0,
+            "managed": false,
+            "mentionable": true
+        }))
+        .unwrap();
+        assert_eq!(role.mention(), "<@&5>");
+
+        let channel: MentionChannel =
+            serde_json::from_value(json!({"id": "3", "guild_id": "4", "type": 0, "name": "general"})).unwrap();
+        assert_eq!(channel.mention(), "<#3>");
+    }
+}
@@ -1 +1,11 @@
 pub mod builders;
+pub mod framework;
+pub mod mentionable;
+pub mod paginator;
+pub mod reaction_roles;
+pub(crate) mod random;
+
+pub use framework::Framework;
+pub use mentionable::Mentionable;
+pub use paginator::Paginator;
+pub use reaction_roles::ReactionRoles;
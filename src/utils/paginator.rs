@@ -0,0 +1,209 @@
+//! Reaction paginator, an embed with pages that are changed with ◀️ and ▶️ reactions
+
+use crate::{
+    client::SessionData,
+    error::{PandaError, Result},
+    models::{channel::Embed, gateway::events::MessageReactionAdd},
+    utils::builders::MessageEdit,
+};
+
+use std::time::Duration;
+
+const PREVIOUS: &str = "◀️";
+const NEXT: &str = "▶️";
+
+/// Sends the first page, and changes the page when a user reacts with ◀️ or ▶️. The paginator
+/// stops when no reaction is received before the timeout, then it removes the reactions.
+///
+/// The reactions of the users are removed to allow flipping again, without the **MANAGE_MESSAGES**
+/// permission they are kept, and at the end only the bot reactions are removed.
+///
+/// ```rust,ignore
+/// client.on_message_create(|s, msg| async move {
+///     if msg.content == "!pages" {
+///         let pages = vec![Embed::new().title("Page 1"), Embed::new().title("Page 2")];
+///
+///         Paginator::new(pages)
+///             .author(&msg.author.id)
+///             .timeout(Duration::from_secs(30))
+///             .run(&s, &msg.channel_id)
+///             .await?;
+///     }
+///     Ok(())
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Paginator {
+    pages: Vec<Embed>,
+    timeout: Duration,
+    author_id: Option<String>,
+}
+
+impl Paginator {
+    /// Create a paginator with the given pages, and a timeout of 60 seconds
+    pub fn new(pages: Vec<Embed>) -> Self {
+        Paginator {
+            pages,
+            timeout: Duration::from_secs(60),
+            author_id: None,
+        }
+    }
+
+    /// Set how long the paginator waits for a reaction, it starts again after each page change
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Only the reactions of this user change the page, by default any user can change it
+    pub fn author(mut self, user_id: impl Into<String>) -> Self {
+        self.author_id = Some(user_id.into());
+
+        self
+    }
+
+    /// Sends the paginator to the channel, and returns when it stops
+    pub async fn run<S>(self, session: &SessionData<S>, channel_id: impl AsRef<str>) -> Result<()> {
+        let http = &session.http;
+        let pages = self.pages;
+
+        if pages.is_empty() {
+            return Err(PandaError::Validation {
+                field: "pages",
+                reason: "the paginator needs at least one page".into(),
+            });
+        }
+
+        let msg = http.send_embed(channel_id, pages[0].clone()).await?;
+
+        // Nothing to paginate
+        if pages.len() == 1 {
+            return Ok(());
+        }
+
+        http.add_reaction(&msg.channel_id, &msg.id, PREVIOUS).await?;
+        http.add_reaction(&msg.channel_id, &msg.id, NEXT).await?;
+
+        let mut current: usize = 0;
+        let mut can_manage_messages = true;
+
+        loop {
+            let predicate = reaction_predicate(&msg.id, &msg.author.id, self.author_id.clone());
+
+            let reaction = match session.await_reaction(predicate, self.timeout).await {
+                Some(reaction) => reaction,
+                None => break,
+            };
+
+            let next = next_page(current, pages.len(), reaction.emoji.name.as_deref());
+
+            if can_manage_messages {
+                let emoji = reaction.emoji.name.as_deref().unwrap_or_default();
+                match http
+                    .remove_user_reaction(&msg.channel_id, &msg.id, &reaction.user_id, emoji)
+                    .await
+                {
+                    Err(PandaError::HttpForbidden) => {
+                        log::warn!("Missing MANAGE_MESSAGES permission, the paginator reactions will be kept");
+                        can_manage_messages = false;
+                    }
+                    res => res?,
+                }
+            }
+
+            if next != current {
+                let edit = MessageEdit::new().embed(pages[next].clone());
+                http.edit_message(&msg.channel_id, &msg.id, edit).await?;
+
+                current = next;
+            }
+        }
+
+        // Remove the controls
+        if can_manage_messages {
+            match http.remove_all_reactions(&msg.channel_id, &msg.id).await {
+                Err(PandaError::HttpForbidden) => {}
+                res => return res,
+            }
+        }
+
+        http.remove_own_reaction(&msg.channel_id, &msg.id, PREVIOUS).await?;
+        http.remove_own_reaction(&msg.channel_id, &msg.id, NEXT).await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the page after the reaction, it wraps around at the first and last pages
+fn next_page(current: usize, count: usize, emoji: Option<&str>) -> usize {
+    match emoji {
+        Some(PREVIOUS) => current.checked_sub(1).unwrap_or(count - 1),
+        _ => (current + 1) % count,
+    }
+}
+
+/// Matches the ◀️ and ▶️ reactions to the paginator message, the bot reactions are ignored
+fn reaction_predicate(
+    message_id: &str,
+    bot_id: &str,
+    author_id: Option<String>,
+) -> impl Fn(&MessageReactionAdd) -> bool + Send + Sync + 'static {
+    let message_id = message_id.to_string();
+    let bot_id = bot_id.to_string();
+
+    move |reaction| {
+        let is_control = matches!(reaction.emoji.name.as_deref(), Some(PREVIOUS) | Some(NEXT));
+        let is_author = author_id.as_ref().map_or(true, |id| id == &reaction.user_id);
+
+        reaction.message_id == message_id && reaction.user_id != bot_id && is_control && is_author
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn reaction(user_id: &str, emoji: &str) -> MessageReactionAdd {
+        serde_json::from_value(json!({
+            "user_id": user_id,
+            "channel_id": "290926798999357250",
+            "message_id": "334385199974967042",
+            "emoji": {"id": null, "name": emoji}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn pages_wrap_around() {
+        assert_eq!(next_page(0, 3, Some(NEXT)), 1);
+        assert_eq!(next_page(2, 3, Some(NEXT)), 0);
+        assert_eq!(next_page(1, 3, Some(PREVIOUS)), 0);
+        assert_eq!(next_page(0, 3, Some(PREVIOUS)), 2);
+    }
+
+    #[test]
+    fn predicate_matches_controls() {
+        let any_user = reaction_predicate("334385199974967042", "1", None);
+
+        assert!(any_user(&reaction("2", NEXT)));
+        assert!(any_user(&reaction("2", PREVIOUS)));
+        assert!(!any_user(&reaction("2", "🐼")));
+        // The bot reactions
+        assert!(!any_user(&reaction("1", NEXT)));
+
+        let mut other_message = reaction("2", NEXT);
+        other_message.message_id = "334385199974967043".into();
+        assert!(!any_user(&other_message));
+    }
+
+    #[test]
+    fn predicate_filters_author() {
+        let author_only = reaction_predicate("334385199974967042", "1", Some("2".into()));
+
+        assert!(author_only(&reaction("2", NEXT)));
+        assert!(!author_only(&reaction("3", NEXT)));
+    }
+}
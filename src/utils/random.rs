@@ -0,0 +1,23 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// Returns a random u64, std's RandomState is seeded randomly, so we avoid a new dependency.
+/// It isn't cryptographically secure, it's used for jitters and multipart boundaries
+pub(crate) fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_values_differ() {
+        let values: Vec<u64> = (0..8).map(|_| random_u64()).collect();
+        assert!(values.iter().any(|v| *v != values[0]));
+    }
+}
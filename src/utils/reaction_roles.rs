@@ -0,0 +1,161 @@
+//! Reaction roles, gives a role to the users who react with an emoji to a message
+
+use crate::{
+    error::Result,
+    http::HttpClient,
+    models::{
+        emoji::Emoji,
+        gateway::events::{MessageReactionAdd, MessageReactionRemove},
+    },
+};
+
+use std::collections::HashMap;
+
+/// Maps an emoji on a message to a role. Call [`handle_add`] and [`handle_remove`] from the
+/// reaction events handlers.
+///
+/// ```rust,ignore
+/// let reaction_roles = Arc::new(
+///     ReactionRoles::new()
+///         .ignore_user("bot user id")
+///         .add("message id", "🐼", "role id"),
+/// );
+///
+/// client.on_message_reaction_add(move |s, reaction| {
+///     let reaction_roles = reaction_roles.clone();
+///     async move {
+///         reaction_roles.handle_add(&s.http, &reaction).await?;
+///         Ok(())
+///     }
+/// });
+/// ```
+///
+/// [`handle_add`]: #method.handle_add
+/// [`handle_remove`]: #method.handle_remove
+#[derive(Debug, Default)]
+pub struct ReactionRoles {
+    // (message_id, emoji) -> role_id
+    roles: HashMap<(String, String), String>,
+    ignored_users: Vec<String>,
+}
+
+impl ReactionRoles {
+    pub fn new() -> Self {
+        ReactionRoles::default()
+    }
+
+    /// Add a new reaction role. The emoji is the unicode emoji, or `name:id` for custom emojis
+    pub fn add(mut self, message_id: impl Into<String>, emoji: impl Into<String>, role_id: impl Into<String>) -> Self {
+        self.roles.insert((message_id.into(), emoji.into()), role_id.into());
+
+        self
+    }
+
+    /// Reactions of this user will be ignored, it should be used with the bot user id
+    pub fn ignore_user(mut self, user_id: impl Into<String>) -> Self {
+        self.ignored_users.push(user_id.into());
+
+        self
+    }
+
+    /// Gives the configured role to the user, returns true if the reaction matched a role
+    pub async fn handle_add(&self, http: &HttpClient, reaction: &MessageReactionAdd) -> Result<bool> {
+        // Bots reactions are ignored
+        let is_bot = reaction.member.as_ref().and_then(|m| m.user.as_ref()).map_or(false, |u| u.bot);
+        if is_bot {
+            return Ok(false);
+        }
+
+        let guild_id = match &reaction.guild_id {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+
+        match self.role_for(&reaction.user_id, &reaction.message_id, &reaction.emoji) {
+            Some(role_id) => {
+                http.add_member_role(guild_id, &reaction.user_id, role_id).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Removes the configured role from the user, returns true if the reaction matched a role
+    pub async fn handle_remove(&self, http: &HttpClient, reaction: &MessageReactionRemove) -> Result<bool> {
+        let guild_id = match &reaction.guild_id {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+
+        match self.role_for(&reaction.user_id, &reaction.message_id, &reaction.emoji) {
+            Some(role_id) => {
+                http.remove_member_role(guild_id, &reaction.user_id, role_id).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn role_for(&self, user_id: &str, message_id: &str, emoji: &Emoji) -> Option<&String> {
+        if self.ignored_users.iter().any(|id| id == user_id) {
+            return None;
+        }
+
+        let emoji = match (&emoji.name, &emoji.id) {
+            (Some(name), Some(id)) => format!("{}:{}", name, id),
+            (Some(name), None) => name.clone(),
+            _ => return None,
+        };
+
+        self.roles.get(&(message_id.to_string(), emoji))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    fn emoji(name: &str, id: Option<&str>) -> Emoji {
+        serde_json::from_value(json!({"id": id, "name": name})).unwrap()
+    }
+
+    #[test]
+    fn roles_of_emojis() {
+        let reaction_roles = ReactionRoles::new()
+            .ignore_user("1")
+            .add("334385199974967042", "🐼", "41771983423143936")
+            .add("334385199974967042", "panda:41771983429993937", "41771983423143937");
+
+        let role = |user_id, emoji| reaction_roles.role_for(user_id, "334385199974967042", &emoji).cloned();
+
+        assert_eq!(role("2", emoji("🐼", None)), Some("41771983423143936".into()));
+        assert_eq!(
+            role("2", emoji("panda", Some("41771983429993937"))),
+            Some("41771983423143937".into())
+        );
+        assert_eq!(role("2", emoji("panda", None)), None);
+        assert_eq!(role("1", emoji("🐼", None)), None);
+        assert_eq!(
+            reaction_roles.role_for("2", "334385199974967043", &emoji("🐼", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn reactions_outside_guilds_are_ignored() {
+        let http = HttpClient::new("token");
+        let reaction_roles = ReactionRoles::new().add("334385199974967042", "🐼", "41771983423143936");
+        let reaction: MessageReactionAdd = serde_json::from_value(json!({
+            "user_id": "2",
+            "channel_id": "290926798999357250",
+            "message_id": "334385199974967042",
+            "emoji": {"id": null, "name": "🐼"}
+        }))
+        .unwrap();
+
+        assert!(!block_on(reaction_roles.handle_add(&http, &reaction)).unwrap());
+    }
+}